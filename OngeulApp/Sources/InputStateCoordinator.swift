@@ -1,15 +1,45 @@
+import Carbon
+import Cocoa
 import os.log
 
 private let log = OSLog(subsystem: "io.github.hiking90.inputmethod.Ongeul", category: "coordinator")
 
-/// 상태 전이 로직 전담. Engine, PerAppModeStore, EnglishLockStore를 소유.
+/// 상태 전이 로직 전담. Engine과 앱별 모드 정책(ModePolicy)을 소유.
 /// UI 의존성 없음. KeyEventTap.currentInputMode 동기화는 CGEvent 레벨의 상태 동기화이므로 여기서 담당.
 final class InputStateCoordinator: FocusStealModeController {
-    private let engine = HangulEngine()
-    private let perAppStore = PerAppModeStore()
-    private let lockStore = EnglishLockStore()
+    private let hangulEngine = HangulEngine()
+    private let policy: ModePolicy
     private(set) var activeAppBundleId: String?
 
+    /// 키 입력 기록 파일 경로 (버그 보고용). 설정되어 있으면 시작할 때 기록을 켠다.
+    static let recordInputPathKey = "recordInputPath"
+
+    init(policy: ModePolicy = .openDefault(), defaults: UserDefaults = .standard) {
+        self.policy = policy
+        // 정책은 잠시 모아서 저장하므로 종료 직전의 변경을 놓치지 않도록 바로 쓴다
+        NotificationCenter.default.addObserver(
+            forName: NSApplication.willTerminateNotification, object: nil, queue: .main
+        ) { _ in
+            try? policy.save()
+        }
+        if let path = defaults.string(forKey: Self.recordInputPathKey) {
+            do {
+                try engine.startRecording(path: path)
+            } catch {
+                os_log("input recording failed: %{public}@", log: log, type: .error,
+                       String(describing: error))
+            }
+        }
+    }
+
+    /// 엔진 호출 직전에 보안 입력 상태를 엔진에 알린다.
+    /// 암호 필드 등 보안 입력 중의 호출은 기록하지 않는다. (기록 중이 아니면 무시됨)
+    /// 포커스·앱 전환·모드 전환처럼 키 이벤트가 아닌 호출도 모두 이 경로를 거친다.
+    private var engine: HangulEngine {
+        hangulEngine.setRecordingExcluded(excluded: IsSecureEventInputEnabled())
+        return hangulEngine
+    }
+
     // MARK: - Read-only
 
     var mode: InputMode { engine.getMode() }
@@ -19,7 +49,7 @@ final class InputStateCoordinator: FocusStealModeController {
 
     func isLocked(_ bundleId: String?) -> Bool {
         guard let bundleId else { return false }
-        return lockStore.isLocked(bundleId)
+        return policy.isLocked(bundleId: bundleId)
     }
 
     // MARK: - Engine passthrough
@@ -28,7 +58,12 @@ final class InputStateCoordinator: FocusStealModeController {
     // State transition 메서드로 승격해야 한다.
 
     func processKey(key: String) -> ProcessResult { engine.processKey(key: key) }
+    func handleEvent(_ event: KeyEvent, shiftSpaceToggle: Bool) -> KeyEventResult {
+        engine.handleEvent(event: event, shiftSpaceToggle: shiftSpaceToggle)
+    }
     func backspace() -> ProcessResult { engine.backspace() }
+    /// 직접 확정 모드를 켜거나 끈다. 전환 전의 조합은 확정되어 반환된다.
+    func setDirectCommit(_ enabled: Bool) -> ProcessResult { engine.setDirectCommit(enabled: enabled) }
     func flush() -> ProcessResult { engine.flush() }
 
     // MARK: - Private: 모드 변경 + KeyEventTap / CapsLock LED 동기화
@@ -68,10 +103,10 @@ final class InputStateCoordinator: FocusStealModeController {
 
     // MARK: - Layout loading
 
-    /// 레이아웃 로드. 초기 로드 시 영문 모드 설정, 재로드 시 조합 flush.
-    func loadLayout(json: String, isInitialLoad: Bool) throws -> ProcessResult? {
+    /// 동봉 레이아웃을 ID로 로드. 초기 로드 시 영문 모드 설정, 재로드 시 조합 flush.
+    func loadLayout(id: String, isInitialLoad: Bool) throws -> ProcessResult? {
         let flushResult = isInitialLoad ? nil : engine.flush()
-        try engine.loadLayout(json: json)
+        try engine.loadBuiltinLayout(id: id)
         if isInitialLoad { setMode(.english) }
         return flushResult
     }
@@ -92,7 +127,7 @@ final class InputStateCoordinator: FocusStealModeController {
         if isAppSwitch { engine.reset() }
 
         // English Lock 우선
-        if lockStore.isLocked(bundleId) {
+        if policy.isLocked(bundleId: bundleId) {
             setMode(.english)
             return StateEffect(
                 lockOverlay: isAppSwitch ? .show(locked: true) : nil
@@ -101,14 +136,14 @@ final class InputStateCoordinator: FocusStealModeController {
 
         // 모드 결정 우선순위:
         // 1. systemMode가 현재 엔진 모드와 다르면 → 사용자가 메뉴바에서 직접 전환. TIS 우선.
-        // 2. per-app 저장 모드 → 앱별 기억 복원.
+        // 2. 정책 → 앱별 기억 복원, 없으면 앱별 기본 모드 규칙.
         // 3. systemMode (fallback) → 최초 활성화 시 TIS 따름.
         // 4. .english (최종 기본값).
         let mode: InputMode
         if let systemMode, systemMode != engine.getMode() {
             mode = systemMode
-        } else if let stored = perAppStore.savedMode(for: bundleId) {
-            mode = stored
+        } else if let policyMode = policy.activateApp(bundleId: bundleId) {
+            mode = policyMode
         } else if let systemMode {
             mode = systemMode
         } else {
@@ -121,11 +156,11 @@ final class InputStateCoordinator: FocusStealModeController {
         let flushResult = (!isAppSwitch && engine.getMode() == .korean && mode == .english)
             ? engine.flush() : nil
         setMode(mode)
-        perAppStore.saveMode(mode, for: bundleId)
+        policy.saveMode(bundleId: bundleId, mode: mode)
 
         // 앱 전환 시 이전 앱과 모드가 다르면 아이콘 동기화
         let prevMode = isAppSwitch
-            ? activeAppBundleId.flatMap { perAppStore.savedMode(for: $0) }
+            ? activeAppBundleId.flatMap { policy.lastMode(bundleId: $0) }
             : nil
         let modeChanged = prevMode != nil && prevMode != mode
 
@@ -142,7 +177,7 @@ final class InputStateCoordinator: FocusStealModeController {
 
         let result = toggleEngineMode()
         let newMode = engine.getMode()
-        if let bundleId { perAppStore.saveMode(newMode, for: bundleId) }
+        if let bundleId { policy.saveMode(bundleId: bundleId, mode: newMode) }
 
         return StateEffect(processResult: result, modeChanged: true)
     }
@@ -161,23 +196,22 @@ final class InputStateCoordinator: FocusStealModeController {
         // 한글 → 영문 전환 시 조합 중인 한글 flush
         let flushResult = (engine.getMode() == .korean && newMode != .korean) ? engine.flush() : nil
         setMode(newMode, syncCapsLock: false)
-        if let bundleId { perAppStore.saveMode(newMode, for: bundleId) }
+        if let bundleId { policy.saveMode(bundleId: bundleId, mode: newMode) }
         return StateEffect(processResult: flushResult, modeChanged: true)
     }
 
     /// English Lock 토글
     func toggleLock(for bundleId: String) -> StateEffect {
-        if lockStore.isLocked(bundleId) {
-            // 해제: 저장된 이전 모드 복원 + detector 리셋
-            let previousMode = lockStore.removeLock(for: bundleId) ?? .korean
-            setMode(previousMode)
+        if policy.isLocked(bundleId: bundleId) {
+            // 해제: 정책이 정한 모드(기본: 잠그기 전 모드) 복원 + detector 리셋
+            let restoredMode = policy.unlock(bundleId: bundleId)
+            setMode(restoredMode)
             engine.reset()  // 잠금 중 쌓인 detector 키 시퀀스 무효화
-            perAppStore.saveMode(previousMode, for: bundleId)
             return StateEffect(lockOverlay: .show(locked: false))
         } else {
             // 잠금: 현재 모드 저장 -> 영어 강제
             let currentMode = engine.getMode()
-            lockStore.addLock(for: bundleId, previousMode: currentMode)
+            policy.lock(bundleId: bundleId, currentMode: currentMode)
             let flushResult = (currentMode == .korean) ? engine.flush() : nil
             setMode(.english)
             return StateEffect(
@@ -200,7 +234,7 @@ final class InputStateCoordinator: FocusStealModeController {
     func setModeFromExternal(_ mode: InputMode, for bundleId: String?) -> ProcessResult? {
         let flushResult = (self.mode == .korean) ? engine.flush() : nil
         setMode(mode)
-        if let bundleId { perAppStore.saveMode(mode, for: bundleId) }
+        if let bundleId { policy.saveMode(bundleId: bundleId, mode: mode) }
         return flushResult
     }
 
@@ -208,7 +242,7 @@ final class InputStateCoordinator: FocusStealModeController {
     func escapeToEnglish(for bundleId: String?, enabled: Bool) -> StateEffect? {
         guard enabled, engine.getMode() == .korean else { return nil }
         setMode(.english)
-        if let bundleId { perAppStore.saveMode(.english, for: bundleId) }
+        if let bundleId { policy.saveMode(bundleId: bundleId, mode: .english) }
         return StateEffect(modeChanged: true)
     }
 
@@ -216,7 +250,7 @@ final class InputStateCoordinator: FocusStealModeController {
     /// activateApp이 복원한 모드와 무관하게 한글 모드를 강제한다.
     /// correctFocusSteal에서만 호출되며, 버퍼된 키를 한글로 리플레이하기 위한 전제 조건.
     ///
-    /// perAppStore는 의도적으로 갱신하지 않는다: focus-steal은 *일시적* 보정이며,
+    /// 정책의 앱별 모드는 의도적으로 갱신하지 않는다: focus-steal은 *일시적* 보정이며,
     /// 사용자의 명시적 의도(토글/메뉴바/외부 변경/비활성화 시 저장)와 구분되어야 한다.
     /// 사용자가 그 앱을 한글 기본으로 쓰고자 한다면 다른 경로(토글 등)에서 학습된다.
     func forceKoreanForReplay() -> ProcessResult {
@@ -228,7 +262,7 @@ final class InputStateCoordinator: FocusStealModeController {
     /// 비활성화: 모드 저장 + flush. 호출자가 Chromium 여부에 따라 적용 결정.
     func deactivate(for bundleId: String?) -> ProcessResult {
         if let bundleId {
-            perAppStore.saveMode(engine.getMode(), for: bundleId)
+            policy.saveMode(bundleId: bundleId, mode: engine.getMode())
         }
         return engine.flush()
     }
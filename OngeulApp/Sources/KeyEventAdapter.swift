@@ -0,0 +1,45 @@
+import AppKit
+
+// 키 누름 라우팅(Backspace/Enter/Escape/단축키/CapsLock 보정)은 Rust `HangulEngine.handleEvent`에 있다.
+// 여기서는 NSEvent를 플랫폼 중립 `KeyEvent`(USB HID usage 키코드)로 바꿔 넘긴다.
+
+extension KeyEvent {
+    init(_ event: NSEvent) {
+        let flags = event.modifierFlags
+        self.init(
+            keycode: KeyEvent.hidUsage(keyCode: event.keyCode),
+            characters: event.characters,
+            modifiers: ModifierFlags(flags),
+            capsLock: flags.contains(.capsLock)
+        )
+    }
+
+    /// macOS 가상 키코드 → USB HID usage ID (Keyboard page). 표에 없는 키는 0.
+    static func hidUsage(keyCode: UInt16) -> UInt32 {
+        hidUsageTable[keyCode] ?? 0
+    }
+
+    /// kVK_* → HID usage. 핫패스에서 매 키마다 할당하지 않도록 static으로 한 번만 생성한다.
+    private static let hidUsageTable: [UInt16: UInt32] = [
+        // 문자 키 (ANSI)
+        0x00: 0x04, 0x0B: 0x05, 0x08: 0x06, 0x02: 0x07, 0x0E: 0x08, 0x03: 0x09,  // A B C D E F
+        0x05: 0x0A, 0x04: 0x0B, 0x22: 0x0C, 0x26: 0x0D, 0x28: 0x0E, 0x25: 0x0F,  // G H I J K L
+        0x2E: 0x10, 0x2D: 0x11, 0x1F: 0x12, 0x23: 0x13, 0x0C: 0x14, 0x0F: 0x15,  // M N O P Q R
+        0x01: 0x16, 0x11: 0x17, 0x20: 0x18, 0x09: 0x19, 0x0D: 0x1A, 0x07: 0x1B,  // S T U V W X
+        0x10: 0x1C, 0x06: 0x1D,                                                  // Y Z
+        0x12: 0x1E, 0x13: 0x1F, 0x14: 0x20, 0x15: 0x21, 0x17: 0x22,              // 1 2 3 4 5
+        0x16: 0x23, 0x1A: 0x24, 0x1C: 0x25, 0x19: 0x26, 0x1D: 0x27,              // 6 7 8 9 0
+        0x1B: 0x2D, 0x18: 0x2E, 0x21: 0x2F, 0x1E: 0x30, 0x2A: 0x31,              // - = [ ] \
+        0x29: 0x33, 0x27: 0x34, 0x32: 0x35, 0x2B: 0x36, 0x2F: 0x37, 0x2C: 0x38,  // ; ' ` , . /
+        0x0A: 0x64,                                                              // ISO §
+        // 편집/제어 키
+        0x24: 0x28, 0x35: 0x29, 0x33: 0x2A, 0x30: 0x2B, 0x31: 0x2C,              // Return Esc Delete Tab Space
+        0x73: 0x4A, 0x74: 0x4B, 0x75: 0x4C, 0x77: 0x4D, 0x79: 0x4E,              // Home PgUp FwdDel End PgDn
+        0x7C: 0x4F, 0x7B: 0x50, 0x7D: 0x51, 0x7E: 0x52,                          // → ← ↓ ↑
+        // 키패드
+        0x47: 0x53, 0x4B: 0x54, 0x43: 0x55, 0x4E: 0x56, 0x45: 0x57, 0x4C: 0x58,  // Clear / * - + Enter
+        0x53: 0x59, 0x54: 0x5A, 0x55: 0x5B, 0x56: 0x5C, 0x57: 0x5D,              // 1 2 3 4 5
+        0x58: 0x5E, 0x59: 0x5F, 0x5B: 0x60, 0x5C: 0x61, 0x52: 0x62,              // 6 7 8 9 0
+        0x41: 0x63, 0x51: 0x67,                                                  // . =
+    ]
+}
@@ -0,0 +1,63 @@
+import Foundation
+import os.log
+
+private let log = OSLog(subsystem: "io.github.hiking90.inputmethod.Ongeul", category: "policy")
+
+// 앱별 모드 기억·English Lock·앱별 기본 모드 정책은 Rust `ModePolicy`(ongeul-automata)에 있다.
+// 여기서는 정책 파일 위치를 정하고 UserDefaults에 남아 있는 이전 English Lock 목록을 옮긴다.
+
+extension ModePolicy {
+    /// 사용자 정책 파일: ~/Library/Application Support/Ongeul/mode-policy.json5
+    static var userPolicyURL: URL? {
+        FileManager.default.urls(for: .applicationSupportDirectory, in: .userDomainMask).first?
+            .appendingPathComponent("Ongeul/mode-policy.json5")
+    }
+
+    /// 팀 공유 정책 파일 (관리자 배포용): /Library/Application Support/Ongeul/mode-policy.json5
+    static let sharedPolicyURL = URL(fileURLWithPath: "/Library/Application Support/Ongeul/mode-policy.json5")
+
+    /// 이전 버전이 English Lock을 저장하던 UserDefaults 키 (bundleId → 잠금 직전 모드)
+    static let legacyLockKey = "EnglishLockApps"
+
+    /// 사용자 정책 파일을 열고 공유 정책을 합친다. 파일이 깨졌으면 메모리 정책으로 동작한다.
+    static func openDefault(defaults: UserDefaults = .standard) -> ModePolicy {
+        var policy = ModePolicy()
+        if let url = userPolicyURL {
+            do {
+                policy = try ModePolicy.open(path: url.path)
+            } catch {
+                os_log("mode policy open failed: %{public}@", log: log, type: .error,
+                       String(describing: error))
+            }
+        }
+        if FileManager.default.fileExists(atPath: sharedPolicyURL.path) {
+            do {
+                try policy.loadShared(path: sharedPolicyURL.path)
+            } catch {
+                os_log("shared mode policy load failed: %{public}@", log: log, type: .error,
+                       String(describing: error))
+            }
+        }
+        policy.migrateLegacyLocks(from: defaults)
+        return policy
+    }
+
+    /// UserDefaults의 이전 English Lock 목록을 정책으로 옮긴다.
+    /// 정책 파일에 저장된 뒤에만 키를 지운다. (메모리 정책이거나 저장에 실패하면 다음 실행에 다시 옮긴다)
+    func migrateLegacyLocks(from defaults: UserDefaults) {
+        guard let legacy = defaults.dictionary(forKey: Self.legacyLockKey) as? [String: String]
+        else { return }
+        for (bundleId, raw) in legacy {
+            lock(bundleId: bundleId, currentMode: raw == "korean" ? .korean : .english)
+        }
+        do {
+            try save()
+        } catch {
+            os_log("legacy English Lock kept, policy not saved: %{public}@", log: log, type: .error,
+                   String(describing: error))
+            return
+        }
+        defaults.removeObject(forKey: Self.legacyLockKey)
+        os_log("migrated %d legacy English Lock entries", log: log, type: .info, legacy.count)
+    }
+}
@@ -126,7 +126,10 @@ private final class PreferencesPanel {
     private let layoutPopup: NSPopUpButton
     private let escapeCheckbox: NSButton
     private let inputSourceLockCheckbox: NSButton
+    private let directCommitCheckbox: NSButton
     private let toggleKeyTitles: [(ToggleKey, String)]
+    /// 한글 자판 (ID, 제목). 엔진에 동봉된 레이아웃 중 영문 배열을 뺀 목록.
+    private let layoutTitles: [(String, String)]
 
     private init() {
         toggleKeyTitles = [
@@ -164,9 +167,11 @@ private final class PreferencesPanel {
         // -- 한글 자판 --
         let layoutLabel = NSTextField(labelWithString: NSLocalizedString("prefs.layout.label", comment: ""))
         layoutPopup = NSPopUpButton(frame: .zero, pullsDown: false)
-        layoutPopup.addItem(withTitle: NSLocalizedString("prefs.layout.2standard", comment: ""))
-        layoutPopup.addItem(withTitle: NSLocalizedString("prefs.layout.3_390", comment: ""))
-        layoutPopup.addItem(withTitle: NSLocalizedString("prefs.layout.3final", comment: ""))
+        // 번역이 없는 자판은 레이아웃 이름을 그대로 쓴다. 실험용 자판은 목록에 넣지 않는다.
+        layoutTitles = listLayouts()
+            .filter { $0.layoutType != .latin && !$0.experimental }
+            .map { ($0.id, NSLocalizedString("prefs.layout.\($0.id)", value: $0.name, comment: "")) }
+        layoutPopup.addItems(withTitles: layoutTitles.map { $0.1 })
 
         // 라벨-팝업 그리드 (열 정렬)
         let settingsGrid = NSGridView(views: [
@@ -192,6 +197,13 @@ private final class PreferencesPanel {
             target: nil, action: nil
         )
 
+        // -- 직접 확정 --
+        directCommitCheckbox = NSButton(
+            checkboxWithTitle: NSLocalizedString("prefs.directCommit", comment: ""),
+            target: nil, action: nil
+        )
+        directCommitCheckbox.toolTip = NSLocalizedString("prefs.directCommit.tooltip", comment: "")
+
         // -- 버전 및 개발자 정보 --
         let separator = NSBox()
         separator.boxType = .separator
@@ -262,7 +274,7 @@ private final class PreferencesPanel {
         container.addArrangedSubview(headerGroup)
 
         // 그리드 + 체크박스를 하나의 설정 그룹으로 묶어 정렬
-        let checkboxGroup = NSStackView(views: [escapeCheckbox, inputSourceLockCheckbox])
+        let checkboxGroup = NSStackView(views: [escapeCheckbox, inputSourceLockCheckbox, directCommitCheckbox])
         checkboxGroup.orientation = .vertical
         checkboxGroup.alignment = .leading
         checkboxGroup.spacing = 8
@@ -301,14 +313,12 @@ private final class PreferencesPanel {
         let currentToggleIndex = toggleKeyTitles.firstIndex { $0.0 == OngeulInputController.toggleKey } ?? 0
         togglePopup.selectItem(at: currentToggleIndex)
 
-        switch OngeulInputController.savedLayoutId {
-        case "3-390": layoutPopup.selectItem(at: 1)
-        case "3-final": layoutPopup.selectItem(at: 2)
-        default: layoutPopup.selectItem(at: 0)
-        }
+        let currentLayoutIndex = layoutTitles.firstIndex { $0.0 == OngeulInputController.savedLayoutId } ?? 0
+        layoutPopup.selectItem(at: currentLayoutIndex)
 
         escapeCheckbox.state = OngeulInputController.escapeToEnglish ? .on : .off
         inputSourceLockCheckbox.state = OngeulInputController.inputSourceLock ? .on : .off
+        directCommitCheckbox.state = OngeulInputController.directCommit ? .on : .off
 
         panel.center()
         panel.orderFrontRegardless()
@@ -353,15 +363,11 @@ private final class PreferencesPanel {
             CapsLockSync.setState(false)
         }
 
-        let newLayout: String
-        switch layoutPopup.indexOfSelectedItem {
-        case 1: newLayout = "3-390"
-        case 2: newLayout = "3-final"
-        default: newLayout = "2-standard"
-        }
+        let newLayout = layoutTitles[layoutPopup.indexOfSelectedItem].0
         OngeulInputController.savedLayoutId = newLayout
         OngeulInputController.escapeToEnglish = escapeCheckbox.state == .on
         OngeulInputController.inputSourceLock = inputSourceLockCheckbox.state == .on
+        OngeulInputController.directCommit = directCommitCheckbox.state == .on
 
         os_log("Settings saved: toggleKey=%{public}@ layoutId=%{public}@ escapeToEnglish=%{public}d",
                log: log, type: .default,
@@ -465,7 +471,7 @@ class OngeulInputController: IMKInputController {
 
     private var loadedLayoutId: String?
     /// 로드/파싱에 실패한 레이아웃 id. 키 입력마다 `loadLayoutIfNeeded`가 호출되므로,
-    /// 깨진 레이아웃에 대해 매 키스트로크마다 파일 IO·파싱·에러 로그가 반복되는 것을 막는다.
+    /// 깨진 레이아웃에 대해 매 키스트로크마다 파싱·에러 로그가 반복되는 것을 막는다.
     /// 설정에서 다른 레이아웃으로 바꾸면 desiredLayoutId가 달라져 자연히 재시도된다.
     private var failedLayoutId: String?
     private var toggleDetector = ToggleDetector()
@@ -474,6 +480,7 @@ class OngeulInputController: IMKInputController {
     private enum SyntheticEvent: Int64 {
         case enter = 0x4F6E_0001         // 기존 syntheticEnterMarker(0x4F6E6765)에서 변경
         case autoSwitch = 0x4F6E_0002    // Phase 3에서 사용
+        case replacement = 0x4F6E_0003   // selectedRange를 모르는 client의 글자 교체
 
         static func from(_ event: NSEvent) -> SyntheticEvent? {
             guard let cgEvent = event.cgEvent else { return nil }
@@ -503,11 +510,6 @@ class OngeulInputController: IMKInputController {
         return c
     }()
 
-    #if DEBUG
-    /// 테스트에서 Bundle.main 대신 사용할 레이아웃 디렉토리 URL
-    var testLayoutsURL: URL?
-    #endif
-
     // Chromium-based apps auto-commit marked text on focus loss via their
     // renderer process (resignFirstResponder → Blur → ImeFinishComposingText).
     // Detected at runtime by looking for "*Helper (Renderer).app" inside the
@@ -559,6 +561,7 @@ class OngeulInputController: IMKInputController {
     private static let layoutIdKey = "layoutId"
     private static let escapeToEnglishKey = "escapeToEnglish"
     private static let inputSourceLockKey = "inputSourceLock"
+    private static let directCommitKey = "directCommit"
 
     fileprivate static var toggleKey: ToggleKey {
         get {
@@ -606,6 +609,12 @@ class OngeulInputController: IMKInputController {
         }
     }
 
+    /// 직접 확정 모드 (기본 꺼짐). 입력 창이 활성화될 때 엔진에 적용한다.
+    fileprivate static var directCommit: Bool {
+        get { UserDefaults.standard.bool(forKey: directCommitKey) }
+        set { UserDefaults.standard.set(newValue, forKey: directCommitKey) }
+    }
+
     private static var hasPromptedAccessibility = false
     private static var hasStartedInputSourceLock = false
 
@@ -686,6 +695,8 @@ class OngeulInputController: IMKInputController {
         refreshLockCache()
         if let client = sender as? (any IMKTextInput) {
             applyEffect(effect, to: client)
+            // 설정 창에서 바꾼 값도 입력 창으로 돌아올 때 적용된다
+            applyResult(coordinator.setDirectCommit(Self.directCommit), to: client)
         }
 
         // 아이콘 동기화 — applyEffect의 modeChanged와 무관하게 항상 수행.
@@ -1044,6 +1055,8 @@ class OngeulInputController: IMKInputController {
         //   1. synthetic backspace 카운트다운 → return false (시스템 통과)
         //   2. buffering/expectingBackspace/replayPending 가드 → return true (소비)
         //   3. corrector 무관 → 정상 처리 진행
+        // 글자 교체용 합성 키는 focus-steal 보정의 backspace 카운트·버퍼에 섞이지 않도록 먼저 통과시킨다.
+        if SyntheticEvent.from(event) == .replacement { return false }
         switch focusSteal.handle(keyCode: event.keyCode, keyLabel: keyLabelFromEvent(event)) {
         case .syntheticBackspaceConsumed: return false
         case .consumed: return true
@@ -1054,32 +1067,31 @@ class OngeulInputController: IMKInputController {
         // handleKeyDown/deactivateServer가 activateServer보다 먼저 실행되어
         // focus-steal 증거를 파괴하는 것을 방지.
 
-        // 합성 이벤트 → 시스템에 통과 (routeKeyDown 진입 전, cancelOnKeyDown 전에 가드)
+        // 합성 이벤트 → 시스템에 통과 (handleEvent 진입 전, cancelOnKeyDown 전에 가드)
         if let synthetic = SyntheticEvent.from(event) {
             switch synthetic {
-            case .enter, .autoSwitch: return false
+            case .enter, .autoSwitch, .replacement: return false
             }
         }
 
         toggleDetector.cancelOnKeyDown()
 
-        let action = routeKeyDown(
-            keyCode: event.keyCode,
-            characters: event.characters,
-            modifiers: event.modifierFlags,
-            engineMode: coordinator.mode,
-            toggleKey: Self.toggleKey
+        // 라우팅과 엔진 처리는 Rust가 결정한다. 여기서는 결과 적용과 플랫폼 부수 효과만 수행.
+        let outcome = coordinator.handleEvent(
+            KeyEvent(event),
+            shiftSpaceToggle: Self.toggleKey == .shiftSpace
         )
-        return executeAction(action, event: event, client: client)
+        return executeAction(outcome.action, result: outcome.result, event: event, client: client)
     }
 
     private func executeAction(
-        _ action: KeyDownAction,
+        _ action: KeyAction,
+        result: ProcessResult,
         event: NSEvent,
         client: any IMKTextInput
     ) -> Bool {
         switch action {
-        case .shiftSpaceToggle:
+        case .toggleMode:
             guard let effect = coordinator.toggleMode(for: currentBundleId)
             else { return false }
             applyEffect(effect, to: client)
@@ -1088,46 +1100,26 @@ class OngeulInputController: IMKInputController {
         case .passToSystem:
             return false
 
-        case .flushAndPassToSystem:
-            applyResult(coordinator.flush(), to: client)
-            return false
-
-        case .backspace:
-            let result = coordinator.backspace()
+        case .apply:
             applyResult(result, to: client)
             return result.handled
 
-        case .enter:
-            let hadComposing = currentComposingText != nil
-            let result = coordinator.flush()
+        case .enter(let repost):
             applyResult(result, to: client)
-            if hadComposing, AXIsProcessTrusted() {
+            if repost, AXIsProcessTrusted() {
                 postSyntheticKey(event: event, marker: .enter)
                 return true
             }
             return false
 
-        case .space:
-            applyResult(coordinator.flush(), to: client)
-            return false
-
         case .escape:
-            applyResult(coordinator.flush(), to: client)
+            applyResult(result, to: client)
             if let effect = coordinator.escapeToEnglish(
                 for: currentBundleId, enabled: Self.escapeToEnglish
             ) {
                 applyEffect(effect, to: client)
             }
             return false
-
-        case .processKey(let label):
-            let result = coordinator.processKey(key: label)
-            applyResult(result, to: client)
-            return result.handled
-
-        case .flushUnknownKey:
-            applyResult(coordinator.flush(), to: client)
-            return false
         }
     }
 
@@ -1147,6 +1139,31 @@ class OngeulInputController: IMKInputController {
         }
     }
 
+    /// 커서 앞 `deleteBefore`글자를 합성 backspace로 지우고 `text`를 합성 키로 입력한다.
+    /// insertText는 이벤트 스트림을 거치지 않아 backspace보다 먼저 반영되므로 글자도 합성 키로 보낸다.
+    /// 합성 이벤트는 `SyntheticEvent.replacement` 표시로 엔진을 거치지 않고 앱에 전달된다.
+    private func postSyntheticReplacement(deleteBefore: Int, text: String) {
+        let src = CGEventSource(stateID: .hidSystemState)
+        let marker = SyntheticEvent.replacement.rawValue
+        for _ in 0..<deleteBefore {
+            for keyDown in [true, false] {
+                if let ev = CGEvent(keyboardEventSource: src, virtualKey: KeyCode.backspace, keyDown: keyDown) {
+                    ev.setIntegerValueField(.eventSourceUserData, value: marker)
+                    ev.post(tap: .cghidEventTap)
+                }
+            }
+        }
+        let utf16 = Array(text.utf16)
+        guard !utf16.isEmpty else { return }
+        for keyDown in [true, false] {
+            if let ev = CGEvent(keyboardEventSource: src, virtualKey: 0, keyDown: keyDown) {
+                ev.keyboardSetUnicodeString(stringLength: utf16.count, unicodeString: utf16)
+                ev.setIntegerValueField(.eventSourceUserData, value: marker)
+                ev.post(tap: .cghidEventTap)
+            }
+        }
+    }
+
     // MARK: - Private: Key Label Conversion
 
     private func keyLabelFromEvent(_ event: NSEvent) -> String? {
@@ -1161,14 +1178,32 @@ class OngeulInputController: IMKInputController {
     // MARK: - Private: Result Application
 
     private func applyResult(_ result: ProcessResult, to client: any IMKTextInput) {
+        // 직접 확정 모드 등: 커서 앞 deleteBefore글자를 committed로 교체한다.
+        var replacementRange = NSRange(location: NSNotFound, length: NSNotFound)
+        if result.deleteBefore > 0 {
+            let selected = client.selectedRange()
+            let count = Int(result.deleteBefore)
+            if selected.location != NSNotFound && selected.location >= count {
+                replacementRange = NSRange(location: selected.location - count, length: count)
+            } else {
+                // 터미널·원격 데스크톱처럼 커서 위치를 알려주지 않는 client:
+                // 그대로 넣으면 지우지 못한 글자 뒤에 중복 입력되므로 합성 키로 교체한다.
+                postSyntheticReplacement(deleteBefore: count, text: result.committed ?? "")
+                applyComposing(result.composing, to: client)
+                return
+            }
+        }
         if let committed = result.committed {
-            client.insertText(
-                committed as NSString,
-                replacementRange: NSRange(location: NSNotFound, length: NSNotFound)
-            )
+            client.insertText(committed as NSString, replacementRange: replacementRange)
+        } else if replacementRange.location != NSNotFound {
+            client.insertText("" as NSString, replacementRange: replacementRange)
         }
 
-        if let composing = result.composing {
+        applyComposing(result.composing, to: client)
+    }
+
+    private func applyComposing(_ composing: String?, to client: any IMKTextInput) {
+        if let composing {
             currentComposingText = composing
             let styled = NSAttributedString(string: composing, attributes: [
                 .underlineStyle: 0,
@@ -1268,32 +1303,17 @@ class OngeulInputController: IMKInputController {
 
         let isInitialLoad = (loadedLayoutId == nil)
 
-        let url: URL?
-        #if DEBUG
-        if let testURL = testLayoutsURL {
-            url = testURL.appendingPathComponent("\(desiredLayoutId).json5")
-        } else {
-            url = Bundle.main.url(forResource: desiredLayoutId, withExtension: "json5")
-        }
-        #else
-        url = Bundle.main.url(forResource: desiredLayoutId, withExtension: "json5")
-        #endif
-
-        guard let url, let json = try? String(contentsOf: url, encoding: .utf8) else {
-            os_log("Failed to load layout: %{public}@.json5", log: log, type: .error, desiredLayoutId)
-            failedLayoutId = desiredLayoutId
-            return
-        }
-
+        // 레이아웃은 엔진에 동봉되어 있으므로 앱 번들에서 파일을 찾지 않는다
         do {
-            if let flushResult = try coordinator.loadLayout(json: json, isInitialLoad: isInitialLoad),
+            if let flushResult = try coordinator.loadLayout(id: desiredLayoutId, isInitialLoad: isInitialLoad),
                let client = self.client() {
                 applyResult(flushResult, to: client)
             }
             loadedLayoutId = desiredLayoutId
             failedLayoutId = nil
         } catch {
-            os_log("Failed to parse layout: %{public}@", log: log, type: .error, String(describing: error))
+            os_log("Failed to load layout %{public}@: %{public}@", log: log, type: .error,
+                   desiredLayoutId, String(describing: error))
             failedLayoutId = desiredLayoutId
         }
     }
@@ -1,19 +1,13 @@
 import XCTest
 import InputMethodKit
 
-/// PoC 3 확장: OngeulInputController 기본 init + testLayoutsURL로 레이아웃 로드 → handle() 파이프라인 테스트
+/// PoC 3 확장: OngeulInputController 기본 init + 동봉 레이아웃 로드 → handle() 파이프라인 테스트
 class InputControllerIntegrationTests: XCTestCase {
-    static let layoutsDir = URL(fileURLWithPath: #filePath)
-        .deletingLastPathComponent()
-        .deletingLastPathComponent()
-        .appendingPathComponent("ongeul-automata/layouts")
-
     var controller: OngeulInputController!
     var client: MockInputClient!
 
     override func setUp() {
         controller = OngeulInputController()
-        controller.testLayoutsURL = Self.layoutsDir
         client = MockInputClient()
     }
 
@@ -0,0 +1,61 @@
+import XCTest
+import AppKit
+
+/// NSEvent 키코드 → HID usage 변환과 `handleEvent` 바인딩 테스트.
+/// 라우팅 세부 케이스는 ongeul-automata `event` 모듈 테스트가 다룬다.
+class KeyEventAdapterTests: XCTestCase {
+
+    // MARK: - 키코드 변환
+
+    func testSpecialKeys() {
+        XCTAssertEqual(KeyEvent.hidUsage(keyCode: 36), 0x28)   // Return
+        XCTAssertEqual(KeyEvent.hidUsage(keyCode: 76), 0x58)   // Keypad Enter
+        XCTAssertEqual(KeyEvent.hidUsage(keyCode: 49), 0x2C)   // Space
+        XCTAssertEqual(KeyEvent.hidUsage(keyCode: 51), 0x2A)   // Delete
+        XCTAssertEqual(KeyEvent.hidUsage(keyCode: 53), 0x29)   // Escape
+        XCTAssertEqual(KeyEvent.hidUsage(keyCode: 0x21), 0x2F) // [
+    }
+
+    func testArrowAndKeypad() {
+        XCTAssertEqual(KeyEvent.hidUsage(keyCode: 123), 0x50)  // ←
+        XCTAssertEqual(KeyEvent.hidUsage(keyCode: 126), 0x52)  // ↑
+        XCTAssertEqual(KeyEvent.hidUsage(keyCode: 0x52), 0x62) // Keypad 0
+        XCTAssertEqual(KeyEvent.hidUsage(keyCode: 0x5C), 0x61) // Keypad 9
+        XCTAssertEqual(KeyEvent.hidUsage(keyCode: 0x51), 0x67) // Keypad =
+    }
+
+    func testLetterKeysMatchPhysicalTable() {
+        // Rust physical 모듈과 같은 위치여야 한다 (예: Q = 0x14)
+        XCTAssertEqual(KeyEvent.hidUsage(keyCode: 0x0C), 0x14)
+        XCTAssertEqual(physicalKeyUsage(code: "KeyG"), KeyEvent.hidUsage(keyCode: 0x05))
+        XCTAssertEqual(physicalKeyUsage(code: "Digit1"), KeyEvent.hidUsage(keyCode: 0x12))
+    }
+
+    func testUnknownKeyCode_isZero() {
+        XCTAssertEqual(KeyEvent.hidUsage(keyCode: 0x72), 0)  // Help/Insert
+    }
+
+    // MARK: - handleEvent
+
+    func testHandleEvent_enterRepostsWhileComposing() throws {
+        let engine = HangulEngine()
+        let layoutURL = HangulEngineTests.layoutsDir.appendingPathComponent("2-standard.json5")
+        try engine.loadLayout(json: try String(contentsOf: layoutURL))
+        engine.setMode(mode: .korean)
+
+        let none = ModifierFlags(shift: false, command: false, option: false, control: false)
+        let g = engine.handleEvent(
+            event: KeyEvent(keycode: 0x0A, characters: "G", modifiers: none, capsLock: true),
+            shiftSpaceToggle: false
+        )
+        XCTAssertEqual(g.action, .apply)
+        XCTAssertEqual(g.result.composing, "ㅎ")
+
+        let enter = engine.handleEvent(
+            event: KeyEvent(keycode: 0x28, characters: "\r", modifiers: none, capsLock: true),
+            shiftSpaceToggle: false
+        )
+        XCTAssertEqual(enter.action, .enter(repost: true))
+        XCTAssertEqual(enter.result.committed, "ㅎ")
+    }
+}
@@ -0,0 +1,42 @@
+import XCTest
+
+/// Rust `ModePolicy` 바인딩과 이전 UserDefaults English Lock 이전 테스트.
+/// 정책 규칙의 세부 케이스는 ongeul-automata `policy` 모듈 테스트가 다룬다.
+class ModePolicyTests: XCTestCase {
+
+    func testPerAppModeMemory() {
+        let policy = ModePolicy()
+        XCTAssertNil(policy.activateApp(bundleId: "com.test.app"))
+
+        policy.saveMode(bundleId: "com.app1", mode: .korean)
+        policy.saveMode(bundleId: "com.app2", mode: .english)
+        XCTAssertEqual(policy.activateApp(bundleId: "com.app1"), .korean)
+        XCTAssertEqual(policy.activateApp(bundleId: "com.app2"), .english)
+    }
+
+    func testLockRestoresPreviousMode() {
+        let policy = ModePolicy()
+        policy.lock(bundleId: "com.test.app", currentMode: .english)
+        XCTAssertTrue(policy.isLocked(bundleId: "com.test.app"))
+        XCTAssertEqual(policy.activateApp(bundleId: "com.test.app"), .english)
+
+        XCTAssertEqual(policy.unlock(bundleId: "com.test.app"), .english)
+        XCTAssertFalse(policy.isLocked(bundleId: "com.test.app"))
+    }
+
+    func testMigrateLegacyLocks() {
+        let suiteName = "com.test.ModePolicyTests.\(UUID().uuidString)"
+        let defaults = UserDefaults(suiteName: suiteName)!
+        defer { UserDefaults.standard.removePersistentDomain(forName: suiteName) }
+        defaults.set(["com.app1": "korean", "com.app2": "english"],
+                     forKey: ModePolicy.legacyLockKey)
+
+        let policy = ModePolicy()
+        policy.migrateLegacyLocks(from: defaults)
+
+        XCTAssertTrue(policy.isLocked(bundleId: "com.app1"))
+        XCTAssertEqual(policy.unlock(bundleId: "com.app1"), .korean)
+        XCTAssertEqual(policy.unlock(bundleId: "com.app2"), .english)
+        XCTAssertNil(defaults.dictionary(forKey: ModePolicy.legacyLockKey))
+    }
+}
@@ -1,6 +1,8 @@
 import XCTest
 import AppKit
 
+/// Rust `ToggleDetector`를 NSEvent 값으로 호출하는 어댑터 테스트.
+/// 상태 머신 자체의 세부 케이스는 ongeul-automata `toggle` 모듈 테스트가 다룬다.
 class ToggleDetectorTests: XCTestCase {
     var detector: ToggleDetector!
 
@@ -67,7 +69,13 @@ class ToggleDetectorTests: XCTestCase {
             toggleKey: .rightCommand, now: now + 0.1
         )
         XCTAssertEqual(r, .none)
-        XCTAssertNil(detector.pendingKeyCode)
+
+        // 대기 중인 전환 키가 해제되었으므로 뗌도 전환하지 않는다
+        let r2 = detector.handleFlagsChanged(
+            keyCode: KeyCode.rightCommand, flags: [],
+            toggleKey: .rightCommand, now: now + 0.2
+        )
+        XCTAssertEqual(r2, .none)
     }
 
     // MARK: - 4-Key English Lock
@@ -136,7 +144,20 @@ class ToggleDetectorTests: XCTestCase {
             keyCode: KeyCode.leftShift, flags: [.command, .shift],
             toggleKey: .rightCommand, now: now
         )
-        XCTAssertTrue(detector.fourKeysSeen.isEmpty)
+        // 나머지 두 키를 눌러도 처음부터 다시 세므로 English Lock이 아니다
+        let _ = detector.handleFlagsChanged(
+            keyCode: KeyCode.leftOption, flags: [.command, .option],
+            toggleKey: .rightCommand, now: now
+        )
+        let _ = detector.handleFlagsChanged(
+            keyCode: KeyCode.rightOption, flags: [.command, .option],
+            toggleKey: .rightCommand, now: now
+        )
+        let r = detector.handleFlagsChanged(
+            keyCode: KeyCode.rightOption, flags: [],
+            toggleKey: .rightCommand, now: now
+        )
+        XCTAssertEqual(r, .none)
     }
 
     // MARK: - Right Option Toggle
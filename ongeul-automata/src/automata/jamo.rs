@@ -6,7 +6,9 @@
 use crate::layout::KeyboardLayout;
 use crate::unicode;
 
-use super::{Automata, AutomataResult, AutomataState, ComposeBuffer};
+use super::{
+    Automata, AutomataResult, AutomataSnapshot, AutomataState, BufferSnapshot, ComposeBuffer,
+};
 
 /// 자모 변환 실패 시 현재 조합을 확정하고 안전하게 반환하는 매크로.
 macro_rules! try_convert {
@@ -23,6 +25,7 @@ macro_rules! try_convert {
 }
 
 /// 두벌식 오토마타
+#[derive(Clone)]
 pub struct JamoAutomata {
     buffer: ComposeBuffer,
     /// 겹모음 상태에서 백스페이스 시 원래 모음을 복원하기 위한 저장값
@@ -501,6 +504,58 @@ impl Automata for JamoAutomata {
         AutomataResult::handled(committed, None)
     }
 
+    fn snapshot(&self) -> AutomataSnapshot {
+        AutomataSnapshot::Jamo(BufferSnapshot {
+            buffer: self.buffer.clone(),
+            prev_choseong: None,
+            prev_jungseong: self.prev_jungseong,
+            prev_jongseong: self.prev_jongseong,
+            pending_jongseong: None,
+        })
+    }
+
+    fn restore(&mut self, snapshot: &AutomataSnapshot, _layout: &KeyboardLayout) -> bool {
+        let AutomataSnapshot::Jamo(snapshot) = snapshot else {
+            return false;
+        };
+        if !snapshot.is_valid() {
+            return false;
+        }
+        self.buffer = snapshot.buffer.clone();
+        self.prev_jungseong = snapshot.prev_jungseong;
+        self.prev_jongseong = snapshot.prev_jongseong;
+        true
+    }
+
+    fn load_syllable(&mut self, ch: char, layout: &KeyboardLayout) -> bool {
+        if self.buffer.state != AutomataState::Empty {
+            return false;
+        }
+        let Some((l, v, t)) = unicode::decompose_syllable(ch) else {
+            return false;
+        };
+        // 겹모음/겹받침은 조합 규칙으로 나눠 첫째 자모를 백스페이스 복원용으로 기억한다
+        let prev_v = unicode::jungseong_to_compat(v)
+            .and_then(|c| layout.split(c))
+            .and_then(|(first, _)| unicode::compat_to_jungseong(first));
+        let prev_t = unicode::jongseong_to_compat(t)
+            .and_then(|c| layout.split(c))
+            .and_then(|(first, _)| unicode::compat_to_jongseong(first));
+
+        self.buffer.choseong = Some(l);
+        self.buffer.jungseong = Some(v);
+        self.buffer.jongseong = (t != 0).then_some(t);
+        self.buffer.state = match (t != 0, prev_t, prev_v) {
+            (true, Some(_), _) => AutomataState::Jongseong2,
+            (true, None, _) => AutomataState::Jongseong,
+            (false, _, Some(_)) => AutomataState::Jungseong2,
+            (false, _, None) => AutomataState::Jungseong,
+        };
+        self.prev_jungseong = prev_v;
+        self.prev_jongseong = prev_t;
+        true
+    }
+
     fn composing_text(&self) -> Option<String> {
         self.buffer.to_string()
     }
@@ -865,4 +920,37 @@ mod tests {
         assert_eq!(committed, "ㄱ");
         assert_eq!(composing, Some("ㄴ".to_string()));
     }
+
+    #[test]
+    fn test_load_syllable_double_jongseong() {
+        // "값" 되살림 → BS → "갑" → 이어서 ㅏ 입력 시 ㅂ이 다음 음절로 넘어가 "가" + "바"
+        let layout = make_layout();
+        let mut automata = JamoAutomata::new();
+        assert!(automata.load_syllable('값', &layout));
+        assert_eq!(automata.state(), AutomataState::Jongseong2);
+        assert_eq!(automata.backspace().composing, Some("갑".to_string()));
+        let (committed, composing) = process_keys(&mut automata, &layout, &["k"]);
+        assert_eq!(committed, "가");
+        assert_eq!(composing, Some("바".to_string()));
+    }
+
+    #[test]
+    fn test_load_syllable_compound_vowel() {
+        // "과" 되살림 → BS → "고" → BS → "ㄱ"
+        let layout = make_layout();
+        let mut automata = JamoAutomata::new();
+        assert!(automata.load_syllable('과', &layout));
+        assert_eq!(automata.state(), AutomataState::Jungseong2);
+        assert_eq!(automata.backspace().composing, Some("고".to_string()));
+        assert_eq!(automata.backspace().composing, Some("ㄱ".to_string()));
+    }
+
+    #[test]
+    fn test_load_syllable_requires_empty_state() {
+        let layout = make_layout();
+        let mut automata = JamoAutomata::new();
+        process_keys(&mut automata, &layout, &["r"]);
+        assert!(!automata.load_syllable('가', &layout));
+        assert!(!JamoAutomata::new().load_syllable('A', &layout));
+    }
 }
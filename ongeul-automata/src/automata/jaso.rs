@@ -6,7 +6,9 @@
 use crate::layout::KeyboardLayout;
 use crate::unicode;
 
-use super::{Automata, AutomataResult, AutomataState, ComposeBuffer};
+use super::{
+    Automata, AutomataResult, AutomataSnapshot, AutomataState, BufferSnapshot, ComposeBuffer,
+};
 
 /// 세벌식 오토마타
 pub struct JasoAutomata {
@@ -48,6 +50,35 @@ impl JasoAutomata {
         text
     }
 
+    /// 조합 버퍼와 백스페이스 복원 정보 (신세벌식 스냅샷에서도 사용)
+    pub(super) fn buffer_snapshot(&self) -> BufferSnapshot {
+        BufferSnapshot {
+            buffer: self.buffer.clone(),
+            prev_choseong: self.prev_choseong,
+            prev_jungseong: self.prev_jungseong,
+            prev_jongseong: self.prev_jongseong,
+            pending_jongseong: self.pending_jongseong,
+        }
+    }
+
+    /// 스냅샷의 버퍼를 되살린다. 값이 잘못되었으면 false.
+    pub(super) fn restore_buffer(&mut self, snapshot: &BufferSnapshot) -> bool {
+        if !snapshot.is_valid() {
+            return false;
+        }
+        self.buffer = snapshot.buffer.clone();
+        self.prev_choseong = snapshot.prev_choseong;
+        self.prev_jungseong = snapshot.prev_jungseong;
+        self.prev_jongseong = snapshot.prev_jongseong;
+        self.pending_jongseong = snapshot.pending_jongseong;
+        true
+    }
+
+    /// 조합 중인 중성 (위치 자모 char)
+    pub(super) fn jungseong(&self) -> Option<char> {
+        self.buffer.jungseong.and_then(Self::v_char)
+    }
+
     /// 자모의 위치(초/중/종)를 분류한다.
     fn classify(ch: char) -> JasoClass {
         if unicode::is_choseong(ch) {
@@ -346,10 +377,82 @@ impl Automata for JasoAutomata {
         AutomataResult::handled(Some(committed), None)
     }
 
+    fn snapshot(&self) -> AutomataSnapshot {
+        AutomataSnapshot::Jaso(self.buffer_snapshot())
+    }
+
+    fn restore(&mut self, snapshot: &AutomataSnapshot, _layout: &KeyboardLayout) -> bool {
+        match snapshot {
+            AutomataSnapshot::Jaso(snapshot) => self.restore_buffer(snapshot),
+            _ => false,
+        }
+    }
+
+    fn load_syllable(&mut self, ch: char, layout: &KeyboardLayout) -> bool {
+        if self.buffer.state != AutomataState::Empty || self.pending_jongseong.is_some() {
+            return false;
+        }
+        let Some((l, v, t)) = unicode::decompose_syllable(ch) else {
+            return false;
+        };
+        // 쌍자음/겹모음/겹종성은 조합 규칙으로 나눠 첫째 자모를 백스페이스 복원용으로 기억한다.
+        // 나눈 자모가 결과와 다른 종류이면 한 단계씩 되돌릴 수 없으므로 되살리지 않는다. (Err)
+        let first_of =
+            |combined: Option<char>, is_class: fn(char) -> bool, base: u32| match combined
+                .and_then(|c| layout.split(c))
+            {
+                None => Ok(None),
+                Some((first, second)) if is_class(first) && is_class(second) => {
+                    (first as u32).checked_sub(base).map(Some).ok_or(())
+                }
+                Some(_) => Err(()),
+            };
+        let prev_l = first_of(Self::l_char(l), unicode::is_choseong, unicode::L_BASE);
+        let prev_v = first_of(Self::v_char(v), unicode::is_jungseong, unicode::V_BASE);
+        let prev_t = if t == 0 {
+            Ok(None)
+        } else {
+            first_of(Self::t_char(t), unicode::is_jongseong, unicode::T_BASE)
+        };
+        let (Ok(prev_l), Ok(prev_v), Ok(prev_t)) = (prev_l, prev_v, prev_t) else {
+            return false;
+        };
+
+        self.buffer.choseong = Some(l);
+        self.buffer.jungseong = Some(v);
+        self.buffer.jongseong = (t != 0).then_some(t);
+        self.buffer.state = match (t != 0, prev_t, prev_v) {
+            (true, Some(_), _) => AutomataState::Jongseong2,
+            (true, None, _) => AutomataState::Jongseong,
+            (false, _, Some(_)) => AutomataState::Jungseong2,
+            (false, _, None) => AutomataState::Jungseong,
+        };
+        self.prev_choseong = prev_l;
+        self.prev_jungseong = prev_v;
+        self.prev_jongseong = prev_t;
+        true
+    }
+
     fn composing_text(&self) -> Option<String> {
         self.buffer.to_string()
     }
 
+    /// 모아치기: 누른 순서와 무관하게 초성 → 중성 → 종성 순으로 처리한다.
+    fn process_chord(&mut self, chars: &[char], layout: &KeyboardLayout) -> AutomataResult {
+        let mut sorted = chars.to_vec();
+        sorted.sort_by_key(|&ch| match Self::classify(ch) {
+            JasoClass::Choseong(_) => 0,
+            JasoClass::Jungseong(_) => 1,
+            JasoClass::Jongseong(_) => 2,
+            JasoClass::Unknown => 3,
+        });
+        sorted
+            .into_iter()
+            .fold(AutomataResult::handled(None, None), |acc, ch| {
+                acc.then(self.process(ch, layout))
+            })
+    }
+
     fn state(&self) -> AutomataState {
         self.buffer.state
     }
@@ -684,4 +787,89 @@ mod tests {
         assert_eq!(committed, "간");
         assert_eq!(composing, Some("ㄱ".to_string()));
     }
+
+    #[test]
+    fn test_chord_any_order() {
+        // 모아치기: ㄴ종(s) ㅏ중(f) ㄱ초(k) 순으로 들어와도 "간"
+        let layout = make_layout();
+        let mut automata = JasoAutomata::new();
+        let chars: Vec<char> = ["s", "f", "k"]
+            .iter()
+            .map(|k| layout.map_key(k).unwrap())
+            .collect();
+        let result = automata.process_chord(&chars, &layout);
+        assert_eq!(result.committed, None);
+        assert_eq!(result.composing, Some("간".to_string()));
+    }
+
+    #[test]
+    fn test_chord_commits_previous_syllable() {
+        // "한" 조합 중 → 모아치기 ㅏ중(f) ㄱ초(k) → "한" 확정 + "가"
+        let layout = make_layout();
+        let mut automata = JasoAutomata::new();
+        process_keys(&mut automata, &layout, &["m", "f", "s"]);
+        let chars = [layout.map_key("f").unwrap(), layout.map_key("k").unwrap()];
+        let result = automata.process_chord(&chars, &layout);
+        assert_eq!(result.committed, Some("한".to_string()));
+        assert_eq!(result.composing, Some("가".to_string()));
+    }
+
+    #[test]
+    fn test_load_syllable_steps_back_jamo_by_jamo() {
+        // "꽋" 되살림 → BS마다 자모 하나씩: 꽉 → 꽈 → 꼬 → ㄲ → ㄱ
+        let layout = make_layout();
+        let mut automata = JasoAutomata::new();
+        assert!(automata.load_syllable('꽋', &layout));
+        assert_eq!(automata.state(), AutomataState::Jongseong2);
+        for expected in ["꽉", "꽈", "꼬", "ㄲ", "ㄱ"] {
+            assert_eq!(automata.backspace().composing, Some(expected.to_string()));
+        }
+        assert_eq!(automata.backspace().composing, None);
+    }
+
+    #[test]
+    fn test_load_syllable_rejects_mixed_class_combination() {
+        // 초성 ㄱ + 종성 ㄱ = 종성 ㄲ 같은 규칙은 한 단계씩 되돌릴 수 없으므로 되살리지 않는다
+        let layout = crate::layout::LayoutResolver::default()
+            .load(
+                r#"{ id: "x", extends: "3-390",
+                     combinations: [{ first: "0x1100", second: "0x11A8", result: "0x11A9" }] }"#,
+            )
+            .unwrap();
+        let mut automata = JasoAutomata::new();
+        assert!(!automata.load_syllable('갂', &layout));
+        assert_eq!(automata.state(), AutomataState::Empty);
+        assert!(automata.load_syllable('각', &layout));
+    }
+
+    #[test]
+    fn test_snapshot_restores_pending_jongseong() {
+        // 모아주기 보류 중(ㄱ초 + ㄴ종) 상태를 되살리면 중성이 와서 "간"이 된다
+        let layout = make_layout();
+        let mut automata = JasoAutomata::new();
+        process_keys(&mut automata, &layout, &["k", "s"]);
+        let snapshot = automata.snapshot();
+
+        let mut restored = JasoAutomata::new();
+        assert!(restored.restore(&snapshot, &layout));
+        assert_eq!(restored.snapshot(), snapshot);
+        let (_, composing) = process_keys(&mut restored, &layout, &["f"]);
+        assert_eq!(composing, Some("간".to_string()));
+    }
+
+    #[test]
+    fn test_restore_rejects_invalid_snapshot() {
+        let layout = make_layout();
+        let mut automata = JasoAutomata::new();
+        process_keys(&mut automata, &layout, &["k", "f"]);
+        let AutomataSnapshot::Jaso(mut buffer) = automata.snapshot() else {
+            unreachable!();
+        };
+        buffer.buffer.jungseong = Some(99);
+        let mut restored = JasoAutomata::new();
+        assert!(!restored.restore(&AutomataSnapshot::Jaso(buffer.clone()), &layout));
+        // 종류가 다른 스냅샷
+        assert!(!restored.restore(&AutomataSnapshot::Jamo(buffer), &layout));
+        assert_eq!(restored.state(), AutomataState::Empty);
+    }
 }
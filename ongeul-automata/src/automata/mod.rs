@@ -1,13 +1,19 @@
 /// 오토마타 트레잇, 상태, 조합 버퍼, 팩토리
 pub mod jamo;
 pub mod jaso;
+pub mod old;
+pub mod romaja;
+pub mod shin;
 
 use crate::layout::KeyboardLayout;
 use crate::layout::schema::LayoutType;
 use crate::unicode;
 
+use serde::{Deserialize, Serialize};
+
 /// 오토마타 상태 (두벌식 6상태)
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum AutomataState {
     /// 초기 상태 — 조합 없음
     Empty,
@@ -24,7 +30,7 @@ pub enum AutomataState {
 }
 
 /// 조합 버퍼 — 현재 조합 중인 자모 정보
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ComposeBuffer {
     /// 초성 인덱스 (L)
     pub choseong: Option<u32>,
@@ -60,6 +66,26 @@ impl ComposeBuffer {
         self.state = AutomataState::Empty;
     }
 
+    /// 인덱스가 자모 범위 안에 있고 상태와 맞는지 (스냅샷 복원 검증용)
+    pub fn is_valid(&self) -> bool {
+        let in_range = self.choseong.is_none_or(|l| l < unicode::L_COUNT)
+            && self.jungseong.is_none_or(|v| v < unicode::V_COUNT)
+            && self.jongseong.is_none_or(|t| t > 0 && t < unicode::T_COUNT);
+        let filled = match self.state {
+            AutomataState::Empty => {
+                self.choseong.is_none() && self.jungseong.is_none() && self.jongseong.is_none()
+            }
+            AutomataState::Choseong => {
+                self.choseong.is_some() && self.jungseong.is_none() && self.jongseong.is_none()
+            }
+            AutomataState::Jungseong | AutomataState::Jungseong2 => {
+                self.jungseong.is_some() && self.jongseong.is_none()
+            }
+            AutomataState::Jongseong | AutomataState::Jongseong2 => self.jongseong.is_some(),
+        };
+        in_range && filled
+    }
+
     /// 현재 버퍼로 합성된 문자열을 반환한다.
     pub fn to_string(&self) -> Option<String> {
         match (self.choseong, self.jungseong) {
@@ -89,6 +115,8 @@ pub struct AutomataResult {
     pub composing: Option<String>,
     /// 키가 처리되었는지 (false면 시스템에 위임)
     pub handled: bool,
+    /// `committed`를 넣기 전에 커서 앞에서 지울 확정 글자 수
+    pub delete_before: u32,
 }
 
 impl AutomataResult {
@@ -97,6 +125,7 @@ impl AutomataResult {
             committed,
             composing,
             handled: true,
+            delete_before: 0,
         }
     }
 
@@ -105,11 +134,90 @@ impl AutomataResult {
             committed: None,
             composing: None,
             handled: false,
+            delete_before: 0,
         }
     }
+
+    /// 이어서 처리한 결과를 합친다. 확정 텍스트는 이어 붙이고 조합 텍스트와 처리 여부는 나중 것을 쓴다.
+    /// 나중 결과의 `delete_before`는 앞 결과가 확정한 텍스트부터 지운다.
+    pub fn then(self, next: AutomataResult) -> AutomataResult {
+        let mut committed = self.committed.unwrap_or_default();
+        let mut delete_before = self.delete_before;
+        for _ in 0..next.delete_before {
+            if committed.pop().is_none() {
+                delete_before += 1;
+            }
+        }
+        committed.push_str(&next.committed.unwrap_or_default());
+        AutomataResult {
+            committed: (!committed.is_empty()).then_some(committed),
+            composing: next.composing,
+            handled: next.handled,
+            delete_before,
+        }
+    }
+}
+
+/// 조합 버퍼와 백스페이스 복원 정보 (두벌식/세벌식 스냅샷)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BufferSnapshot {
+    pub buffer: ComposeBuffer,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prev_choseong: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prev_jungseong: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prev_jongseong: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pending_jongseong: Option<u32>,
 }
 
-/// 오토마타 트레잇 — 두벌식/세벌식 공통 인터페이스
+impl BufferSnapshot {
+    /// 버퍼와 복원용 인덱스가 자모 범위 안에 있는지
+    pub fn is_valid(&self) -> bool {
+        self.buffer.is_valid()
+            && self.prev_choseong.is_none_or(|l| l < unicode::L_COUNT)
+            && self.prev_jungseong.is_none_or(|v| v < unicode::V_COUNT)
+            && self
+                .prev_jongseong
+                .is_none_or(|t| t > 0 && t < unicode::T_COUNT)
+            && self
+                .pending_jongseong
+                .is_none_or(|t| t > 0 && t < unicode::T_COUNT)
+    }
+}
+
+/// 옛한글 음절 스냅샷 (첫가끝 자모)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyllableSnapshot {
+    pub choseong: Option<char>,
+    pub jungseong: Option<char>,
+    pub jongseong: Option<char>,
+}
+
+/// 오토마타 조합 상태 스냅샷. 오토마타 종류마다 상태를 되살리는 데 필요한 값이 다르다.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AutomataSnapshot {
+    /// 두벌식
+    Jamo(BufferSnapshot),
+    /// 세벌식
+    Jaso(BufferSnapshot),
+    /// 신세벌식: 세벌식 상태 + 겹모음 가능 여부
+    Shin {
+        inner: BufferSnapshot,
+        vowel_combinable: bool,
+    },
+    /// 옛한글: 조합 중 음절 + 백스페이스 이력
+    OldHangul {
+        syllable: SyllableSnapshot,
+        history: Vec<SyllableSnapshot>,
+    },
+    /// 로마자: 확정되지 않은 키열 (다시 해석해 되살린다)
+    Romaja { keys: String },
+}
+
+/// 오토마타 트레잇 — 두벌식/세벌식/신세벌식/옛한글/로마자 공통 인터페이스
 pub trait Automata {
     /// 자모 문자 하나를 처리한다.
     fn process(&mut self, ch: char, layout: &KeyboardLayout) -> AutomataResult;
@@ -121,12 +229,86 @@ pub trait Automata {
     fn composing_text(&self) -> Option<String>;
     /// 현재 오토마타 상태를 반환한다.
     fn state(&self) -> AutomataState;
+    /// 동시에 눌린 자모들(모아치기)을 한 번에 처리한다. 기본은 입력 순서대로 처리.
+    fn process_chord(&mut self, chars: &[char], layout: &KeyboardLayout) -> AutomataResult {
+        chars
+            .iter()
+            .fold(AutomataResult::handled(None, None), |acc, &ch| {
+                acc.then(self.process(ch, layout))
+            })
+    }
+    /// 현재 조합 상태를 스냅샷으로 만든다.
+    fn snapshot(&self) -> AutomataSnapshot;
+    /// 스냅샷의 조합 상태를 되살린다. 종류가 다르거나 값이 잘못되었으면 상태를 바꾸지 않고 false.
+    fn restore(&mut self, snapshot: &AutomataSnapshot, layout: &KeyboardLayout) -> bool;
+    /// 확정된 완성형 음절을 조합 상태로 되살린다. 조합 중일 때나 지원하지 않으면 false.
+    /// 겹자모는 레이아웃 조합 규칙으로 나눠 백스페이스가 자모 하나씩 지우도록 한다.
+    fn load_syllable(&mut self, _ch: char, _layout: &KeyboardLayout) -> bool {
+        false
+    }
+    /// 키 레이블을 직접 처리한다. 여러 키가 모여 자모 하나가 되는 자판(로마자)이 쓴다.
+    /// None이면 엔진이 [`resolve_key`](Self::resolve_key)로 자모를 얻어 [`process`](Self::process)를 호출한다.
+    fn process_key(&mut self, _key: &str, _layout: &KeyboardLayout) -> Option<AutomataResult> {
+        None
+    }
+    /// 키 레이블을 자모로 해석한다. 기본은 상태와 무관한 레이아웃 매핑.
+    /// 상태에 따라 키의 뜻이 달라지는 자판(신세벌식)은 해석 결과를 기억해 다음 키에 반영할 수 있다.
+    fn resolve_key(&mut self, key: &str, layout: &KeyboardLayout) -> Option<char> {
+        layout.map_key(key)
+    }
 }
 
 /// 레이아웃 타입에 따라 적절한 오토마타를 생성한다.
-pub fn create_automata(layout: &KeyboardLayout) -> Box<dyn Automata + Send> {
+/// 영문 배열(latin)은 한글을 조합하지 않으므로 None.
+pub fn create_automata(layout: &KeyboardLayout) -> Option<Box<dyn Automata + Send>> {
     match layout.layout_type {
-        LayoutType::Jamo => Box::new(jamo::JamoAutomata::new()),
-        LayoutType::Jaso => Box::new(jaso::JasoAutomata::new()),
+        LayoutType::Jamo => Some(Box::new(jamo::JamoAutomata::new())),
+        LayoutType::Jaso => Some(Box::new(jaso::JasoAutomata::new())),
+        LayoutType::ShinJaso => Some(Box::new(shin::ShinAutomata::new())),
+        LayoutType::OldHangul => Some(Box::new(old::OldHangulAutomata::new())),
+        LayoutType::Romaja => Some(Box::new(romaja::RomajaAutomata::new())),
+        LayoutType::Latin => None,
+    }
+}
+
+/// 오토마타가 조합하는 자모인지. 옛한글 자모는 옛한글 배열에서만 조합한다.
+pub fn is_composable_jamo(ch: char, layout: &KeyboardLayout) -> bool {
+    if layout.layout_type == LayoutType::OldHangul {
+        unicode::is_old_korean_jamo(ch)
+    } else {
+        unicode::is_korean_jamo(ch)
     }
 }
+
+/// 키 레이블 하나를 오토마타에 입력한다.
+/// 레이아웃에 없는 키는 조합을 확정하고 handled = false로 돌려준다(호출 측이 키를 그대로 입력).
+/// 자모가 아닌 문자(숫자, 기호 등)는 조합을 확정한 뒤 바로 확정한다.
+pub fn process_key_label(
+    automata: &mut dyn Automata,
+    key: &str,
+    layout: &KeyboardLayout,
+) -> AutomataResult {
+    if let Some(result) = automata.process_key(key, layout) {
+        return result;
+    }
+
+    let ch = match automata.resolve_key(key, layout) {
+        Some(ch) => ch,
+        None => {
+            // 레이아웃에 없는 키 → 현재 조합 확정 후 패스스루
+            let mut result = automata.flush();
+            result.handled = false;
+            return result;
+        }
+    };
+
+    // 비자모 문자 (숫자, 기호 등): 오토마타를 거치지 않고 직접 처리
+    if !is_composable_jamo(ch, layout) {
+        let flush = automata.flush();
+        let mut committed = flush.committed.unwrap_or_default();
+        committed.push(ch);
+        return AutomataResult::handled(Some(committed), None);
+    }
+
+    automata.process(ch, layout)
+}
@@ -0,0 +1,402 @@
+//! 옛한글(old Hangul) 세벌식 오토마타
+//!
+//! 첫가끝 자모(U+1100~U+11FF, 확장 A/B)를 초/중/종 슬롯에 채운다.
+//! 현대 자모 인덱스(19/21/28) 대신 자모 char 자체를 보관하므로 옛 자모도 조합할 수 있다.
+//! 결합 규칙은 슬롯마다 반복 적용되어 ㅂ+ㅅ+ㄱ=ᄢ 같은 세 자모 이상의 연쇄도 만든다.
+//! 완성형이 있으면 완성형 음절로, 없으면 L+V+T 첫가끝 시퀀스로 출력한다.
+
+use crate::layout::KeyboardLayout;
+use crate::unicode;
+
+use super::{Automata, AutomataResult, AutomataSnapshot, AutomataState, SyllableSnapshot};
+
+/// 조합 중인 첫가끝 음절
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Syllable {
+    choseong: Option<char>,
+    jungseong: Option<char>,
+    jongseong: Option<char>,
+}
+
+impl Syllable {
+    fn is_empty(&self) -> bool {
+        self.choseong.is_none() && self.jungseong.is_none() && self.jongseong.is_none()
+    }
+
+    fn to_string(self) -> Option<String> {
+        unicode::compose_conjoining(self.choseong, self.jungseong, self.jongseong)
+    }
+}
+
+impl From<Syllable> for SyllableSnapshot {
+    fn from(s: Syllable) -> Self {
+        SyllableSnapshot {
+            choseong: s.choseong,
+            jungseong: s.jungseong,
+            jongseong: s.jongseong,
+        }
+    }
+}
+
+impl Syllable {
+    /// 스냅샷의 자모가 제 슬롯의 첫가끝 자모일 때만 음절로 되살린다.
+    fn from_snapshot(s: &SyllableSnapshot) -> Option<Self> {
+        let valid = s.choseong.is_none_or(unicode::is_old_choseong)
+            && s.jungseong.is_none_or(unicode::is_old_jungseong)
+            && s.jongseong.is_none_or(unicode::is_old_jongseong);
+        valid.then_some(Syllable {
+            choseong: s.choseong,
+            jungseong: s.jungseong,
+            jongseong: s.jongseong,
+        })
+    }
+}
+
+/// 옛한글 오토마타
+pub struct OldHangulAutomata {
+    syllable: Syllable,
+    /// 백스페이스 복원용: 키마다 직전 음절 상태를 쌓는다 (현재 음절 범위)
+    history: Vec<Syllable>,
+}
+
+impl Default for OldHangulAutomata {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OldHangulAutomata {
+    pub fn new() -> Self {
+        OldHangulAutomata {
+            syllable: Syllable::default(),
+            history: Vec::new(),
+        }
+    }
+
+    fn commit_current(&mut self) -> Option<String> {
+        let text = self.syllable.to_string();
+        self.syllable = Syllable::default();
+        self.history.clear();
+        text
+    }
+
+    /// 현재 음절을 확정하고 `next`로 새 음절을 시작한다.
+    fn commit_and_start(&mut self, next: Syllable) -> AutomataResult {
+        let committed = self.commit_current();
+        self.syllable = next;
+        self.history.push(Syllable::default());
+        AutomataResult::handled(committed, self.syllable.to_string())
+    }
+
+    /// 현재 음절을 갱신한다. (백스페이스용으로 직전 상태 보관)
+    fn update(&mut self, next: Syllable) -> AutomataResult {
+        self.history.push(self.syllable);
+        self.syllable = next;
+        AutomataResult::handled(None, self.syllable.to_string())
+    }
+
+    /// 같은 슬롯의 두 자모를 결합한다. 결과가 같은 위치 자모일 때만 유효.
+    fn combine(
+        layout: &KeyboardLayout,
+        current: char,
+        new: char,
+        is_class: fn(char) -> bool,
+    ) -> Option<char> {
+        layout.combine(current, new).filter(|&ch| is_class(ch))
+    }
+}
+
+impl Automata for OldHangulAutomata {
+    fn process(&mut self, ch: char, layout: &KeyboardLayout) -> AutomataResult {
+        let current = self.syllable;
+
+        if unicode::is_old_choseong(ch) {
+            if current.jungseong.is_some() || current.jongseong.is_some() {
+                return self.commit_and_start(Syllable {
+                    choseong: Some(ch),
+                    ..Syllable::default()
+                });
+            }
+            return match current.choseong {
+                Some(l) => match Self::combine(layout, l, ch, unicode::is_old_choseong) {
+                    Some(combined) => self.update(Syllable {
+                        choseong: Some(combined),
+                        ..current
+                    }),
+                    None => self.commit_and_start(Syllable {
+                        choseong: Some(ch),
+                        ..Syllable::default()
+                    }),
+                },
+                None => self.update(Syllable {
+                    choseong: Some(ch),
+                    ..current
+                }),
+            };
+        }
+
+        if unicode::is_old_jungseong(ch) {
+            if current.jongseong.is_some() {
+                return self.commit_and_start(Syllable {
+                    jungseong: Some(ch),
+                    ..Syllable::default()
+                });
+            }
+            return match current.jungseong {
+                Some(v) => match Self::combine(layout, v, ch, unicode::is_old_jungseong) {
+                    Some(combined) => self.update(Syllable {
+                        jungseong: Some(combined),
+                        ..current
+                    }),
+                    None => self.commit_and_start(Syllable {
+                        jungseong: Some(ch),
+                        ..Syllable::default()
+                    }),
+                },
+                None => self.update(Syllable {
+                    jungseong: Some(ch),
+                    ..current
+                }),
+            };
+        }
+
+        if unicode::is_old_jongseong(ch) {
+            // 중성 없이 종성 → 현재 음절과 독립 종성 모두 확정
+            let attached = match (current.jungseong, current.jongseong) {
+                (None, _) => None,
+                (Some(_), None) => Some(ch),
+                (Some(_), Some(t)) => Self::combine(layout, t, ch, unicode::is_old_jongseong),
+            };
+            return match attached {
+                Some(t) => self.update(Syllable {
+                    jongseong: Some(t),
+                    ..current
+                }),
+                None => {
+                    let mut committed = self.commit_current().unwrap_or_default();
+                    if let Some(text) = unicode::compose_conjoining(None, None, Some(ch)) {
+                        committed.push_str(&text);
+                    }
+                    AutomataResult::handled(Some(committed), None)
+                }
+            };
+        }
+
+        // 자모가 아닌 입력 → 현재 조합 확정 후 패스스루
+        if !current.is_empty() {
+            let committed = self.commit_current();
+            return AutomataResult::handled(committed, None);
+        }
+        AutomataResult::not_handled()
+    }
+
+    fn backspace(&mut self) -> AutomataResult {
+        match self.history.pop() {
+            Some(prev) => {
+                self.syllable = prev;
+                AutomataResult::handled(None, self.syllable.to_string())
+            }
+            None if !self.syllable.is_empty() => {
+                // 불변식 위반 방어: 기록 없이 조합이 남아 있으면 통째로 지운다.
+                crate::warn_unexpected("backspace (old)", "history is empty");
+                self.syllable = Syllable::default();
+                AutomataResult::handled(None, None)
+            }
+            None => AutomataResult::not_handled(),
+        }
+    }
+
+    fn flush(&mut self) -> AutomataResult {
+        if self.syllable.is_empty() {
+            self.history.clear();
+            return AutomataResult::handled(None, None);
+        }
+        let committed = self.commit_current();
+        AutomataResult::handled(committed, None)
+    }
+
+    fn composing_text(&self) -> Option<String> {
+        self.syllable.to_string()
+    }
+
+    fn snapshot(&self) -> AutomataSnapshot {
+        AutomataSnapshot::OldHangul {
+            syllable: self.syllable.into(),
+            history: self.history.iter().map(|&s| s.into()).collect(),
+        }
+    }
+
+    fn restore(&mut self, snapshot: &AutomataSnapshot, _layout: &KeyboardLayout) -> bool {
+        let AutomataSnapshot::OldHangul { syllable, history } = snapshot else {
+            return false;
+        };
+        let (Some(syllable), Some(history)) = (
+            Syllable::from_snapshot(syllable),
+            history.iter().map(Syllable::from_snapshot).collect(),
+        ) else {
+            return false;
+        };
+        self.syllable = syllable;
+        self.history = history;
+        true
+    }
+
+    fn state(&self) -> AutomataState {
+        let s = self.syllable;
+        // 직전 상태와 같은 슬롯이 이미 채워져 있었다면 결합된 것(겹자모)
+        let prev = self.history.last().copied().unwrap_or_default();
+        match (s.choseong, s.jungseong, s.jongseong) {
+            (None, None, None) => AutomataState::Empty,
+            (_, _, Some(_)) if prev.jongseong.is_some() => AutomataState::Jongseong2,
+            (_, _, Some(_)) => AutomataState::Jongseong,
+            (_, Some(_), None) if prev.jungseong.is_some() => AutomataState::Jungseong2,
+            (_, Some(_), None) => AutomataState::Jungseong,
+            (Some(_), None, None) => AutomataState::Choseong,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::KeyboardLayout;
+
+    const LAYOUT_OLD_JSON: &str = include_str!("../../layouts/3-390-old.json5");
+
+    fn make_layout() -> KeyboardLayout {
+        KeyboardLayout::from_json(LAYOUT_OLD_JSON).unwrap()
+    }
+
+    fn process_keys(
+        automata: &mut OldHangulAutomata,
+        layout: &KeyboardLayout,
+        keys: &[&str],
+    ) -> (String, Option<String>) {
+        let mut committed = String::new();
+        let mut composing = None;
+        for key in keys {
+            let ch = layout.map_key(key).unwrap();
+            let result = automata.process(ch, layout);
+            if let Some(c) = &result.committed {
+                committed.push_str(c);
+            }
+            composing = result.composing;
+        }
+        (committed, composing)
+    }
+
+    #[test]
+    fn test_modern_syllable_precomposed() {
+        // ㅎ초(m) ㅏ중(f) ㄴ종(s) → 완성형 "한"
+        let layout = make_layout();
+        let mut automata = OldHangulAutomata::new();
+        let (committed, composing) = process_keys(&mut automata, &layout, &["m", "f", "s"]);
+        assert_eq!(committed, "");
+        assert_eq!(composing, Some("한".to_string()));
+        assert_eq!(automata.state(), AutomataState::Jongseong);
+    }
+
+    #[test]
+    fn test_arae_a_conjoining() {
+        // ㅎ초(m) ㆍ(G) → 완성형 없음 → 첫가끝 "ᄒᆞ"
+        let layout = make_layout();
+        let mut automata = OldHangulAutomata::new();
+        let (_, composing) = process_keys(&mut automata, &layout, &["m", "G"]);
+        assert_eq!(composing, Some("\u{1112}\u{119E}".to_string()));
+    }
+
+    #[test]
+    fn test_old_choseong_syllable() {
+        // ㅿ(Y) ㅏ(f) ㆁ종(B) → "ᅀᅡᇰ"
+        let layout = make_layout();
+        let mut automata = OldHangulAutomata::new();
+        let (_, composing) = process_keys(&mut automata, &layout, &["Y", "f", "B"]);
+        assert_eq!(composing, Some("\u{1140}\u{1161}\u{11F0}".to_string()));
+    }
+
+    #[test]
+    fn test_three_jamo_chain() {
+        // ㅂ(;) + ㅅ(n) = ᄡ, + ㄱ(k) = ᄢ → ᄢ + ㅏ(f)
+        let layout = make_layout();
+        let mut automata = OldHangulAutomata::new();
+        let (committed, composing) = process_keys(&mut automata, &layout, &[";", "n", "k", "f"]);
+        assert_eq!(committed, "");
+        assert_eq!(composing, Some("\u{1122}\u{1161}".to_string()));
+    }
+
+    #[test]
+    fn test_chain_backspace_one_step() {
+        // ᄢ → BS → ᄡ → BS → ㅂ(호환 자모 표시)
+        let layout = make_layout();
+        let mut automata = OldHangulAutomata::new();
+        process_keys(&mut automata, &layout, &[";", "n", "k"]);
+        assert_eq!(
+            automata.backspace().composing,
+            Some("\u{1121}\u{1160}".to_string())
+        );
+        assert_eq!(automata.backspace().composing, Some("ㅂ".to_string()));
+        assert_eq!(automata.backspace().composing, None);
+        assert!(!automata.backspace().handled);
+    }
+
+    #[test]
+    fn test_ssang_arae_a() {
+        // ㆍ + ㆍ = ᆢ
+        let layout = make_layout();
+        let mut automata = OldHangulAutomata::new();
+        let (_, composing) = process_keys(&mut automata, &layout, &["k", "G", "G"]);
+        assert_eq!(composing, Some("\u{1100}\u{11A2}".to_string()));
+        assert_eq!(automata.state(), AutomataState::Jungseong2);
+    }
+
+    #[test]
+    fn test_new_syllable_commits_sequence() {
+        // ᄒᆞᆫ + ㄱ초 → "ᄒᆞᆫ" 확정 + "ㄱ"
+        let layout = make_layout();
+        let mut automata = OldHangulAutomata::new();
+        let (committed, composing) = process_keys(&mut automata, &layout, &["m", "G", "s", "k"]);
+        assert_eq!(committed, "\u{1112}\u{119E}\u{11AB}");
+        assert_eq!(composing, Some("ㄱ".to_string()));
+    }
+
+    #[test]
+    fn test_standalone_old_jongseong() {
+        // 중성 없이 ㆁ종 → 채움 문자와 함께 즉시 확정
+        let layout = make_layout();
+        let mut automata = OldHangulAutomata::new();
+        let (committed, composing) = process_keys(&mut automata, &layout, &["B"]);
+        assert_eq!(committed, "\u{115F}\u{1160}\u{11F0}");
+        assert_eq!(composing, None);
+    }
+
+    #[test]
+    fn test_flush_and_backspace_from_jongseong() {
+        let layout = make_layout();
+        let mut automata = OldHangulAutomata::new();
+        process_keys(&mut automata, &layout, &["Y", "f", "B"]);
+        assert_eq!(
+            automata.backspace().composing,
+            Some("\u{1140}\u{1161}".to_string())
+        );
+        let result = automata.flush();
+        assert_eq!(result.committed, Some("\u{1140}\u{1161}".to_string()));
+        assert_eq!(automata.state(), AutomataState::Empty);
+    }
+
+    #[test]
+    fn test_snapshot_keeps_backspace_history() {
+        // ᄢ 상태를 되살려도 백스페이스는 ᄡ → ㅂ 순서
+        let layout = make_layout();
+        let mut automata = OldHangulAutomata::new();
+        process_keys(&mut automata, &layout, &[";", "n", "k"]);
+
+        let mut restored = OldHangulAutomata::new();
+        assert!(restored.restore(&automata.snapshot(), &layout));
+        assert_eq!(restored.composing_text(), automata.composing_text());
+        assert_eq!(
+            restored.backspace().composing,
+            Some("\u{1121}\u{1160}".to_string())
+        );
+        assert_eq!(restored.backspace().composing, Some("ㅂ".to_string()));
+    }
+}
@@ -0,0 +1,466 @@
+//! 로마자 소리 입력(romaja) 오토마타
+//!
+//! "ng", "eo", "kk" 같은 여러 글자 키를 가장 긴 것부터 자모로 바꿔 두벌식 오토마타에 넘긴다.
+//! 입력한 키는 키 단위로 쌓아 두고, 새 키가 오면 마지막 키 하나(받침 전용 키 뒤에 모음이
+//! 오면 둘)만 되돌려 다시 해석하므로 뒤따르는 키에 따라 앞의 해석이 바뀔 수 있다
+//! (hang → 항, hangu → 한구). 글자가 확정되면 조합 중인 글자의 키만 남긴다.
+//! 모음으로 시작하는 음절에는 초성 ㅇ을 자동으로 넣는다.
+//!
+//! 여러 글자 키의 앞부분(예: "ch"의 "c")은 조합 중에 그대로 보여 주고, 다음 키로 키를
+//! 이루지 못하면 한글이 될 수 없으므로 버린다. (ca → 아)
+
+use crate::layout::KeyboardLayout;
+use crate::unicode;
+
+use super::jamo::JamoAutomata;
+use super::{Automata, AutomataResult, AutomataSnapshot, AutomataState};
+
+/// 모음 앞에 자동으로 넣는 초성
+const IMPLICIT_CHOSEONG: char = 'ㅇ';
+
+/// 해석한 로마자 키 하나
+#[derive(Clone)]
+struct Token {
+    /// 키 (소문자)
+    keys: String,
+    /// 키의 자모. None이면 아직 키 하나로 완성되지 않은 앞부분 (예: "ch"를 치는 중의 "c")
+    ch: Option<char>,
+    /// 이 키를 넣기 전의 두벌식 오토마타
+    before: JamoAutomata,
+}
+
+/// 로마자 소리 입력 오토마타
+pub struct RomajaAutomata {
+    /// 조합 중인 글자의 키
+    tokens: Vec<Token>,
+    /// `tokens`를 해석한 두벌식 오토마타
+    inner: JamoAutomata,
+    /// 백스페이스 복원용: 조합 중인 글자의 키를 하나씩 넣기 전 상태
+    history: Vec<(Vec<Token>, JamoAutomata)>,
+}
+
+impl Default for RomajaAutomata {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RomajaAutomata {
+    pub fn new() -> Self {
+        RomajaAutomata {
+            tokens: Vec::new(),
+            inner: JamoAutomata::new(),
+            history: Vec::new(),
+        }
+    }
+
+    /// `input` 앞부분의 키 하나를 찾는다.
+    /// 받침 전용 키(final_only) 뒤에 모음이 오면 더 짧은 키로 나눈다.
+    fn next_token(input: &str, layout: &KeyboardLayout) -> Option<(usize, char)> {
+        let (len, ch) = layout.longest_key_match(input)?;
+        let vowel_follows = layout
+            .longest_key_match(&input[len..])
+            .is_some_and(|(_, next)| unicode::is_compat_vowel(next));
+        if Self::is_final_only(&input[..len], layout) && vowel_follows {
+            return layout
+                .longest_key_match(&input[..len - 1])
+                .or(Some((len, ch)));
+        }
+        Some((len, ch))
+    }
+
+    fn is_final_only(keys: &str, layout: &KeyboardLayout) -> bool {
+        layout.options.final_only.iter().any(|k| k == keys)
+    }
+
+    /// 마지막 키를 되돌리고 그 키를 반환한다.
+    fn pop_token(&mut self) -> Option<Token> {
+        let token = self.tokens.pop()?;
+        self.inner = token.before.clone();
+        Some(token)
+    }
+
+    /// 자모 하나를 넣고 키를 쌓는다. 확정된 텍스트를 반환한다.
+    fn push_token(&mut self, keys: &str, ch: Option<char>, layout: &KeyboardLayout) -> String {
+        let before = self.inner.clone();
+        let mut committed = String::new();
+        if let Some(ch) = ch {
+            let needs_choseong = matches!(
+                self.inner.state(),
+                AutomataState::Empty | AutomataState::Jungseong | AutomataState::Jungseong2
+            );
+            if unicode::is_compat_vowel(ch) && needs_choseong {
+                committed.extend(self.inner.process(IMPLICIT_CHOSEONG, layout).committed);
+            }
+            committed.extend(self.inner.process(ch, layout).committed);
+        }
+        self.tokens.push(Token {
+            keys: keys.to_string(),
+            ch,
+            before,
+        });
+        committed
+    }
+
+    /// 키열을 키 단위로 나눠 차례로 넣는다. 확정된 텍스트를 반환한다.
+    fn feed(&mut self, input: &str, layout: &KeyboardLayout) -> String {
+        let mut committed = String::new();
+        let mut rest = input;
+        while let Some(first) = rest.chars().next() {
+            match Self::next_token(rest, layout) {
+                Some((len, ch)) => {
+                    committed.push_str(&self.push_token(&rest[..len], Some(ch), layout));
+                    rest = &rest[len..];
+                }
+                None if layout.is_key_prefix(rest) => {
+                    // 여러 글자 키를 입력하는 중
+                    committed.push_str(&self.push_token(rest, None, layout));
+                    break;
+                }
+                // 키가 될 수 없는 앞부분 → 버린다
+                None => rest = &rest[first.len_utf8()..],
+            }
+        }
+        committed
+    }
+
+    /// 로마자 키 하나를 넣는다. 다시 해석할 키만 되돌리므로 조합 중인 키 수와 무관하게 처리한다.
+    fn push_key(&mut self, key: &str, layout: &KeyboardLayout) -> String {
+        self.history.push((self.tokens.clone(), self.inner.clone()));
+        let mut input = key.to_string();
+        // 마지막 키를 늘려 더 긴 키가 되거나, 완성되지 않은 앞부분이면 이어서 다시 해석한다
+        if let Some(last) = self.tokens.last()
+            && (last.ch.is_none() || layout.is_key_prefix(&format!("{}{key}", last.keys)))
+            && let Some(last) = self.pop_token()
+        {
+            input.insert_str(0, &last.keys);
+        }
+        // 받침 전용 키 뒤에 모음이 오면 받침 전용 키도 다시 나눈다 (hang + u → han + gu)
+        let vowel = layout
+            .longest_key_match(&input)
+            .is_some_and(|(_, ch)| unicode::is_compat_vowel(ch));
+        if vowel
+            && let Some(last) = self.tokens.last()
+            && last.ch.is_some()
+            && Self::is_final_only(&last.keys, layout)
+            && let Some(last) = self.pop_token()
+        {
+            input.insert_str(0, &last.keys);
+        }
+        let committed = self.feed(&input, layout);
+        if !committed.is_empty() {
+            self.trim(layout);
+        } else if self.history.len() != self.keys().len() {
+            // 버린 키가 있으면 남은 키로 백스페이스 이력을 다시 만든다
+            if let Some(rebuilt) = Self::from_keys(&self.keys(), layout) {
+                *self = rebuilt;
+            }
+        }
+        committed
+    }
+
+    /// 로마자 키열을 빈 오토마타에 차례로 넣는다. 확정되는 글자가 있으면 None.
+    fn from_keys(keys: &str, layout: &KeyboardLayout) -> Option<RomajaAutomata> {
+        let mut automata = RomajaAutomata::new();
+        for ch in keys.chars() {
+            let key = ch.to_string();
+            if !ch.is_ascii_lowercase() || !layout.is_key_prefix(&key) {
+                return None;
+            }
+            if !automata.push_key(&key, layout).is_empty() {
+                return None;
+            }
+        }
+        Some(automata)
+    }
+
+    /// 글자가 확정된 뒤 조합 중인 글자를 이루는 가장 긴 뒷부분의 키만 남긴다.
+    /// 키 경계에서만 자르므로 한 글자 분량의 키만 다시 넣는다.
+    fn trim(&mut self, layout: &KeyboardLayout) {
+        let composing = self.composing_text();
+        let keys = self.keys();
+        let suffix = self
+            .tokens
+            .iter()
+            .scan(0, |start, token| {
+                *start += token.keys.len();
+                Some(*start)
+            })
+            .find_map(|start| {
+                Self::from_keys(&keys[start..], layout)
+                    .filter(|candidate| candidate.composing_text() == composing)
+            });
+        match suffix {
+            Some(suffix) => *self = suffix,
+            None => {
+                // 뒷부분만으로 재현되지 않으면 조합 중인 키를 더 되돌릴 수 없다
+                crate::warn_unexpected("romaja trim", &keys);
+                self.tokens.clear();
+                self.history.clear();
+            }
+        }
+    }
+
+    /// 조합 중인 로마자 키
+    fn keys(&self) -> String {
+        self.tokens.iter().map(|t| t.keys.as_str()).collect()
+    }
+
+    fn clear(&mut self) {
+        self.tokens.clear();
+        self.inner = JamoAutomata::new();
+        self.history.clear();
+    }
+}
+
+impl Automata for RomajaAutomata {
+    /// 로마자 키를 거치지 않은 자모는 지금까지의 조합을 확정한 뒤 그대로 입력한다.
+    fn process(&mut self, ch: char, _layout: &KeyboardLayout) -> AutomataResult {
+        let mut committed = self.flush().committed.unwrap_or_default();
+        committed.push(ch);
+        AutomataResult::handled(Some(committed), None)
+    }
+
+    /// 백스페이스는 로마자 키 하나를 지운다.
+    fn backspace(&mut self) -> AutomataResult {
+        if self.tokens.is_empty() {
+            return AutomataResult::not_handled();
+        }
+        match self.history.pop() {
+            Some((tokens, inner)) => {
+                self.tokens = tokens;
+                self.inner = inner;
+            }
+            None => {
+                crate::warn_unexpected("backspace (romaja)", "history is empty");
+                self.clear();
+            }
+        }
+        AutomataResult::handled(None, self.composing_text())
+    }
+
+    /// 조합 중인 글자를 확정한다. 키가 되지 못한 앞부분은 한글이 아니므로 버린다.
+    fn flush(&mut self) -> AutomataResult {
+        let text = self.inner.composing_text();
+        self.clear();
+        AutomataResult::handled(text, None)
+    }
+
+    fn composing_text(&self) -> Option<String> {
+        let mut text = self.inner.composing_text().unwrap_or_default();
+        if let Some(Token { keys, ch: None, .. }) = self.tokens.last() {
+            text.push_str(keys);
+        }
+        (!text.is_empty()).then_some(text)
+    }
+
+    fn state(&self) -> AutomataState {
+        self.inner.state()
+    }
+
+    fn snapshot(&self) -> AutomataSnapshot {
+        AutomataSnapshot::Romaja { keys: self.keys() }
+    }
+
+    /// 키열을 다시 입력해 되살린다. 입력 중 확정되는 글자가 있으면 이 오토마타의 키열이 아니다.
+    fn restore(&mut self, snapshot: &AutomataSnapshot, layout: &KeyboardLayout) -> bool {
+        let AutomataSnapshot::Romaja { keys } = snapshot else {
+            return false;
+        };
+        match Self::from_keys(keys, layout) {
+            Some(automata) => {
+                *self = automata;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn process_key(&mut self, key: &str, layout: &KeyboardLayout) -> Option<AutomataResult> {
+        let key = key.to_ascii_lowercase();
+        if key.chars().count() != 1 || !layout.is_key_prefix(&key) {
+            return None;
+        }
+        let committed = self.push_key(&key, layout);
+        Some(AutomataResult::handled(
+            (!committed.is_empty()).then_some(committed),
+            self.composing_text(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::KeyboardLayout;
+
+    const LAYOUT_ROMAJA_JSON: &str = include_str!("../../layouts/romaja.json5");
+
+    fn make_layout() -> KeyboardLayout {
+        KeyboardLayout::from_json(LAYOUT_ROMAJA_JSON).unwrap()
+    }
+
+    fn type_text(
+        automata: &mut RomajaAutomata,
+        layout: &KeyboardLayout,
+        text: &str,
+    ) -> (String, Option<String>) {
+        let mut committed = String::new();
+        let mut composing = None;
+        for ch in text.chars() {
+            let result = automata.process_key(&ch.to_string(), layout).unwrap();
+            if let Some(c) = &result.committed {
+                committed.push_str(c);
+            }
+            composing = result.composing;
+        }
+        (committed, composing)
+    }
+
+    #[test]
+    fn test_hangul() {
+        let layout = make_layout();
+        let mut automata = RomajaAutomata::new();
+        let (committed, composing) = type_text(&mut automata, &layout, "hangul");
+        assert_eq!(committed, "한");
+        assert_eq!(composing, Some("굴".to_string()));
+    }
+
+    #[test]
+    fn test_ng_reinterpreted_by_following_vowel() {
+        // hang → 항, hangu → 한구
+        let layout = make_layout();
+        let mut automata = RomajaAutomata::new();
+        let (_, composing) = type_text(&mut automata, &layout, "hang");
+        assert_eq!(composing, Some("항".to_string()));
+        let (committed, composing) = type_text(&mut automata, &layout, "u");
+        assert_eq!(committed, "한");
+        assert_eq!(composing, Some("구".to_string()));
+    }
+
+    #[test]
+    fn test_implicit_ieung() {
+        // seoul → 서울, annyeong → 안녕
+        let layout = make_layout();
+        let mut automata = RomajaAutomata::new();
+        let (committed, composing) = type_text(&mut automata, &layout, "seoul");
+        assert_eq!(committed, "서");
+        assert_eq!(composing, Some("울".to_string()));
+
+        let mut automata = RomajaAutomata::new();
+        let (committed, composing) = type_text(&mut automata, &layout, "annyeong");
+        assert_eq!(committed, "안");
+        assert_eq!(composing, Some("녕".to_string()));
+    }
+
+    #[test]
+    fn test_multi_letter_vowels_and_consonants() {
+        // saranghae → 사랑해 (ng 뒤에 자음 → 받침 ㅇ, ae → ㅐ)
+        let layout = make_layout();
+        let mut automata = RomajaAutomata::new();
+        let (committed, composing) = type_text(&mut automata, &layout, "saranghae");
+        assert_eq!(committed, "사랑");
+        assert_eq!(composing, Some("해".to_string()));
+    }
+
+    #[test]
+    fn test_partial_key_shown_as_tail() {
+        // "c"는 "ch"의 앞부분 → 조합 중 "c"로 표시
+        let layout = make_layout();
+        let mut automata = RomajaAutomata::new();
+        let (_, composing) = type_text(&mut automata, &layout, "c");
+        assert_eq!(composing, Some("c".to_string()));
+        let (_, composing) = type_text(&mut automata, &layout, "ha");
+        assert_eq!(composing, Some("차".to_string()));
+    }
+
+    #[test]
+    fn test_backspace_one_keystroke() {
+        // han → BS → ha → BS → h → BS → 없음
+        let layout = make_layout();
+        let mut automata = RomajaAutomata::new();
+        type_text(&mut automata, &layout, "han");
+        assert_eq!(automata.backspace().composing, Some("하".to_string()));
+        assert_eq!(automata.backspace().composing, Some("ㅎ".to_string()));
+        assert_eq!(automata.backspace().composing, None);
+        assert!(!automata.backspace().handled);
+    }
+
+    #[test]
+    fn test_backspace_after_commit() {
+        // hangu("한" 확정, "구" 조합) → BS → "ㄱ"
+        let layout = make_layout();
+        let mut automata = RomajaAutomata::new();
+        type_text(&mut automata, &layout, "hangu");
+        assert_eq!(automata.backspace().composing, Some("ㄱ".to_string()));
+    }
+
+    #[test]
+    fn test_non_romaja_key_not_handled() {
+        let layout = make_layout();
+        let mut automata = RomajaAutomata::new();
+        assert!(automata.process_key("1", &layout).is_none());
+        assert!(automata.process_key("x", &layout).is_none());
+        // 대문자는 소문자로
+        assert!(automata.process_key("H", &layout).is_some());
+    }
+
+    #[test]
+    fn test_snapshot_replays_keys() {
+        // "hang"을 되살리면 뒤따르는 모음에 따라 다시 해석된다 (hangu → 한구)
+        let layout = make_layout();
+        let mut automata = RomajaAutomata::new();
+        type_text(&mut automata, &layout, "hang");
+
+        let mut restored = RomajaAutomata::new();
+        assert!(restored.restore(&automata.snapshot(), &layout));
+        assert_eq!(restored.composing_text(), Some("항".to_string()));
+        let (committed, composing) = type_text(&mut restored, &layout, "u");
+        assert_eq!(committed, "한");
+        assert_eq!(composing, Some("구".to_string()));
+        assert_eq!(restored.backspace().composing, Some("ㄱ".to_string()));
+    }
+
+    #[test]
+    fn test_unmatched_prefix_is_dropped() {
+        // "c" 뒤에 "h"가 오지 않으면 키가 될 수 없으므로 버린다 (라틴 문자를 확정하지 않는다)
+        let layout = make_layout();
+        let mut automata = RomajaAutomata::new();
+        let (committed, composing) = type_text(&mut automata, &layout, "ca");
+        assert_eq!(committed, "");
+        assert_eq!(composing, Some("아".to_string()));
+        assert_eq!(automata.backspace().composing, None);
+
+        let mut automata = RomajaAutomata::new();
+        let (committed, composing) = type_text(&mut automata, &layout, "hacca");
+        assert_eq!(committed, "하");
+        assert_eq!(composing, Some("아".to_string()));
+
+        // 완성되지 않은 앞부분은 확정하지 않는다
+        let mut automata = RomajaAutomata::new();
+        type_text(&mut automata, &layout, "hac");
+        assert_eq!(automata.composing_text(), Some("하c".to_string()));
+        assert_eq!(automata.flush().committed, Some("하".to_string()));
+    }
+
+    #[test]
+    fn test_long_input_keeps_only_composing_keys() {
+        // 키를 계속 쳐도 조합 중인 글자의 키만 남는다
+        let layout = make_layout();
+        let mut automata = RomajaAutomata::new();
+        let (committed, composing) = type_text(&mut automata, &layout, &"hangul".repeat(2000));
+        assert_eq!(committed, format!("{}한", "한굴".repeat(1999)));
+        assert_eq!(composing, Some("굴".to_string()));
+        assert_eq!(
+            automata.snapshot(),
+            AutomataSnapshot::Romaja { keys: "gul".into() }
+        );
+        assert_eq!(automata.history.len(), 3);
+
+        let mut automata = RomajaAutomata::new();
+        let (committed, composing) = type_text(&mut automata, &layout, &"c".repeat(5000));
+        assert_eq!(committed, "");
+        assert_eq!(composing, Some("c".to_string()));
+        assert_eq!(automata.history.len(), 1);
+    }
+}
@@ -0,0 +1,238 @@
+//! 신세벌식(Shin-sebeolsik) 오토마타
+//!
+//! 왼손 글쇠가 상태에 따라 종성 또는 중성이 되는 갈마들이 방식.
+//! 키 해석만 상태에 따라 달라지고, 음절 조합은 세벌식 오토마타와 같다.
+//! 겹모음은 갈마들이가 아닌 일반 중성 글쇠(오른손 ㅗ/ㅜ/ㅡ)로 넣은 모음 뒤에서만 만든다.
+
+use crate::layout::KeyboardLayout;
+use crate::unicode;
+
+use super::jaso::JasoAutomata;
+use super::{Automata, AutomataResult, AutomataSnapshot, AutomataState};
+
+/// 신세벌식 오토마타
+pub struct ShinAutomata {
+    inner: JasoAutomata,
+    /// 현재 중성이 일반 중성 글쇠로 입력되어 겹모음을 이룰 수 있는지
+    vowel_combinable: bool,
+}
+
+impl Default for ShinAutomata {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ShinAutomata {
+    pub fn new() -> Self {
+        ShinAutomata {
+            inner: JasoAutomata::new(),
+            vowel_combinable: false,
+        }
+    }
+
+    /// 중성 상태에서 키의 갈마들이 모음이 현재 모음과 겹모음을 이루면 그 모음을 반환한다.
+    fn compound_vowel(&self, key: &str, layout: &KeyboardLayout) -> Option<char> {
+        if !self.vowel_combinable || self.inner.state() != AutomataState::Jungseong {
+            return None;
+        }
+        let current = self.inner.jungseong()?;
+        let vowel = layout.map_key_in_state(key, AutomataState::Choseong)?;
+        layout
+            .combine(current, vowel)
+            .filter(|&ch| unicode::is_jungseong(ch))
+            .map(|_| vowel)
+    }
+}
+
+impl Automata for ShinAutomata {
+    fn process(&mut self, ch: char, layout: &KeyboardLayout) -> AutomataResult {
+        self.inner.process(ch, layout)
+    }
+
+    fn backspace(&mut self) -> AutomataResult {
+        let before = self.inner.state();
+        let result = self.inner.backspace();
+        // 겹모음은 일반 중성 글쇠 뒤에서만 만들어지므로, 겹모음을 지우면 다시 겹모음을 이룰 수 있다
+        if before == AutomataState::Jungseong2 && self.inner.state() == AutomataState::Jungseong {
+            self.vowel_combinable = true;
+        }
+        result
+    }
+
+    fn flush(&mut self) -> AutomataResult {
+        self.inner.flush()
+    }
+
+    fn composing_text(&self) -> Option<String> {
+        self.inner.composing_text()
+    }
+
+    fn state(&self) -> AutomataState {
+        self.inner.state()
+    }
+
+    fn snapshot(&self) -> AutomataSnapshot {
+        AutomataSnapshot::Shin {
+            inner: self.inner.buffer_snapshot(),
+            vowel_combinable: self.vowel_combinable,
+        }
+    }
+
+    fn restore(&mut self, snapshot: &AutomataSnapshot, _layout: &KeyboardLayout) -> bool {
+        let AutomataSnapshot::Shin {
+            inner,
+            vowel_combinable,
+        } = snapshot
+        else {
+            return false;
+        };
+        if !self.inner.restore_buffer(inner) {
+            return false;
+        }
+        self.vowel_combinable = *vowel_combinable;
+        true
+    }
+
+    fn load_syllable(&mut self, ch: char, layout: &KeyboardLayout) -> bool {
+        // 되살린 모음은 어느 글쇠로 넣었는지 알 수 없으므로 겹모음을 만들지 않는다
+        self.vowel_combinable = false;
+        self.inner.load_syllable(ch, layout)
+    }
+
+    fn resolve_key(&mut self, key: &str, layout: &KeyboardLayout) -> Option<char> {
+        let ch = self
+            .compound_vowel(key, layout)
+            .or_else(|| layout.map_key_in_state(key, self.inner.state()))?;
+        if unicode::is_jungseong(ch) {
+            self.vowel_combinable = !layout.is_contextual(key);
+        }
+        Some(ch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::KeyboardLayout;
+
+    const LAYOUT_SHIN_JSON: &str = include_str!("../../layouts/3-shin.json5");
+
+    fn make_layout() -> KeyboardLayout {
+        KeyboardLayout::from_json(LAYOUT_SHIN_JSON).unwrap()
+    }
+
+    fn process_keys(
+        automata: &mut ShinAutomata,
+        layout: &KeyboardLayout,
+        keys: &[&str],
+    ) -> (String, Option<String>) {
+        let mut committed = String::new();
+        let mut composing = None;
+        for key in keys {
+            let ch = automata.resolve_key(key, layout).unwrap();
+            let result = automata.process(ch, layout);
+            if let Some(c) = &result.committed {
+                committed.push_str(c);
+            }
+            composing = result.composing;
+        }
+        (committed, composing)
+    }
+
+    #[test]
+    fn test_left_key_is_vowel_after_choseong() {
+        // ㅎ초(m) + f(초성 뒤 ㅏ) + s(ㄴ종) → "한"
+        let layout = make_layout();
+        let mut automata = ShinAutomata::new();
+        let (committed, composing) = process_keys(&mut automata, &layout, &["m", "f", "s"]);
+        assert_eq!(committed, "");
+        assert_eq!(composing, Some("한".to_string()));
+    }
+
+    #[test]
+    fn test_same_key_vowel_then_jongseong() {
+        // ㄷ초(u) + f(ㅏ) + f(ㄷ종) → "닫"
+        let layout = make_layout();
+        let mut automata = ShinAutomata::new();
+        let (_, composing) = process_keys(&mut automata, &layout, &["u", "f", "f"]);
+        assert_eq!(composing, Some("닫".to_string()));
+    }
+
+    #[test]
+    fn test_compound_vowel_from_plain_key() {
+        // ㄱ초(k) + ㅗ(/) + f(ㅏ) → "과"
+        let layout = make_layout();
+        let mut automata = ShinAutomata::new();
+        let (_, composing) = process_keys(&mut automata, &layout, &["k", "/", "f"]);
+        assert_eq!(composing, Some("과".to_string()));
+        assert_eq!(automata.state(), AutomataState::Jungseong2);
+    }
+
+    #[test]
+    fn test_alternating_vowel_does_not_combine() {
+        // ㄱ초(k) + v(갈마들이 ㅗ) + f(ㄷ종) → "곧"
+        let layout = make_layout();
+        let mut automata = ShinAutomata::new();
+        let (_, composing) = process_keys(&mut automata, &layout, &["k", "v", "f"]);
+        assert_eq!(composing, Some("곧".to_string()));
+    }
+
+    #[test]
+    fn test_left_key_without_choseong_is_jongseong() {
+        // 빈 상태에서 왼손 글쇠 → 종성(독립 종성 확정)
+        let layout = make_layout();
+        let mut automata = ShinAutomata::new();
+        assert_eq!(automata.resolve_key("s", &layout), Some('\u{11AB}'));
+    }
+
+    #[test]
+    fn test_backspace_restores_state_for_next_key() {
+        // "한" → BS → "하" → s는 다시 ㄴ종
+        let layout = make_layout();
+        let mut automata = ShinAutomata::new();
+        process_keys(&mut automata, &layout, &["m", "f", "s"]);
+        assert_eq!(automata.backspace().composing, Some("하".to_string()));
+        assert_eq!(automata.backspace().composing, Some("ㅎ".to_string()));
+        // 초성만 남으면 s는 다시 모음(ㅒ)
+        let (_, composing) = process_keys(&mut automata, &layout, &["s"]);
+        assert_eq!(composing, Some("햬".to_string()));
+    }
+
+    #[test]
+    fn test_backspace_after_compound_vowel_combines_again() {
+        // "과" → BS → "고" → f는 다시 ㅏ로 겹모음 "과"
+        let layout = make_layout();
+        let mut automata = ShinAutomata::new();
+        process_keys(&mut automata, &layout, &["k", "/", "f"]);
+        assert_eq!(automata.backspace().composing, Some("고".to_string()));
+        let (_, composing) = process_keys(&mut automata, &layout, &["f"]);
+        assert_eq!(composing, Some("과".to_string()));
+
+        // ㅜ+ㅓ=ㅝ, ㅡ+ㅣ=ㅢ도 같다
+        for (keys, compound, single) in
+            [(["k", "9", "t"], "궈", "구"), (["k", "8", "d"], "긔", "그")]
+        {
+            let mut automata = ShinAutomata::new();
+            process_keys(&mut automata, &layout, &keys);
+            assert_eq!(automata.backspace().composing.as_deref(), Some(single));
+            let (_, composing) = process_keys(&mut automata, &layout, &keys[2..]);
+            assert_eq!(composing.as_deref(), Some(compound));
+        }
+    }
+
+    #[test]
+    fn test_restored_compound_vowel_backspace() {
+        // 스냅샷으로 되살린 "과"에서도 지운 뒤 다시 겹모음을 만든다
+        let layout = make_layout();
+        let mut automata = ShinAutomata::new();
+        process_keys(&mut automata, &layout, &["k", "/", "f"]);
+        let snapshot = automata.snapshot();
+
+        let mut restored = ShinAutomata::new();
+        assert!(restored.restore(&snapshot, &layout));
+        assert_eq!(restored.backspace().composing, Some("고".to_string()));
+        let (_, composing) = process_keys(&mut restored, &layout, &["f"]);
+        assert_eq!(composing, Some("과".to_string()));
+    }
+}
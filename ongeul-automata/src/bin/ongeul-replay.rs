@@ -0,0 +1,93 @@
+//! 키 입력 기록 재현 도구
+//!
+//! `HangulEngine::start_recording`으로 만든 기록을 현재 엔진에 다시 입력하고,
+//! 결과가 처음 달라지는 곳을 보고한다. 모두 같으면 0, 달라지면 1, 오류면 2로 끝난다.
+//!
+//! 사용법: ongeul-replay <기록 파일> [--layouts <레이아웃 디렉터리>]
+//!
+//! 레이아웃은 동봉 레이아웃에서 찾는다. `--layouts`를 주면 그 디렉터리의 레이아웃을 먼저 찾는다.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::ExitCode;
+
+use ongeul_automata::layout;
+use ongeul_automata::record::{self, Output};
+
+/// 레이아웃 디렉터리의 JSON5 파일을 ID별로 읽는다.
+fn load_layouts(dir: &Path) -> Result<HashMap<String, String>, String> {
+    let mut layouts = HashMap::new();
+    for file in layout::read_dir(dir)? {
+        match file.layout {
+            Ok(layout) => {
+                layouts.insert(layout.id, file.json);
+            }
+            Err(e) => eprintln!("skip {}: {e}", file.path.display()),
+        }
+    }
+    Ok(layouts)
+}
+
+fn describe(out: &Option<Output>) -> String {
+    match out {
+        Some(out) => json5::to_string(out).unwrap_or_else(|e| e.to_string()),
+        None => "(no result)".to_string(),
+    }
+}
+
+fn run(record_path: &Path, layouts_dir: Option<&Path>) -> Result<bool, String> {
+    let text = std::fs::read_to_string(record_path)
+        .map_err(|e| format!("{}: {e}", record_path.display()))?;
+    let entries = record::parse_record(&text)?;
+    let layouts = match layouts_dir {
+        Some(dir) => load_layouts(dir)?,
+        None => HashMap::new(),
+    };
+    let find = |id: &str| {
+        layouts
+            .get(id)
+            .cloned()
+            .or_else(|| layout::registry::builtin_json(id).map(str::to_string))
+    };
+
+    match record::replay(&entries, find)? {
+        None => {
+            println!("OK: {} calls reproduced", entries.len());
+            Ok(true)
+        }
+        Some(divergence) => {
+            let entry = &divergence.entry;
+            // 헤더가 첫 줄이므로 항목 번호 + 2
+            println!(
+                "Diverged at line {} (t = {} ms): {:?}",
+                divergence.index + 2,
+                entry.t,
+                entry.call
+            );
+            println!("  recorded: {}", describe(&entry.out));
+            println!("  actual:   {}", describe(&divergence.actual));
+            Ok(false)
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (record_path, layouts_dir) = match args.as_slice() {
+        [path] => (path.as_str(), None),
+        [path, flag, dir] if flag == "--layouts" => (path.as_str(), Some(Path::new(dir))),
+        _ => {
+            eprintln!("usage: ongeul-replay <record file> [--layouts <dir>]");
+            return ExitCode::from(2);
+        }
+    };
+
+    match run(Path::new(record_path), layouts_dir) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(1),
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::from(2)
+        }
+    }
+}
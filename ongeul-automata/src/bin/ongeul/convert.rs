@@ -0,0 +1,247 @@
+//! 비대화형 변환: 키 입력열을 읽어 결과 텍스트를 출력한다.
+//!
+//! 입력 한 줄이 입력열 하나다. 줄마다 새 엔진(한글 모드)에서 입력하고 끝에서 조합을 확정해
+//! 결과를 한 줄씩 출력하므로 입력과 출력의 줄이 맞는다.
+//! `--json`이면 모든 중간 `ProcessResult`를 JSON Lines로 출력한다.
+//! `--vectors`면 끝에서 확정하지 않고 골든 테스트 벡터([`golden`]) 형식으로 출력한다.
+//!
+//! 입력열 표기는 [`keyseq`]: `{bs}` 백스페이스, `{flush}` 확정, `{toggle}` 한/영 전환, `{{`는 `{`.
+//! `--raw`면 토큰 없이 모든 글자를 키 레이블로 보낸다.
+
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::process::ExitCode;
+
+use ongeul_automata::keyseq::{self, Document, KeyToken};
+use ongeul_automata::layout::KeyboardLayout;
+use ongeul_automata::record::Output;
+use ongeul_automata::{HangulEngine, InputMode, golden};
+use serde::Serialize;
+
+pub const USAGE: &str = "usage: ongeul convert [--layout <id> | --layout-file <path>] \
+[--layouts <dir>] [--raw] [--json | --vectors] [<file> | -]";
+
+/// `--json` 출력 한 줄: 입력 줄 번호, 토큰, 결과, 그때까지의 확정 텍스트
+#[derive(Serialize)]
+struct Step<'a> {
+    line: usize,
+    key: String,
+    #[serde(flatten)]
+    out: Output,
+    text: &'a str,
+}
+
+/// 출력 형식
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    /// 줄마다 확정한 결과 텍스트
+    Text,
+    /// 토큰마다 결과 (JSON Lines)
+    Json,
+    /// 골든 테스트 벡터
+    Vectors,
+}
+
+struct Options {
+    layout_id: String,
+    layout_file: Option<String>,
+    /// `--layouts` 디렉터리 (없으면 동봉 레이아웃)
+    layouts_dir: Option<String>,
+    raw: bool,
+    format: Format,
+    input: Option<String>,
+}
+
+fn parse_args(args: &[String]) -> Option<Options> {
+    let mut options = Options {
+        layout_id: "2-standard".to_string(),
+        layout_file: None,
+        layouts_dir: None,
+        raw: false,
+        format: Format::Text,
+        input: None,
+    };
+    let mut rest = args;
+    while !rest.is_empty() {
+        rest = match rest {
+            [flag, id, tail @ ..] if flag == "--layout" => {
+                options.layout_id = id.clone();
+                tail
+            }
+            [flag, path, tail @ ..] if flag == "--layout-file" => {
+                options.layout_file = Some(path.clone());
+                tail
+            }
+            [flag, dir, tail @ ..] if flag == "--layouts" => {
+                options.layouts_dir = Some(dir.clone());
+                tail
+            }
+            [flag, tail @ ..] if flag == "--raw" => {
+                options.raw = true;
+                tail
+            }
+            [flag, tail @ ..] if flag == "--json" => {
+                options.format = Format::Json;
+                tail
+            }
+            [flag, tail @ ..] if flag == "--vectors" => {
+                options.format = Format::Vectors;
+                tail
+            }
+            [input, tail @ ..] if options.input.is_none() && !input.starts_with("--") => {
+                options.input = Some(input.clone());
+                tail
+            }
+            _ => return None,
+        };
+    }
+    Some(options)
+}
+
+/// 변환할 레이아웃의 JSON5 원본
+fn layout_json(options: &Options) -> Result<String, String> {
+    if let Some(path) = &options.layout_file {
+        return std::fs::read_to_string(path).map_err(|e| format!("{path}: {e}"));
+    }
+    crate::read_layouts(options.layouts_dir.as_deref().map(Path::new))?
+        .into_iter()
+        .find(|file| {
+            file.layout
+                .as_ref()
+                .is_ok_and(|l| l.id == options.layout_id)
+        })
+        .map(|file| file.json)
+        .ok_or_else(|| format!("Layout '{}' not found", options.layout_id))
+}
+
+pub fn read_input(input: Option<&str>) -> Result<String, String> {
+    match input {
+        None | Some("-") => {
+            let mut text = String::new();
+            io::stdin()
+                .read_to_string(&mut text)
+                .map_err(|e| format!("stdin: {e}"))?;
+            Ok(text)
+        }
+        Some(path) => std::fs::read_to_string(path).map_err(|e| format!("{path}: {e}")),
+    }
+}
+
+/// 입력열 한 줄을 입력하고 끝에서 조합을 확정한다.
+/// `step`은 토큰마다 결과와 그때까지의 문서로 호출된다.
+fn convert_line(
+    engine: &HangulEngine,
+    tokens: &[KeyToken],
+    mut step: impl FnMut(&KeyToken, &Output, &Document) -> io::Result<()>,
+) -> io::Result<String> {
+    let mut doc = Document::default();
+    for token in tokens.iter().chain([&KeyToken::Flush]) {
+        let result = doc.input(engine, token);
+        step(token, &Output::from(&result), &doc)?;
+    }
+    Ok(doc.text)
+}
+
+fn run(options: &Options) -> Result<(), String> {
+    let json = layout_json(options)?;
+    let text = read_input(options.input.as_deref())?;
+    let mut out = io::BufWriter::new(io::stdout().lock());
+    let io_err = |e: io::Error| format!("stdout: {e}");
+    if options.format == Format::Vectors {
+        let layout = KeyboardLayout::from_json(&json).map_err(|e| e.to_string())?;
+        writeln!(out, "@layout {}", layout.id).map_err(io_err)?;
+    }
+
+    for (index, line) in text.lines().enumerate() {
+        let tokens = if options.raw {
+            line.chars()
+                .map(|ch| KeyToken::Key(ch.to_string()))
+                .collect()
+        } else {
+            keyseq::parse(line).map_err(|e| format!("line {}: {e}", index + 1))?
+        };
+        if options.format == Format::Vectors {
+            let doc = golden::run(&tokens, &json)?;
+            writeln!(out, "{}", golden::format_line(&tokens, &doc)).map_err(io_err)?;
+            continue;
+        }
+        let engine = HangulEngine::new();
+        engine
+            .load_layout(json.clone())
+            .map_err(|e| e.to_string())?;
+        engine.set_mode(InputMode::Korean);
+
+        let converted = convert_line(&engine, &tokens, |token, output, doc| {
+            if options.format != Format::Json {
+                return Ok(());
+            }
+            let step = Step {
+                line: index + 1,
+                key: token.to_string(),
+                out: output.clone(),
+                text: &doc.text,
+            };
+            let json = json5::to_string(&step).map_err(io::Error::other)?;
+            writeln!(out, "{json}")
+        })
+        .map_err(io_err)?;
+        if options.format == Format::Text {
+            writeln!(out, "{converted}").map_err(io_err)?;
+        }
+    }
+    out.flush().map_err(io_err)
+}
+
+pub fn main(args: &[String]) -> ExitCode {
+    let Some(options) = parse_args(args) else {
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    };
+    match run(&options) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LAYOUT_2BUL: &str = include_str!("../../../layouts/2-standard.json5");
+
+    fn convert(keys: &str) -> String {
+        let engine = HangulEngine::new();
+        engine.load_layout(LAYOUT_2BUL.to_string()).unwrap();
+        engine.set_mode(InputMode::Korean);
+        convert_line(&engine, &keyseq::parse(keys).unwrap(), |_, _, _| Ok(())).unwrap()
+    }
+
+    #[test]
+    fn test_convert_line() {
+        assert_eq!(convert("dkssud{bs}"), "안녀");
+        assert_eq!(convert("gksrmf{toggle} abc"), "한글 abc");
+        // 처리하지 않은 백스페이스는 앞 글자를 지운다
+        assert_eq!(convert("rk{flush}{bs}{bs}a"), "ㅁ");
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let args: Vec<String> = ["--layout", "3-390", "--json", "keys.txt"]
+            .map(String::from)
+            .to_vec();
+        let options = parse_args(&args).unwrap();
+        assert_eq!(options.layout_id, "3-390");
+        assert_eq!(options.format, Format::Json);
+        assert!(!options.raw);
+        assert_eq!(options.input.as_deref(), Some("keys.txt"));
+        // `--layouts`가 없으면 동봉 레이아웃
+        assert_eq!(options.layouts_dir, None);
+        assert!(layout_json(&options).unwrap().contains("id: \"3-390\""));
+
+        assert!(parse_args(&["--bogus".to_string()]).is_none());
+        assert!(parse_args(&["a".to_string(), "b".to_string()]).is_none());
+    }
+}
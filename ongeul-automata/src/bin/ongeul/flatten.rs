@@ -0,0 +1,101 @@
+//! 레이아웃 평탄화: `extends`로 상속한 레이아웃을 `extends` 없는 JSON5로 출력한다.
+//!
+//! 부모 레이아웃은 입력 파일과 같은 디렉터리, 그다음 `--layouts` 디렉터리에서 `<ID>.json5`로 찾고,
+//! 없으면 동봉 레이아웃에서 찾는다. 입력이 `-`이거나 없으면 표준 입력을 읽는다.
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use ongeul_automata::layout::LayoutResolver;
+
+pub const USAGE: &str = "usage: ongeul flatten [--layouts <dir>] [<file> | -]";
+
+struct Options {
+    layouts_dir: Option<String>,
+    input: Option<String>,
+}
+
+fn parse_args(args: &[String]) -> Option<Options> {
+    let mut options = Options {
+        layouts_dir: None,
+        input: None,
+    };
+    let mut rest = args;
+    while !rest.is_empty() {
+        rest = match rest {
+            [flag, dir, tail @ ..] if flag == "--layouts" => {
+                options.layouts_dir = Some(dir.clone());
+                tail
+            }
+            [input, tail @ ..] if options.input.is_none() && !input.starts_with("--") => {
+                options.input = Some(input.clone());
+                tail
+            }
+            _ => return None,
+        };
+    }
+    Some(options)
+}
+
+/// 부모 레이아웃 검색 디렉터리 (동봉 레이아웃은 검색기가 마지막에 찾는다)
+fn search_dirs(options: &Options) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Some(input) = options.input.as_deref().filter(|input| *input != "-") {
+        dirs.push(
+            Path::new(input)
+                .parent()
+                .filter(|dir| !dir.as_os_str().is_empty())
+                .unwrap_or(Path::new("."))
+                .to_path_buf(),
+        );
+    }
+    dirs.extend(options.layouts_dir.as_ref().map(PathBuf::from));
+    dirs
+}
+
+pub fn main(args: &[String]) -> ExitCode {
+    let Some(options) = parse_args(args) else {
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    };
+    let result = crate::convert::read_input(options.input.as_deref()).and_then(|json| {
+        LayoutResolver::new(search_dirs(&options))
+            .flatten(&json)
+            .map_err(|e| e.to_string())
+    });
+    match result {
+        Ok(flat) => {
+            print!("{flat}");
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_dirs() {
+        let args: Vec<String> = ["user/mine.json5", "--layouts", "bundled"]
+            .map(String::from)
+            .to_vec();
+        let options = parse_args(&args).unwrap();
+        assert_eq!(
+            search_dirs(&options),
+            [PathBuf::from("user"), PathBuf::from("bundled")]
+        );
+
+        let options = parse_args(&["mine.json5".to_string()]).unwrap();
+        assert_eq!(search_dirs(&options), [PathBuf::from(".")]);
+
+        // 동봉 레이아웃은 검색기가 찾는다
+        let options = parse_args(&[]).unwrap();
+        assert!(search_dirs(&options).is_empty());
+        assert!(parse_args(&["--bogus".to_string()]).is_none());
+    }
+}
@@ -0,0 +1,138 @@
+//! libhangul 자판 XML 변환
+//!
+//! `import`는 `hangul-keyboard-*.xml`을 JSON5 레이아웃으로, `export`는 JSON5 레이아웃을 XML로 출력한다.
+//! `<include>` 파일은 입력 파일과 같은 디렉터리, 그다음 `--include` 디렉터리에서 찾고,
+//! 내보낼 레이아웃의 부모는 입력 파일과 같은 디렉터리, 그다음 `--layouts` 디렉터리, 그다음 동봉 레이아웃에서 찾는다.
+//! 입력이 `-`이거나 없으면 표준 입력을 읽는다.
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use ongeul_automata::layout::{LayoutResolver, libhangul};
+
+pub const USAGE: &str = "usage: ongeul libhangul import [--include <dir>]... [<file.xml> | -]
+       ongeul libhangul export [--layouts <dir>] [<file> | -]";
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Import,
+    Export,
+}
+
+struct Options {
+    command: Command,
+    /// `--include` 또는 `--layouts` 디렉터리
+    dirs: Vec<PathBuf>,
+    input: Option<String>,
+}
+
+fn parse_args(args: &[String]) -> Option<Options> {
+    let (command, mut rest) = match args {
+        [command, rest @ ..] if command == "import" => (Command::Import, rest),
+        [command, rest @ ..] if command == "export" => (Command::Export, rest),
+        _ => return None,
+    };
+    let mut options = Options {
+        command,
+        dirs: Vec::new(),
+        input: None,
+    };
+    while !rest.is_empty() {
+        rest = match rest {
+            [flag, dir, tail @ ..] if flag == "--include" && options.command == Command::Import => {
+                options.dirs.push(PathBuf::from(dir));
+                tail
+            }
+            [flag, dir, tail @ ..] if flag == "--layouts" && options.command == Command::Export => {
+                options.dirs.push(PathBuf::from(dir));
+                tail
+            }
+            [input, tail @ ..] if options.input.is_none() && !input.starts_with("--") => {
+                options.input = Some(input.clone());
+                tail
+            }
+            _ => return None,
+        };
+    }
+    Some(options)
+}
+
+/// 검색 디렉터리: 입력 파일의 디렉터리, 그다음 옵션으로 준 디렉터리
+fn search_dirs(options: &Options) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Some(input) = options.input.as_deref().filter(|input| *input != "-") {
+        dirs.push(
+            Path::new(input)
+                .parent()
+                .filter(|dir| !dir.as_os_str().is_empty())
+                .unwrap_or(Path::new("."))
+                .to_path_buf(),
+        );
+    }
+    dirs.extend(options.dirs.iter().cloned());
+    dirs
+}
+
+pub fn main(args: &[String]) -> ExitCode {
+    let Some(options) = parse_args(args) else {
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    };
+    let dirs = search_dirs(&options);
+    let result = crate::convert::read_input(options.input.as_deref()).and_then(|input| {
+        match options.command {
+            Command::Import => libhangul::import_json5(&input, &dirs),
+            Command::Export => LayoutResolver::new(dirs)
+                .resolve(&input)
+                .and_then(|schema| libhangul::export(&schema)),
+        }
+        .map_err(|e| e.to_string())
+    });
+    match result {
+        Ok(output) => {
+            print!("{output}");
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_search_dirs() {
+        let options = parse_args(&args(&[
+            "import",
+            "kbd/hangul-keyboard-2.xml",
+            "--include",
+            "a",
+            "--include",
+            "b",
+        ]))
+        .unwrap();
+        assert_eq!(options.command, Command::Import);
+        assert_eq!(
+            search_dirs(&options),
+            [PathBuf::from("kbd"), PathBuf::from("a"), PathBuf::from("b")]
+        );
+
+        let options = parse_args(&args(&["export", "mine.json5"])).unwrap();
+        assert_eq!(search_dirs(&options), [PathBuf::from(".")]);
+        let options = parse_args(&args(&["export", "--layouts", "mine", "-"])).unwrap();
+        assert_eq!(search_dirs(&options), [PathBuf::from("mine")]);
+
+        // 명령에 맞지 않는 옵션
+        assert!(parse_args(&args(&["import", "--layouts", "x"])).is_none());
+        assert!(parse_args(&args(&["export", "--include", "x"])).is_none());
+        assert!(parse_args(&args(&["convert"])).is_none());
+    }
+}
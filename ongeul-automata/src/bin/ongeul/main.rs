@@ -0,0 +1,220 @@
+//! Ongeul 엔진 터미널 도구
+//!
+//! 키를 누를 때마다 `HangulEngine`에 보내고 확정·조합 텍스트와 오토마타 상태를 바로 보여준다.
+//! 맥 없이 자판 레이아웃 변경을 확인할 때 쓴다.
+//!
+//! 사용법: ongeul [--layouts <레이아웃 디렉터리>] [--layout <ID>]
+//!         (레이아웃은 동봉 레이아웃을 쓰고, `--layouts`를 주면 그 디렉터리의 레이아웃을 쓴다)
+//!         ongeul convert ... (키 입력열 일괄 변환, [`convert`] 참고)
+//!         ongeul validate ... (레이아웃 검사, [`validate`] 참고)
+//!         ongeul flatten ... (상속한 레이아웃 평탄화, [`flatten`] 참고)
+//!         ongeul libhangul ... (libhangul 자판 XML 변환, [`libhangul`] 참고)
+
+mod convert;
+mod flatten;
+mod libhangul;
+mod repl;
+mod terminal;
+mod validate;
+
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use ongeul_automata::layout::{self, KeyboardLayout, LayoutFile, registry};
+use repl::{Repl, Reply};
+use terminal::{Input, Keys, RawMode};
+
+const USAGE: &str = "usage: ongeul [--layouts <dir>] [--layout <id>]";
+
+/// 레이아웃 디렉터리를 읽는다. 디렉터리가 없으면 동봉 레이아웃을 쓴다.
+/// 파싱에 실패한 파일은 알리고 목록에 남긴다.
+fn read_layouts(dir: Option<&Path>) -> Result<Vec<LayoutFile>, String> {
+    let files = match dir {
+        Some(dir) => layout::read_dir(dir)?,
+        None => builtin_layouts(),
+    };
+    for file in &files {
+        if let Err(e) = &file.layout {
+            eprintln!("skip {}: {e}", file.path.display());
+        }
+    }
+    Ok(files)
+}
+
+/// 동봉 레이아웃 (경로는 `<ID>.json5`)
+fn builtin_layouts() -> Vec<LayoutFile> {
+    registry::builtin_layouts()
+        .into_iter()
+        .filter_map(|info| {
+            let json = registry::builtin_json(&info.id)?.to_string();
+            Some(LayoutFile {
+                path: PathBuf::from(format!("{}.json5", info.id)),
+                layout: KeyboardLayout::from_json(&json),
+                json,
+            })
+        })
+        .collect()
+}
+
+/// 상태 줄과 입력 줄 두 줄을 다시 그리는 화면
+struct Screen {
+    out: io::Stdout,
+    /// 두 줄을 이미 그렸는지 (다시 그릴 때 위로 올라가 지운다)
+    drawn: bool,
+}
+
+impl Screen {
+    fn clear(&mut self) -> io::Result<()> {
+        if self.drawn {
+            write!(self.out, "\r\x1b[1A\x1b[J")?;
+            self.drawn = false;
+        }
+        Ok(())
+    }
+
+    /// 두 줄 위에 남길 텍스트를 출력한다.
+    fn print(&mut self, text: &str) -> io::Result<()> {
+        self.clear()?;
+        for line in text.lines() {
+            writeln!(self.out, "{line}")?;
+        }
+        self.out.flush()
+    }
+
+    /// 상태 줄(흐리게)과 입력 줄(조합 중 텍스트는 밑줄)을 그린다.
+    fn draw(&mut self, repl: &Repl, command: Option<&str>) -> io::Result<()> {
+        self.clear()?;
+        write!(self.out, "\x1b[2m{}\x1b[0m\r\n", repl.status())?;
+        match command {
+            Some(command) => write!(self.out, ": {command}")?,
+            None => write!(
+                self.out,
+                "> {}\x1b[4m{}\x1b[0m",
+                repl.line(),
+                repl.composing().unwrap_or_default()
+            )?,
+        }
+        self.drawn = true;
+        self.out.flush()
+    }
+}
+
+fn run(repl: &mut Repl) -> io::Result<()> {
+    let _raw = RawMode::enable()?;
+    let mut screen = Screen {
+        out: io::stdout(),
+        drawn: false,
+    };
+    let mut keys = Keys::new();
+    screen.print(repl::HELP)?;
+    screen.draw(repl, None)?;
+
+    while let Some(input) = keys.next_input() {
+        match input {
+            Input::Quit => break,
+            Input::Key(key) => {
+                if let Some(line) = repl.key(key) {
+                    screen.print(&format!("> {line}"))?;
+                }
+            }
+            Input::Command => {
+                let mut echo_error = Ok(());
+                let command = keys.read_command(|text| {
+                    if let Err(e) = screen.draw(repl, Some(text)) {
+                        echo_error = Err(e);
+                    }
+                });
+                echo_error?;
+                let reply = command.map(|c| repl.command(&c));
+                match reply {
+                    Some(Ok(Reply::Quit)) => break,
+                    Some(Ok(Reply::Message(message))) => screen.print(&message)?,
+                    Some(Err(e)) => screen.print(&format!("error: {e}"))?,
+                    None => {}
+                }
+            }
+        }
+        screen.draw(repl, None)?;
+    }
+    screen.clear()?;
+    // 남은 조합과 줄을 확정해 보여준다
+    if (!repl.line().is_empty() || repl.composing().is_some())
+        && let Some(line) = repl.key(repl::Key::Enter)
+    {
+        screen.print(&format!("> {line}"))?;
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.as_slice() {
+        [command, rest @ ..] if command == "convert" => convert::main(rest),
+        [command, rest @ ..] if command == "validate" => validate::main(rest),
+        [command, rest @ ..] if command == "flatten" => flatten::main(rest),
+        [command, rest @ ..] if command == "libhangul" => libhangul::main(rest),
+        _ => repl_main(&args),
+    }
+}
+
+/// 대화형 REPL
+fn repl_main(args: &[String]) -> ExitCode {
+    let mut layouts_dir = None;
+    let mut layout_id = None;
+    let mut rest = args;
+    while !rest.is_empty() {
+        rest = match rest {
+            [flag, dir, tail @ ..] if flag == "--layouts" => {
+                layouts_dir = Some(Path::new(dir));
+                tail
+            }
+            [flag, id, tail @ ..] if flag == "--layout" => {
+                layout_id = Some(id.clone());
+                tail
+            }
+            _ => {
+                eprintln!(
+                    "{USAGE}\n{}\n{}\n{}\n{}",
+                    convert::USAGE,
+                    validate::USAGE,
+                    flatten::USAGE,
+                    libhangul::USAGE
+                );
+                return ExitCode::from(2);
+            }
+        };
+    }
+
+    if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+        eprintln!("ongeul: the REPL needs a terminal");
+        return ExitCode::from(2);
+    }
+
+    let files = match read_layouts(layouts_dir) {
+        Ok(files) => files,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::from(2);
+        }
+    };
+    let Some(layout_id) = layout_id.or_else(|| repl::default_layout(&files)) else {
+        eprintln!("error: no Korean layout");
+        return ExitCode::from(2);
+    };
+    let mut repl = match Repl::new(files, &layout_id) {
+        Ok(repl) => repl,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::from(2);
+        }
+    };
+
+    match run(&mut repl) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::from(2)
+        }
+    }
+}
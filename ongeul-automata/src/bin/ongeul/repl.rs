@@ -0,0 +1,394 @@
+//! REPL 세션: 키를 엔진에 보내고 문서 줄과 조합 상태를 관리한다.
+//!
+//! 터미널 입출력과 분리되어 있어 키 처리와 명령을 그대로 테스트할 수 있다.
+
+use std::collections::BTreeMap;
+
+use ongeul_automata::automata::{AutomataSnapshot, BufferSnapshot, SyllableSnapshot};
+use ongeul_automata::keyseq::{Document, KeyToken};
+use ongeul_automata::layout::schema::LayoutType;
+use ongeul_automata::layout::{KeyboardLayout, LayoutFile};
+use ongeul_automata::{HangulEngine, InputMode};
+
+/// 터미널에서 읽은 입력 하나
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    /// 출력 가능한 ASCII 문자
+    Char(char),
+    Backspace,
+    /// 조합을 확정하고 줄을 끝낸다
+    Enter,
+    /// 조합을 확정한다
+    Escape,
+    /// 한/영 전환
+    ToggleMode,
+}
+
+/// 명령 실행 결과
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reply {
+    /// 상태 줄 아래에 보여줄 메시지
+    Message(String),
+    Quit,
+}
+
+/// 불러올 수 있는 레이아웃
+struct LayoutEntry {
+    name: String,
+    latin: bool,
+    json: String,
+}
+
+pub const HELP: &str = "\
+keys: Ctrl-T toggle mode, Enter/Esc flush, Ctrl-X command, Ctrl-D quit
+commands:
+  layouts              list layouts
+  layout <id>          switch Korean layout
+  english <id>|off     set or clear the English-mode layout
+  mode ko|en           set input mode
+  direct on|off        direct commit mode
+  chord <ms>           chord window (0 = off)
+  clear                flush and clear the line
+  quit";
+
+pub struct Repl {
+    engine: HangulEngine,
+    layouts: BTreeMap<String, LayoutEntry>,
+    layout_id: String,
+    english_id: Option<String>,
+    /// 현재 줄
+    doc: Document,
+}
+
+impl Repl {
+    /// 레이아웃 목록으로 세션을 만들고 `layout_id` 배열을 한글 모드로 불러온다.
+    pub fn new(files: Vec<LayoutFile>, layout_id: &str) -> Result<Self, String> {
+        let layouts = files
+            .into_iter()
+            .filter_map(|file| {
+                let layout = file.layout.ok()?;
+                let entry = LayoutEntry {
+                    name: layout.name,
+                    latin: layout.layout_type == LayoutType::Latin,
+                    json: file.json,
+                };
+                Some((layout.id, entry))
+            })
+            .collect();
+        let mut repl = Repl {
+            engine: HangulEngine::new(),
+            layouts,
+            layout_id: String::new(),
+            english_id: None,
+            doc: Document::default(),
+        };
+        repl.load_layout(layout_id)?;
+        repl.engine.set_mode(InputMode::Korean);
+        Ok(repl)
+    }
+
+    /// 현재 줄의 확정 텍스트
+    pub fn line(&self) -> &str {
+        &self.doc.text
+    }
+
+    /// 현재 조합 중인 텍스트
+    pub fn composing(&self) -> Option<&str> {
+        self.doc.composing.as_deref()
+    }
+
+    /// 키 하나를 처리한다. Enter로 끝난 줄을 반환한다.
+    pub fn key(&mut self, key: Key) -> Option<String> {
+        let token = match key {
+            Key::Char(ch) => KeyToken::Key(ch.to_string()),
+            Key::Backspace => KeyToken::Backspace,
+            Key::Escape => KeyToken::Flush,
+            Key::ToggleMode => KeyToken::ToggleMode,
+            Key::Enter => {
+                self.flush();
+                return Some(std::mem::take(&mut self.doc.text));
+            }
+        };
+        self.doc.input(&self.engine, &token);
+        None
+    }
+
+    /// 명령 한 줄을 실행한다.
+    pub fn command(&mut self, input: &str) -> Result<Reply, String> {
+        let words: Vec<&str> = input.split_whitespace().collect();
+        let message = match words.as_slice() {
+            [] => return Ok(Reply::Message(String::new())),
+            ["quit" | "q"] => return Ok(Reply::Quit),
+            ["help" | "?"] => HELP.to_string(),
+            ["layouts"] => self.list_layouts(),
+            ["layout", id] => {
+                self.flush();
+                self.load_layout(id)?;
+                format!("layout: {id}")
+            }
+            ["english", "off"] => {
+                self.flush();
+                self.engine.clear_english_layout();
+                self.english_id = None;
+                "english layout: off".to_string()
+            }
+            ["english", id] => {
+                let entry = self.layouts.get(*id).filter(|e| e.latin);
+                let json = entry
+                    .ok_or_else(|| format!("unknown English layout '{id}'"))?
+                    .json
+                    .clone();
+                self.flush();
+                self.engine
+                    .load_english_layout(json)
+                    .map_err(|e| e.to_string())?;
+                self.english_id = Some(id.to_string());
+                format!("english layout: {id}")
+            }
+            ["mode", mode] => {
+                let mode = match *mode {
+                    "ko" => InputMode::Korean,
+                    "en" => InputMode::English,
+                    _ => return Err(format!("unknown mode '{mode}' (ko|en)")),
+                };
+                self.flush();
+                self.engine.set_mode(mode);
+                format!("mode: {}", mode_label(mode))
+            }
+            ["direct", value @ ("on" | "off")] => {
+                let result = self.engine.set_direct_commit(*value == "on");
+                self.doc.apply(&result);
+                format!("direct commit: {value}")
+            }
+            ["chord", ms] => {
+                let ms: u64 = ms.parse().map_err(|_| format!("invalid window '{ms}'"))?;
+                self.engine.set_chord_window(ms);
+                format!("chord window: {ms} ms")
+            }
+            ["clear"] => {
+                self.flush();
+                self.doc.text.clear();
+                String::new()
+            }
+            _ => return Err(format!("unknown command '{input}' (try 'help')")),
+        };
+        Ok(Reply::Message(message))
+    }
+
+    /// 상태 줄: 입력 모드, 배열, 오토마타 상태
+    pub fn status(&self) -> String {
+        let mode = mode_label(self.engine.get_mode());
+        let english = match &self.english_id {
+            Some(id) => format!(" / {id}"),
+            None => String::new(),
+        };
+        let state = self
+            .engine
+            .automata_snapshot()
+            .map(|s| describe_snapshot(&s))
+            .unwrap_or_default();
+        format!("[{mode}] {}{english} | {state}", self.layout_id)
+    }
+
+    fn load_layout(&mut self, id: &str) -> Result<(), String> {
+        let entry = self.layouts.get(id).filter(|e| !e.latin);
+        let entry = entry.ok_or_else(|| format!("unknown Korean layout '{id}'"))?;
+        self.engine
+            .load_layout(entry.json.clone())
+            .map_err(|e| e.to_string())?;
+        self.layout_id = id.to_string();
+        self.doc.composing = None;
+        Ok(())
+    }
+
+    fn list_layouts(&self) -> String {
+        self.layouts
+            .iter()
+            .map(|(id, entry)| {
+                let current = *id == self.layout_id || self.english_id.as_ref() == Some(id);
+                let mark = if current { '*' } else { ' ' };
+                let kind = if entry.latin { "en" } else { "ko" };
+                format!("{mark} {id:<12} {kind}  {}", entry.name)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn flush(&mut self) {
+        self.doc.input(&self.engine, &KeyToken::Flush);
+    }
+}
+
+fn mode_label(mode: InputMode) -> &'static str {
+    match mode {
+        InputMode::Korean => "KO",
+        InputMode::English => "EN",
+    }
+}
+
+fn index(value: Option<u32>) -> String {
+    value.map_or_else(|| "-".to_string(), |v| v.to_string())
+}
+
+fn jamo(value: Option<char>) -> String {
+    value.map_or_else(|| "-".to_string(), |ch| format!("U+{:04X}", ch as u32))
+}
+
+/// 조합 버퍼 인덱스(L/V/T)와 백스페이스 복원 정보
+fn describe_buffer(b: &BufferSnapshot) -> String {
+    let buffer = &b.buffer;
+    let mut text = format!(
+        "{:?} L={} V={} T={}",
+        buffer.state,
+        index(buffer.choseong),
+        index(buffer.jungseong),
+        index(buffer.jongseong)
+    );
+    if b.prev_choseong.is_some() || b.prev_jungseong.is_some() || b.prev_jongseong.is_some() {
+        text.push_str(&format!(
+            " prev L={} V={} T={}",
+            index(b.prev_choseong),
+            index(b.prev_jungseong),
+            index(b.prev_jongseong)
+        ));
+    }
+    if let Some(t) = b.pending_jongseong {
+        text.push_str(&format!(" pending T={t}"));
+    }
+    text
+}
+
+fn describe_syllable(s: &SyllableSnapshot) -> String {
+    format!(
+        "L={} V={} T={}",
+        jamo(s.choseong),
+        jamo(s.jungseong),
+        jamo(s.jongseong)
+    )
+}
+
+/// 오토마타 종류별 상태 표시
+fn describe_snapshot(snapshot: &AutomataSnapshot) -> String {
+    match snapshot {
+        AutomataSnapshot::Jamo(b) | AutomataSnapshot::Jaso(b) => describe_buffer(b),
+        AutomataSnapshot::Shin {
+            inner,
+            vowel_combinable,
+        } => {
+            let combinable = if *vowel_combinable { " +vowel" } else { "" };
+            format!("{}{combinable}", describe_buffer(inner))
+        }
+        AutomataSnapshot::OldHangul { syllable, history } => {
+            format!("{} history={}", describe_syllable(syllable), history.len())
+        }
+        AutomataSnapshot::Romaja { keys } => format!("keys={keys:?}"),
+    }
+}
+
+/// 레이아웃 목록에서 기본 한글 배열 ID를 고른다. (두벌식 표준이 있으면 그것)
+pub fn default_layout(files: &[LayoutFile]) -> Option<String> {
+    let korean: Vec<&KeyboardLayout> = files
+        .iter()
+        .filter_map(|f| f.layout.as_ref().ok())
+        .filter(|l| l.layout_type != LayoutType::Latin)
+        .collect();
+    korean
+        .iter()
+        .find(|l| l.id == "2-standard")
+        .or(korean.first())
+        .map(|l| l.id.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+
+    fn create_repl(layout_id: &str) -> Repl {
+        let dir = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/layouts"));
+        let files = ongeul_automata::layout::read_dir(dir).unwrap();
+        Repl::new(files, layout_id).unwrap()
+    }
+
+    fn type_keys(repl: &mut Repl, keys: &str) {
+        for ch in keys.chars() {
+            repl.key(Key::Char(ch));
+        }
+    }
+
+    #[test]
+    fn test_compose_and_enter() {
+        let mut repl = create_repl("2-standard");
+        type_keys(&mut repl, "dkssud");
+        assert_eq!(repl.line(), "안");
+        assert_eq!(repl.composing(), Some("녕"));
+        assert!(repl.status().starts_with("[KO] 2-standard | Jongseong"));
+
+        // 처리하지 않은 키(공백)는 그대로 줄에 들어간다
+        repl.key(Key::Char(' '));
+        assert_eq!(repl.line(), "안녕 ");
+        assert_eq!(repl.key(Key::Enter).as_deref(), Some("안녕 "));
+        assert_eq!(repl.line(), "");
+    }
+
+    #[test]
+    fn test_backspace_and_toggle() {
+        let mut repl = create_repl("2-standard");
+        type_keys(&mut repl, "rk");
+        repl.key(Key::Backspace);
+        assert_eq!(repl.composing(), Some("ㄱ"));
+        repl.key(Key::Backspace);
+        repl.key(Key::Backspace);
+        assert_eq!((repl.line(), repl.composing()), ("", None));
+
+        type_keys(&mut repl, "r");
+        repl.key(Key::ToggleMode);
+        type_keys(&mut repl, "ab");
+        assert_eq!(repl.line(), "ㄱab");
+        repl.key(Key::Backspace);
+        assert_eq!(repl.line(), "ㄱa");
+        assert!(repl.status().starts_with("[EN]"));
+    }
+
+    #[test]
+    fn test_switch_layout_command() {
+        let mut repl = create_repl("2-standard");
+        type_keys(&mut repl, "r");
+        assert_eq!(
+            repl.command("layout 3-390"),
+            Ok(Reply::Message("layout: 3-390".into()))
+        );
+        // 바꾸기 전 조합은 확정된다
+        assert_eq!(repl.line(), "ㄱ");
+        type_keys(&mut repl, "kf");
+        assert_eq!(repl.composing(), Some("가"));
+
+        assert!(repl.command("layout en-dvorak").is_err());
+        assert!(repl.command("layout nope").is_err());
+        assert_eq!(repl.command("quit"), Ok(Reply::Quit));
+    }
+
+    #[test]
+    fn test_english_layout_command() {
+        let mut repl = create_repl("2-standard");
+        assert!(repl.command("english 2-standard").is_err());
+        repl.command("english en-dvorak").unwrap();
+        repl.command("mode en").unwrap();
+        // 드보락: QWERTY d 자리 → e
+        type_keys(&mut repl, "d");
+        assert_eq!(repl.line(), "e");
+        assert!(repl.status().contains("2-standard / en-dvorak"));
+        let Ok(Reply::Message(list)) = repl.command("layouts") else {
+            panic!("layouts failed");
+        };
+        assert!(list.contains("* en-dvorak"));
+    }
+
+    #[test]
+    fn test_describe_snapshot() {
+        let mut repl = create_repl("romaja");
+        type_keys(&mut repl, "n");
+        assert!(repl.status().ends_with("keys=\"n\""), "{}", repl.status());
+    }
+}
@@ -0,0 +1,131 @@
+//! 터미널 raw 모드와 키 입력 해석
+//!
+//! 외부 크레이트 없이 `stty`로 줄 단위 입력과 에코를 끈다. (Linux, macOS)
+
+use std::io::{self, Bytes, Read, StdinLock};
+use std::process::{Command, Stdio};
+
+use crate::repl::Key;
+
+/// raw 모드 구간. drop될 때 원래 터미널 설정으로 되돌린다.
+pub struct RawMode {
+    saved: String,
+}
+
+fn stty(args: &[&str]) -> io::Result<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!("stty {} failed", args.join(" "))));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+impl RawMode {
+    /// 줄 단위 입력, 에코, 시그널 키(Ctrl-C 등)를 끈다. 출력 처리는 그대로 둔다.
+    pub fn enable() -> io::Result<Self> {
+        let saved = stty(&["-g"])?;
+        stty(&[
+            "-icanon", "-echo", "-isig", "-ixon", "-iexten", "min", "1", "time", "0",
+        ])?;
+        Ok(RawMode { saved })
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        let _ = stty(&[&self.saved]);
+    }
+}
+
+/// 터미널에서 읽은 입력
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Input {
+    Key(Key),
+    /// 명령 입력 시작 (Ctrl-X)
+    Command,
+    /// 종료 (Ctrl-C, Ctrl-D)
+    Quit,
+}
+
+/// 표준 입력에서 키를 하나씩 읽는다.
+pub struct Keys {
+    bytes: Bytes<StdinLock<'static>>,
+    /// Esc 뒤에 읽었지만 이스케이프 시퀀스가 아닌 바이트
+    pending: Option<u8>,
+}
+
+impl Keys {
+    pub fn new() -> Self {
+        Keys {
+            bytes: io::stdin().lock().bytes(),
+            pending: None,
+        }
+    }
+
+    fn next_byte(&mut self) -> Option<u8> {
+        self.pending
+            .take()
+            .or_else(|| self.bytes.next().and_then(Result::ok))
+    }
+
+    /// 다음 입력. 입력이 끝나면 None.
+    /// 화살표 같은 이스케이프 시퀀스와 ASCII 밖의 문자는 건너뛴다.
+    pub fn next_input(&mut self) -> Option<Input> {
+        loop {
+            let input = match self.next_byte()? {
+                0x03 | 0x04 => Input::Quit,
+                0x14 => Input::Key(Key::ToggleMode),
+                0x18 => Input::Command,
+                0x08 | 0x7f => Input::Key(Key::Backspace),
+                b'\r' | b'\n' => Input::Key(Key::Enter),
+                0x1b => {
+                    self.skip_escape_sequence();
+                    Input::Key(Key::Escape)
+                }
+                b @ 0x20..=0x7e => Input::Key(Key::Char(b as char)),
+                _ => continue,
+            };
+            return Some(input);
+        }
+    }
+
+    /// Esc 뒤의 CSI/SS3 시퀀스(`ESC [ ... 최종 바이트`, `ESC O x`)를 버린다.
+    fn skip_escape_sequence(&mut self) {
+        match self.next_byte() {
+            Some(b'[') => {
+                while let Some(b) = self.next_byte() {
+                    if (0x40..=0x7e).contains(&b) {
+                        break;
+                    }
+                }
+            }
+            Some(b'O') => {
+                self.next_byte();
+            }
+            other => self.pending = other,
+        }
+    }
+
+    /// 명령 한 줄을 읽는다. Enter로 끝나고, Esc나 Ctrl-C로 취소하면 None.
+    /// 입력하는 동안 `echo`로 지금까지의 내용을 보여준다.
+    pub fn read_command(&mut self, mut echo: impl FnMut(&str)) -> Option<String> {
+        let mut command = String::new();
+        echo(&command);
+        loop {
+            match self.next_byte()? {
+                b'\r' | b'\n' => return Some(command),
+                0x03 | 0x04 | 0x1b => return None,
+                0x08 | 0x7f => {
+                    command.pop();
+                }
+                b @ 0x20..=0x7e => command.push(b as char),
+                _ => continue,
+            }
+            echo(&command);
+        }
+    }
+}
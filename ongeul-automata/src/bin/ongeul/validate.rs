@@ -0,0 +1,168 @@
+//! 레이아웃 검사: 자판 파일의 진단([`validate`](ongeul_automata::layout::validate))을 출력한다.
+//!
+//! 인자는 레이아웃 파일이나 디렉터리(안의 `*.json5`)이고, 없으면 동봉 레이아웃을 검사한다.
+//! `extends`로 상속한 레이아웃은 상속을 푼 결과를 검사한다.
+//! 진단은 `<파일>: <심각도>: <JSON 경로>: <메시지>` 형식으로, `--json`이면 JSON Lines로 출력한다.
+//! 에러가 하나라도 있으면 종료 코드 1.
+
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use ongeul_automata::layout::validate::{self, DiagnosticSeverity, LayoutDiagnostic};
+use ongeul_automata::layout::{self, LayoutResolver, registry};
+use serde::Serialize;
+
+pub const USAGE: &str = "usage: ongeul validate [--json] [<file | dir>...]";
+
+/// `--json` 출력 한 줄
+#[derive(Serialize)]
+struct Record<'a> {
+    file: String,
+    #[serde(flatten)]
+    diagnostic: &'a LayoutDiagnostic,
+}
+
+struct Options {
+    json: bool,
+    paths: Vec<PathBuf>,
+}
+
+fn parse_args(args: &[String]) -> Option<Options> {
+    let mut options = Options {
+        json: false,
+        paths: Vec::new(),
+    };
+    for arg in args {
+        match arg.as_str() {
+            "--json" => options.json = true,
+            flag if flag.starts_with("--") => return None,
+            path => options.paths.push(PathBuf::from(path)),
+        }
+    }
+    Some(options)
+}
+
+/// 레이아웃 파일 하나를 검사한다. `extends`는 파일과 같은 디렉터리, 그다음 동봉 레이아웃에서 찾는다.
+fn diagnose_file(path: &Path) -> Result<Vec<LayoutDiagnostic>, String> {
+    let json = std::fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    let parent = path.parent().unwrap_or(Path::new(".")).to_path_buf();
+    let resolver = LayoutResolver::new(vec![parent]);
+    Ok(validate::validate_with(&resolver, &json))
+}
+
+/// 동봉 레이아웃의 진단 (경로는 `<ID>.json5`)
+fn diagnose_builtin() -> Vec<(PathBuf, Vec<LayoutDiagnostic>)> {
+    registry::builtin_layouts()
+        .into_iter()
+        .filter_map(|info| {
+            let json = registry::builtin_json(&info.id)?;
+            let diagnostics = validate::validate_with(&LayoutResolver::default(), json);
+            Some((PathBuf::from(format!("{}.json5", info.id)), diagnostics))
+        })
+        .collect()
+}
+
+/// 파일마다 진단을 만든다. 디렉터리는 안의 레이아웃 파일을 모두 검사한다.
+/// 경로가 없으면 동봉 레이아웃을 검사한다.
+fn diagnose(paths: &[PathBuf]) -> Result<Vec<(PathBuf, Vec<LayoutDiagnostic>)>, String> {
+    if paths.is_empty() {
+        return Ok(diagnose_builtin());
+    }
+    let mut results = Vec::new();
+    for path in paths {
+        let files = if path.is_dir() {
+            layout::read_dir(path)?
+                .into_iter()
+                .map(|file| file.path)
+                .collect()
+        } else {
+            vec![path.clone()]
+        };
+        for file in files {
+            let diagnostics = diagnose_file(&file)?;
+            results.push((file, diagnostics));
+        }
+    }
+    Ok(results)
+}
+
+/// 진단을 출력하고 에러가 있었는지 반환한다.
+fn run(options: &Options) -> Result<bool, String> {
+    let files = diagnose(&options.paths)?;
+    let mut out = io::BufWriter::new(io::stdout().lock());
+    let io_err = |e: io::Error| format!("stdout: {e}");
+    let mut failed = false;
+    for (path, diagnostics) in files {
+        let file = path.display().to_string();
+        for diagnostic in diagnostics {
+            failed |= diagnostic.severity == DiagnosticSeverity::Error;
+            if options.json {
+                let record = Record {
+                    file: file.clone(),
+                    diagnostic: &diagnostic,
+                };
+                let line = json5::to_string(&record).map_err(|e| e.to_string())?;
+                writeln!(out, "{line}").map_err(io_err)?;
+            } else {
+                writeln!(out, "{file}: {diagnostic}").map_err(io_err)?;
+            }
+        }
+    }
+    out.flush().map_err(io_err)?;
+    Ok(failed)
+}
+
+pub fn main(args: &[String]) -> ExitCode {
+    let Some(options) = parse_args(args) else {
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    };
+    match run(&options) {
+        Ok(false) => ExitCode::SUCCESS,
+        Ok(true) => ExitCode::from(1),
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_args() {
+        let options = parse_args(&[]).unwrap();
+        assert!(!options.json);
+        // 경로가 없으면 동봉 레이아웃을 검사한다
+        assert!(options.paths.is_empty());
+
+        let args: Vec<String> = ["a.json5", "--json", "dir"].map(String::from).to_vec();
+        let options = parse_args(&args).unwrap();
+        assert!(options.json);
+        assert_eq!(options.paths, [Path::new("a.json5"), Path::new("dir")]);
+
+        assert!(parse_args(&["--bogus".to_string()]).is_none());
+    }
+
+    #[test]
+    fn test_builtin_layouts_have_no_errors() {
+        let files = diagnose(&[]).unwrap();
+        assert!(
+            files
+                .iter()
+                .any(|(path, _)| path == Path::new("2-standard.json5"))
+        );
+        for (path, diagnostics) in files {
+            assert!(
+                diagnostics
+                    .iter()
+                    .all(|d| d.severity != DiagnosticSeverity::Error),
+                "{}: {diagnostics:?}",
+                path.display()
+            );
+        }
+    }
+}
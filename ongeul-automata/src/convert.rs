@@ -0,0 +1,245 @@
+//! 한/영 전환 착오 교정: 잘못된 입력 모드로 친 문자열을 다른 모드의 문자열로 바꾼다.
+//!
+//! - [`to_hangul`]: 영문으로 입력된 키 레이블을 배열에 다시 입력해 한글로 만든다. ("dkssud" → "안녕")
+//! - [`to_latin`]: 한글을 역 키맵으로 풀어 키 레이블로 되돌린다. ("안녕" → "dkssud")
+//!
+//! 영문(latin) 배열에서는 키 레이블과 그 배열의 글자 사이를 변환한다.
+
+use crate::automata::{self, AutomataResult};
+use crate::layout::KeyboardLayout;
+use crate::layout::schema::LayoutType;
+use crate::unicode;
+
+/// 음절 하나를 되돌릴 때 자모마다 시험할 키 후보 수 상한
+const MAX_CANDIDATES: usize = 4;
+
+/// 로마자에서 입력하지 않아도 되는 초성 ㅇ의 인덱스
+const ROMAJA_SILENT_L: u32 = 11;
+
+/// 키 레이블 문자열을 배열로 입력한 결과를 반환한다.
+/// 배열에 없는 글자(공백, 기호 등)는 조합을 끊고 그대로 둔다.
+pub fn to_hangul(layout: &KeyboardLayout, text: &str) -> String {
+    let Some(mut automata) = automata::create_automata(layout) else {
+        return text
+            .chars()
+            .map(|ch| layout.map_latin_key(&ch.to_string()).unwrap_or(ch))
+            .collect();
+    };
+
+    let mut out = String::new();
+    for ch in text.chars() {
+        let result = automata::process_key_label(automata.as_mut(), &ch.to_string(), layout);
+        apply(&mut out, &result);
+        if !result.handled {
+            out.push(ch);
+        }
+    }
+    apply(&mut out, &automata.flush());
+    out
+}
+
+/// 배열로 입력했을 때 `text`가 나오는 키 레이블 문자열을 반환한다.
+/// 키로 입력할 수 없는 글자는 그대로 둔다.
+pub fn to_latin(layout: &KeyboardLayout, text: &str) -> String {
+    if layout.layout_type == LayoutType::Latin {
+        return text.chars().map(|ch| latin_key_for(layout, ch)).collect();
+    }
+
+    let mut out = String::new();
+    for ch in text.chars() {
+        match keys_for_char(layout, ch) {
+            Some(keys) => out.push_str(&keys),
+            None => out.push(ch),
+        }
+    }
+    out
+}
+
+fn apply(out: &mut String, result: &AutomataResult) {
+    for _ in 0..result.delete_before {
+        out.pop();
+    }
+    if let Some(c) = &result.committed {
+        out.push_str(c);
+    }
+}
+
+/// 영문 배열의 역 키맵. Shift 매핑이 없는 대문자는 소문자 키를 대문자로 바꾼다.
+fn latin_key_for(layout: &KeyboardLayout, ch: char) -> String {
+    if let Some(key) = layout.keys_for(ch).first() {
+        return key.to_string();
+    }
+    if ch.is_ascii_uppercase()
+        && let Some(key) = layout.keys_for(ch.to_ascii_lowercase()).first()
+    {
+        return key.to_ascii_uppercase();
+    }
+    ch.to_string()
+}
+
+/// 글자 하나를 입력하는 키 시퀀스.
+/// 자모마다 키 후보를 조합해 보고, 다시 입력했을 때 같은 글자가 나오는 첫 시퀀스를 고른다.
+/// (신세벌식처럼 같은 모음을 여러 키로 넣을 수 있으면 뒤따르는 자모에 따라 맞는 키가 다르다)
+fn keys_for_char(layout: &KeyboardLayout, ch: char) -> Option<String> {
+    let mut candidates: Vec<Vec<&str>> = Vec::new();
+    for jamo in layout_jamo(layout, ch) {
+        let mut keys = layout.keys_for(jamo);
+        keys.truncate(MAX_CANDIDATES);
+        if keys.is_empty() {
+            return None;
+        }
+        candidates.push(keys);
+    }
+    if candidates.is_empty() {
+        return None;
+    }
+    // 로마자: 모음 앞의 초성 ㅇ은 입력하지 않는다 ("an" → "안")
+    if layout.layout_type == LayoutType::Romaja
+        && unicode::decompose_syllable(ch).is_some_and(|(l, _, _)| l == ROMAJA_SILENT_L)
+    {
+        candidates[0].insert(0, "");
+    }
+
+    let expected = ch.to_string();
+    let mut indices = vec![0; candidates.len()];
+    loop {
+        let keys: String = indices
+            .iter()
+            .zip(&candidates)
+            .map(|(&i, keys)| keys[i])
+            .collect();
+        if to_hangul(layout, &keys) == expected {
+            return Some(keys);
+        }
+        // 다음 후보 조합 (마지막 자모부터 올림)
+        let mut pos = candidates.len();
+        loop {
+            if pos == 0 {
+                // 맞는 조합이 없으면 각 자모의 첫 후보
+                return Some(candidates.iter().map(|keys| keys[0]).collect());
+            }
+            pos -= 1;
+            indices[pos] += 1;
+            if indices[pos] < candidates[pos].len() {
+                break;
+            }
+            indices[pos] = 0;
+        }
+    }
+}
+
+/// 글자를 배열이 쓰는 자모로 나눈다.
+/// 호환 자모 배열(두벌식, 로마자)과 위치 자모 배열(세벌식 계열)에 맞춰 변환하고,
+/// 키가 없는 겹자모는 조합 규칙을 거꾸로 적용해 쪼갠다.
+fn layout_jamo(layout: &KeyboardLayout, ch: char) -> Vec<char> {
+    let compat = matches!(layout.layout_type, LayoutType::Jamo | LayoutType::Romaja);
+    let parts: Vec<char> = if let Some((l, v, t)) = unicode::decompose_syllable(ch) {
+        let jamo = if compat {
+            [
+                unicode::choseong_to_compat(l),
+                unicode::jungseong_to_compat(v),
+                unicode::jongseong_to_compat(t),
+            ]
+        } else {
+            [
+                char::from_u32(unicode::L_BASE + l),
+                char::from_u32(unicode::V_BASE + v),
+                (t != 0)
+                    .then(|| char::from_u32(unicode::T_BASE + t))
+                    .flatten(),
+            ]
+        };
+        jamo.into_iter().flatten().collect()
+    } else if !compat && unicode::is_compat_consonant(ch) {
+        let l = unicode::compat_to_choseong(ch).and_then(|l| char::from_u32(unicode::L_BASE + l));
+        vec![l.unwrap_or(ch)]
+    } else if !compat && unicode::is_compat_vowel(ch) {
+        let v = unicode::compat_to_jungseong(ch).and_then(|v| char::from_u32(unicode::V_BASE + v));
+        vec![v.unwrap_or(ch)]
+    } else {
+        vec![ch]
+    };
+
+    let mut out = Vec::new();
+    for jamo in parts {
+        split_into(layout, jamo, &mut out);
+    }
+    out
+}
+
+fn split_into(layout: &KeyboardLayout, jamo: char, out: &mut Vec<char>) {
+    if layout.keys_for(jamo).is_empty()
+        && let Some((first, second)) = layout.split(jamo)
+    {
+        split_into(layout, first, out);
+        split_into(layout, second, out);
+    } else {
+        out.push(jamo);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LAYOUT_2BUL: &str = include_str!("../layouts/2-standard.json5");
+    const LAYOUT_390: &str = include_str!("../layouts/3-390.json5");
+    const LAYOUT_SHIN: &str = include_str!("../layouts/3-shin.json5");
+    const LAYOUT_ROMAJA: &str = include_str!("../layouts/romaja.json5");
+    const LAYOUT_COLEMAK: &str = include_str!("../layouts/en-colemak.json5");
+
+    fn layout(json: &str) -> KeyboardLayout {
+        KeyboardLayout::from_json(json).unwrap()
+    }
+
+    #[test]
+    fn test_2bul_round_trip() {
+        let layout = layout(LAYOUT_2BUL);
+        assert_eq!(to_hangul(&layout, "dkssudgktpdy"), "안녕하세요");
+        assert_eq!(to_latin(&layout, "안녕하세요"), "dkssudgktpdy");
+        // 겹받침/겹모음은 조합 규칙으로 쪼개 입력
+        assert_eq!(to_latin(&layout, "값과 닭"), "rkqtrhk ekfr");
+        assert_eq!(to_hangul(&layout, "rkqtrhk ekfr"), "값과 닭");
+    }
+
+    #[test]
+    fn test_passthrough() {
+        let layout = layout(LAYOUT_2BUL);
+        assert_eq!(to_hangul(&layout, "gks, 1!"), "한, 1!");
+        assert_eq!(to_latin(&layout, "한, 1!"), "gks, 1!");
+    }
+
+    #[test]
+    fn test_390_round_trip() {
+        let layout = layout(LAYOUT_390);
+        let text = "한글 입력기";
+        let keys = to_latin(&layout, text);
+        assert_eq!(keys, "mfskgw jd3yexkd");
+        assert_eq!(to_hangul(&layout, &keys), text);
+    }
+
+    #[test]
+    fn test_shin_picks_key_by_context() {
+        // 과: 일반 ㅗ 글쇠 뒤에서만 겹모음, 곧: 갈마들이 ㅗ 뒤의 f는 ㄷ종
+        let layout = layout(LAYOUT_SHIN);
+        for text in ["과", "곧", "한글"] {
+            let keys = to_latin(&layout, text);
+            assert_eq!(to_hangul(&layout, &keys), text, "keys: {keys}");
+        }
+    }
+
+    #[test]
+    fn test_romaja_round_trip() {
+        let layout = layout(LAYOUT_ROMAJA);
+        assert_eq!(to_latin(&layout, "한국"), "hangug");
+        assert_eq!(to_hangul(&layout, "hangug"), "한국");
+    }
+
+    #[test]
+    fn test_latin_layout() {
+        // QWERTY 레이블로 친 "hello" ↔ Colemak 배열 글자
+        let layout = layout(LAYOUT_COLEMAK);
+        let colemak = to_hangul(&layout, "Hello");
+        assert_eq!(to_latin(&layout, &colemak), "Hello");
+    }
+}
@@ -1,20 +1,96 @@
 /// 엔진 내부 상태: 입력 모드, 레이아웃, 오토마타를 관리한다.
-use crate::automata::{self, Automata, AutomataResult};
-use crate::layout::KeyboardLayout;
+use crate::automata::{self, Automata, AutomataResult, AutomataSnapshot, AutomataState};
+use crate::convert;
+use crate::english::{self, EnglishDetector};
+use crate::event::{self, KeyAction, KeyEvent, KeyRoute};
+use crate::hanja::{HanjaDictionary, HanjaEntry};
+use crate::layout::schema::LayoutType;
+use crate::layout::{KeyboardLayout, LayoutError, physical};
 use crate::unicode;
 
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
 /// 입력 모드
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum InputMode {
     English,
     Korean,
 }
 
+/// 모아치기 기본 허용 시간 (첫 키를 누른 뒤 같은 묶음으로 받는 시간, 밀리초).
+/// 기본은 꺼짐(0)이며, 앱이나 사용자가 [`EngineState::set_chord_window`]로 켠다.
+pub const DEFAULT_CHORD_WINDOW_MS: u64 = 0;
+
+/// 조합 상태 스냅샷 형식 버전
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// 조합 상태 스냅샷 (JSON으로 저장)
+#[derive(Debug, Serialize, Deserialize)]
+struct EngineSnapshot {
+    version: u32,
+    mode: InputMode,
+    /// 스냅샷을 만든 한글 배열 ID (복원할 때 같은 배열이어야 한다)
+    layout_id: Option<String>,
+    #[serde(default)]
+    direct_commit: bool,
+    automata: Option<AutomataSnapshot>,
+}
+
+/// 모아치기 묶음에서 눌린 키
+#[derive(Debug, Clone)]
+struct ChordKey {
+    key: String,
+    released: bool,
+}
+
 /// 엔진 내부 가변 상태
 pub struct EngineState {
     pub mode: InputMode,
-    layout: Option<KeyboardLayout>,
+    /// 한글 배열 (세션끼리 공유)
+    layout: Option<Arc<KeyboardLayout>>,
+    /// 영문 모드에 적용할 배열 (None이면 키 레이블 그대로 입력)
+    english_layout: Option<Arc<KeyboardLayout>>,
     automata: Option<Box<dyn Automata + Send>>,
+    /// 한자 사전 (미로드 시 None)
+    hanja: Option<Arc<HanjaDictionary>>,
+    /// 커서 앞에 연속으로 확정된 한글 음절 (한자 변환 대상)
+    recent_word: String,
+    /// 모아치기: 아직 해소되지 않은 키 묶음 (누른 순서)
+    chord: Vec<ChordKey>,
+    /// 모아치기: 묶음의 첫 키를 누른 시각
+    chord_started_at: u64,
+    /// 모아치기 허용 시간 (0이면 모아치기 없이 키를 누르는 즉시 처리)
+    chord_window_ms: u64,
+    /// 직접 확정 모드: 조합 중인 글자도 바로 확정하고, 바뀌면 지운 뒤 다시 넣는다
+    direct_commit: bool,
+    /// 직접 확정 모드에서 문서에 이미 들어가 있는 조합 중 텍스트
+    direct_shown: String,
+    /// 자동 한영 전환: 영어 단어 감지기 (단어 목록 미로드 시 None)
+    english_detector: Option<Arc<EnglishDetector>>,
+    /// 자동 한영 전환 임계값 (단어 목록과 따로 보관해 로드 순서와 무관하게 적용)
+    english_threshold: f32,
+    /// 자동 한영 전환 예외 단어 (단어 목록과 따로 보관)
+    english_exceptions: Vec<String>,
+    /// 자동 한영 전환: 현재 단어에서 누른 키
+    word_keys: String,
+    /// 자동 한영 전환: 현재 단어에서 이미 확정된 한글
+    word_text: String,
+    /// 자동 한영 전환: 영문자가 아닌 자모 키나 백스페이스로 감지 대상에서 빠진 단어인지
+    word_broken: bool,
+    /// 마지막 처리 결과에 딸린 입력 모드 전환 제안
+    suggested_mode: Option<InputMode>,
+}
+
+/// 한자 변환 대상
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HanjaTarget {
+    /// 조합 중인 음절
+    Composing(String),
+    /// 최근 확정된 단어 (커서 앞에서 지울 글자 수 = 글자 길이)
+    Committed(String),
 }
 
 impl Default for EngineState {
@@ -28,24 +104,202 @@ impl EngineState {
         EngineState {
             mode: InputMode::English,
             layout: None,
+            english_layout: None,
             automata: None,
+            hanja: None,
+            recent_word: String::new(),
+            chord: Vec::new(),
+            chord_started_at: 0,
+            chord_window_ms: DEFAULT_CHORD_WINDOW_MS,
+            direct_commit: false,
+            direct_shown: String::new(),
+            english_detector: None,
+            english_threshold: english::DEFAULT_THRESHOLD,
+            english_exceptions: Vec::new(),
+            word_keys: String::new(),
+            word_text: String::new(),
+            word_broken: false,
+            suggested_mode: None,
         }
     }
 
     /// JSON5 문자열로 레이아웃을 로드하고 오토마타를 생성한다.
-    pub fn load_layout(&mut self, json: &str) -> Result<(), String> {
+    pub fn load_layout(&mut self, json: &str) -> Result<(), LayoutError> {
         let layout = KeyboardLayout::from_json(json)?;
-        let auto = automata::create_automata(&layout);
-        self.layout = Some(layout);
+        let auto = automata::create_automata(&layout).ok_or_else(|| LayoutError::WrongType {
+            id: layout.id.clone(),
+            message: "is a latin layout; load it as the English layout".to_string(),
+        })?;
+        self.layout = Some(Arc::new(layout));
         self.automata = Some(auto);
+        self.chord.clear();
         Ok(())
     }
 
+    /// 영문 모드용 배열(type: "latin")을 로드한다.
+    pub fn load_english_layout(&mut self, json: &str) -> Result<(), LayoutError> {
+        let layout = KeyboardLayout::from_json(json)?;
+        if layout.layout_type != LayoutType::Latin {
+            return Err(LayoutError::WrongType {
+                id: layout.id,
+                message: "is not a latin layout".to_string(),
+            });
+        }
+        self.english_layout = Some(Arc::new(layout));
+        Ok(())
+    }
+
+    /// 로드된 한글 배열 ID
+    pub fn layout_id(&self) -> Option<&str> {
+        self.layout.as_ref().map(|l| l.id.as_str())
+    }
+
+    /// 로드된 영문 배열 ID
+    pub fn english_layout_id(&self) -> Option<&str> {
+        self.english_layout.as_ref().map(|l| l.id.as_str())
+    }
+
+    /// 영문 모드용 배열을 해제한다. (시스템 배열의 키 레이블을 그대로 입력)
+    pub fn clear_english_layout(&mut self) {
+        self.english_layout = None;
+    }
+
+    /// 이 상태와 레이아웃·사전·설정을 공유하는 새 세션 상태를 만든다. (입력 모드는 같고 조합은 비어 있음)
+    pub fn new_session(&self) -> EngineState {
+        let mut session = EngineState::new();
+        session.mode = self.mode;
+        session.share_config(self);
+        session
+    }
+
+    /// 레이아웃·사전·모아치기 설정을 `other`와 같게 맞춘다.
+    /// 한글 배열이 바뀌면 조합 중이던 글자는 폐기된다. 입력 모드와 직접 확정 모드는 세션마다 따로 둔다.
+    pub fn share_config(&mut self, other: &EngineState) {
+        if !same_shared(&self.layout, &other.layout) {
+            self.reset();
+            self.layout = other.layout.clone();
+            self.automata = other.layout.as_deref().and_then(automata::create_automata);
+        }
+        self.english_layout = other.english_layout.clone();
+        self.hanja = other.hanja.clone();
+        if !same_shared(&self.english_detector, &other.english_detector) {
+            self.english_detector = other.english_detector.clone();
+            self.clear_word();
+        }
+        self.english_threshold = other.english_threshold;
+        self.english_exceptions = other.english_exceptions.clone();
+        self.chord_window_ms = other.chord_window_ms;
+    }
+
+    /// 세션이 포커스를 잃을 때 호출한다. 조합 중인 글자는 세션에 남겨 두고
+    /// 해소되지 않은 모아치기 묶음만 조합한다. 결과의 `composing`은 항상 None이므로
+    /// 프런트엔드는 marked text를 지운다.
+    ///
+    /// 직접 확정 모드에서는 조합 중 글자가 이미 문서에 있으므로 확정한다.
+    pub fn suspend(&mut self) -> AutomataResult {
+        let mut result = if self.direct_commit {
+            let result = self.flush();
+            self.present(result)
+        } else {
+            self.resolve_chord()
+        };
+        result.composing = None;
+        result
+    }
+
+    /// 세션이 다시 포커스를 얻을 때 호출한다. 남겨 둔 조합 중 텍스트를 반환한다.
+    pub fn resume(&self) -> AutomataResult {
+        let composing = if self.direct_commit {
+            None
+        } else {
+            self.composing_text()
+        };
+        AutomataResult::handled(None, composing)
+    }
+
+    /// 입력 모드와 조합 상태를 버전이 붙은 JSON 스냅샷으로 만든다.
+    /// 해소되지 않은 모아치기 묶음은 포함하지 않는다.
+    pub fn snapshot(&self) -> String {
+        let snapshot = EngineSnapshot {
+            version: SNAPSHOT_VERSION,
+            mode: self.mode,
+            layout_id: self.layout_id().map(str::to_string),
+            direct_commit: self.direct_commit,
+            automata: self.automata.as_ref().map(|a| a.snapshot()),
+        };
+        json5::to_string(&snapshot).unwrap_or_else(|e| {
+            crate::warn_unexpected("snapshot", e);
+            String::new()
+        })
+    }
+
+    /// [`snapshot`](Self::snapshot)으로 만든 상태를 되살리고, 되살린 조합 중 텍스트를 반환한다.
+    /// 같은 한글 배열이 로드되어 있어야 하며, 실패하면 상태를 바꾸지 않는다.
+    pub fn restore(&mut self, snapshot: &str) -> Result<AutomataResult, String> {
+        let snapshot: EngineSnapshot =
+            json5::from_str(snapshot).map_err(|e| format!("Invalid snapshot: {e}"))?;
+        if snapshot.version != SNAPSHOT_VERSION {
+            return Err(format!(
+                "Unsupported snapshot version {} (expected {SNAPSHOT_VERSION})",
+                snapshot.version
+            ));
+        }
+        let layout_id = self.layout_id();
+        if snapshot.layout_id.is_some() && snapshot.layout_id.as_deref() != layout_id {
+            return Err(format!(
+                "Snapshot layout '{}' does not match loaded layout '{}'",
+                snapshot.layout_id.unwrap_or_default(),
+                layout_id.unwrap_or_default()
+            ));
+        }
+
+        let mut restored = self.layout.as_deref().and_then(automata::create_automata);
+        if let (Some(automata), Some(layout), Some(state)) =
+            (&mut restored, &self.layout, &snapshot.automata)
+            && !automata.restore(state, layout)
+        {
+            return Err("Snapshot does not match the layout's automata".to_string());
+        }
+
+        self.reset();
+        if restored.is_some() {
+            self.automata = restored;
+        }
+        self.mode = snapshot.mode;
+        self.direct_commit = snapshot.direct_commit;
+        if self.direct_commit {
+            // 직접 확정 모드의 조합 중 글자는 이미 문서에 들어가 있다
+            self.direct_shown = self.composing_text().unwrap_or_default();
+        }
+        Ok(self.resume())
+    }
+
+    /// 영문 모드 키 처리: 영문 배열이 있으면 변환, 없으면 키 레이블 그대로.
+    fn process_english_key(&self, key: &str) -> AutomataResult {
+        let text = match self
+            .english_layout
+            .as_ref()
+            .and_then(|l| l.map_latin_key(key))
+        {
+            Some(ch) => ch.to_string(),
+            None => key.to_string(),
+        };
+        AutomataResult::handled(Some(text), None)
+    }
+
     /// 키 레이블을 처리한다.
     pub fn process_key(&mut self, key: &str) -> AutomataResult {
-        // 영문 모드: 키를 그대로 committed로 반환 (단독 입력 소스 전략)
+        let composing = self.composing_text();
+        let result = self.process_key_inner(key);
+        let result = self.detect_english_word(key, composing, result);
+        self.track_result(&result);
+        result
+    }
+
+    fn process_key_inner(&mut self, key: &str) -> AutomataResult {
+        // 영문 모드: 키(또는 영문 배열로 변환한 문자)를 committed로 반환 (단독 입력 소스 전략)
         if self.mode == InputMode::English {
-            return AutomataResult::handled(Some(key.to_string()), None);
+            return self.process_english_key(key);
         }
 
         let (layout, automata) = match (&self.layout, &mut self.automata) {
@@ -53,44 +307,283 @@ impl EngineState {
             _ => return AutomataResult::not_handled(),
         };
 
-        let ch = match layout.map_key(key) {
-            Some(ch) => ch,
-            None => {
-                // 레이아웃에 없는 키 → 현재 조합 확정 후 패스스루
-                let mut result = automata.flush();
-                result.handled = false;
-                return result;
+        automata::process_key_label(automata.as_mut(), key, layout)
+    }
+
+    /// 물리 키(USB HID usage)를 처리한다.
+    ///
+    /// 물리 위치에 해당하는 US QWERTY 레이블로 바꿔 [`process_key`](Self::process_key)와 같이 처리한다.
+    /// 영문 모드에서 영문 배열이 없으면 문자는 시스템 배열이 결정하므로 처리하지 않는다(handled = false).
+    pub fn process_physical_key(&mut self, usage: u32, shift: bool) -> AutomataResult {
+        if self.mode == InputMode::English && self.english_layout.is_none() {
+            return AutomataResult::not_handled();
+        }
+        match physical::from_usage(usage) {
+            Some(key) => self.process_key(key.label(shift)),
+            // 문자 키가 아님 → 현재 조합 확정 후 패스스루
+            None => self.flush_word_boundary(),
+        }
+    }
+
+    /// 키 누름 이벤트를 라우팅하고 처리한다.
+    ///
+    /// Backspace·Enter·Escape·단축키·방향키 등을 구분해 필요한 엔진 동작(처리, 확정)을 수행하고,
+    /// 프런트엔드가 결과를 어떻게 적용할지를 [`KeyAction`]으로 함께 반환한다.
+    /// `shift_space_toggle`은 Shift+Space가 한/영 전환 키인지 여부.
+    pub fn handle_event(
+        &mut self,
+        event: &KeyEvent,
+        shift_space_toggle: bool,
+    ) -> (KeyAction, AutomataResult) {
+        let route = event::route_key_down(
+            event,
+            self.mode,
+            shift_space_toggle,
+            self.english_layout.is_some(),
+        );
+        match route {
+            KeyRoute::ToggleMode => (
+                KeyAction::ToggleMode,
+                AutomataResult::handled(None, self.composing_text()),
+            ),
+            KeyRoute::PassToSystem => (KeyAction::PassToSystem, AutomataResult::not_handled()),
+            KeyRoute::FlushAndPassToSystem => (KeyAction::Apply, self.flush_and_pass()),
+            KeyRoute::Backspace => (KeyAction::Apply, self.backspace()),
+            KeyRoute::Enter => {
+                // 조합 중 글자가 marked text로 있었을 때만 Enter를 다시 보낸다
+                let repost = !self.direct_commit && self.composing_text().is_some();
+                (KeyAction::Enter { repost }, self.flush_word_boundary())
             }
+            KeyRoute::Space => (KeyAction::Apply, self.flush_word_boundary()),
+            KeyRoute::Escape => (KeyAction::Escape, self.flush_and_pass()),
+            KeyRoute::ProcessKey(label) => (KeyAction::Apply, self.process_key(&label)),
+        }
+    }
+
+    /// 단어 경계 키: 현재 조합을 확정하고 키는 시스템에 넘긴다. 자동 한영 전환 판단 지점.
+    fn flush_word_boundary(&mut self) -> AutomataResult {
+        let composing = self.composing_text();
+        let mut result = self.flush_composing();
+        result.handled = false;
+        let result = self.detect_english_word("", composing, result);
+        self.recent_word.clear();
+        result
+    }
+
+    /// 현재 조합을 확정하고 키는 시스템에 넘긴다. (단축키, 커서 이동 등)
+    fn flush_and_pass(&mut self) -> AutomataResult {
+        let mut result = self.flush();
+        result.handled = false;
+        self.recent_word.clear();
+        result
+    }
+
+    /// 영문 모드로 잘못 친 문자열을 한글 배열로 다시 입력한 결과를 반환한다. (조합 상태와 무관)
+    /// 영문 배열이 있으면 화면의 글자를 먼저 키 레이블로 되돌린다.
+    pub fn convert_to_hangul(&self, text: &str) -> String {
+        let Some(layout) = &self.layout else {
+            return text.to_string();
+        };
+        let keys = match &self.english_layout {
+            Some(english) => convert::to_latin(english, text),
+            None => text.to_string(),
         };
+        convert::to_hangul(layout, &keys)
+    }
+
+    /// 한글 모드로 잘못 친 문자열을 영문 모드로 입력했을 때의 문자열로 되돌린다. (조합 상태와 무관)
+    pub fn convert_to_latin(&self, text: &str) -> String {
+        let Some(layout) = &self.layout else {
+            return text.to_string();
+        };
+        let keys = convert::to_latin(layout, text);
+        match &self.english_layout {
+            Some(english) => convert::to_hangul(english, &keys),
+            None => keys,
+        }
+    }
+
+    /// 모아치기 허용 시간을 설정한다. 0이면 키를 누르는 즉시 처리한다.
+    pub fn set_chord_window(&mut self, window_ms: u64) {
+        self.chord_window_ms = window_ms;
+    }
+
+    /// 모아치기 묶음으로 받을 수 있는 상태인지 (한글 모드의 세벌식 레이아웃)
+    fn chord_enabled(&self) -> bool {
+        self.mode == InputMode::Korean
+            && self.chord_window_ms > 0
+            && self
+                .layout
+                .as_ref()
+                .is_some_and(|l| l.layout_type == LayoutType::Jaso)
+    }
+
+    /// 키 누름 이벤트를 처리한다. (`timestamp_ms`: 단조 증가 시각, 밀리초)
+    ///
+    /// 세벌식에서는 허용 시간 안에 겹쳐 눌린 키를 한 묶음(모아치기)으로 모았다가
+    /// 모두 떼었을 때 누른 순서와 무관하게 한 음절로 조합한다.
+    /// 그 밖의 레이아웃이나 영문 모드에서는 [`process_key`](Self::process_key)와 같다.
+    pub fn key_down(&mut self, key: &str, timestamp_ms: u64) -> AutomataResult {
+        let is_jamo = self.layout.as_ref().is_some_and(|l| {
+            l.map_key(key)
+                .is_some_and(|ch| automata::is_composable_jamo(ch, l))
+        });
+        if !self.chord_enabled() || !is_jamo {
+            // 자모가 아닌 키는 시스템에 위임될 수 있으므로 묶지 않고 즉시 처리
+            let pending = self.resolve_chord();
+            return pending.then(self.process_key(key));
+        }
+
+        let pending = if !self.chord.is_empty()
+            && timestamp_ms.saturating_sub(self.chord_started_at) > self.chord_window_ms
+        {
+            // 허용 시간을 넘긴 키 → 이전 묶음을 먼저 조합
+            self.resolve_chord()
+        } else {
+            AutomataResult::handled(None, self.composing_text())
+        };
+        if self.chord.iter().any(|c| c.key == key) {
+            // 키 반복 입력 → 무시
+            return pending;
+        }
+        if self.chord.is_empty() {
+            self.chord_started_at = timestamp_ms;
+        }
+        self.chord.push(ChordKey {
+            key: key.to_string(),
+            released: false,
+        });
+        pending
+    }
 
-        // 비자모 문자 (숫자, 기호 등): 오토마타를 거치지 않고 직접 처리
-        if !unicode::is_korean_jamo(ch) {
-            let flush = automata.flush();
-            let mut committed = flush.committed.unwrap_or_default();
-            committed.push(ch);
-            return AutomataResult::handled(Some(committed), None);
+    /// 키 뗌 이벤트를 처리한다. 묶음의 키가 모두 떼어지면 조합한다.
+    /// 묶음에 없는 키(이미 처리된 키)는 처리하지 않는다(handled = false).
+    pub fn key_up(&mut self, key: &str, _timestamp_ms: u64) -> AutomataResult {
+        let Some(entry) = self.chord.iter_mut().find(|c| c.key == key) else {
+            return AutomataResult::not_handled();
+        };
+        entry.released = true;
+        if self.chord.iter().all(|c| c.released) {
+            self.resolve_chord()
+        } else {
+            AutomataResult::handled(None, self.composing_text())
         }
+    }
 
-        automata.process(ch, layout)
+    /// 모아 둔 묶음을 누른 순서와 무관하게 조합한다.
+    fn resolve_chord(&mut self) -> AutomataResult {
+        if self.chord.is_empty() {
+            return AutomataResult::handled(None, self.composing_text());
+        }
+        let keys: Vec<String> = self.chord.drain(..).map(|c| c.key).collect();
+        let (Some(layout), Some(automata)) = (&self.layout, &mut self.automata) else {
+            return AutomataResult::not_handled();
+        };
+        // 묶음에는 자모 키만 들어온다 (key_down 참조)
+        let chars: Vec<char> = keys.iter().filter_map(|k| layout.map_key(k)).collect();
+        let result = automata.process_chord(&chars, layout);
+        for key in &keys {
+            self.track_word_key(key, &result);
+        }
+        self.track_result(&result);
+        result
     }
 
     /// 백스페이스 처리
     pub fn backspace(&mut self) -> AutomataResult {
         if self.mode == InputMode::English {
+            self.recent_word.clear();
             return AutomataResult::not_handled();
         }
-        match &mut self.automata {
+        let result = match &mut self.automata {
             Some(a) => a.backspace(),
             None => AutomataResult::not_handled(),
+        };
+        self.word_broken = true;
+        if !result.handled {
+            // 시스템이 커서 앞 한 글자를 지운다
+            self.recent_word.pop();
         }
+        result
     }
 
-    /// 현재 조합을 확정한다.
+    /// 커서 앞 텍스트를 참고하는 백스페이스.
+    /// 조합 중인 글자가 없고 커서 바로 앞이 한글 음절이면 그 음절을 다시 조합 상태로 되살려
+    /// 자모 하나만 지운다. 되살린 음절은 `delete_before`로 문서에서 지운 뒤 조합으로 다시 보낸다.
+    pub fn backspace_with_context(&mut self, text_before_cursor: &str) -> AutomataResult {
+        if self.mode == InputMode::Korean
+            && self.chord.is_empty()
+            && let Some(ch) = text_before_cursor.chars().last()
+            && unicode::is_syllable(ch)
+            && let (Some(a), Some(layout)) = (&mut self.automata, &self.layout)
+            && a.state() == AutomataState::Empty
+            && a.load_syllable(ch, layout)
+        {
+            let mut result = a.backspace();
+            result.delete_before += 1;
+            self.word_broken = true;
+            if self.recent_word.ends_with(ch) {
+                self.recent_word.pop();
+            }
+            return result;
+        }
+        self.backspace()
+    }
+
+    /// 현재 조합을 확정한다. 해소되지 않은 모아치기 묶음이 있으면 먼저 조합한다.
     pub fn flush(&mut self) -> AutomataResult {
-        match &mut self.automata {
+        let result = self.flush_composing();
+        self.clear_word();
+        result
+    }
+
+    fn flush_composing(&mut self) -> AutomataResult {
+        let pending = self.resolve_chord();
+        let result = match &mut self.automata {
             Some(a) => a.flush(),
             None => AutomataResult::handled(None, None),
+        };
+        self.track_result(&result);
+        pending.then(result)
+    }
+
+    /// 직접 확정 모드 여부
+    pub fn direct_commit(&self) -> bool {
+        self.direct_commit
+    }
+
+    /// 직접 확정 모드를 켜거나 끈다. 현재 조합은 확정하고 그 결과를 반환한다.
+    pub fn set_direct_commit(&mut self, enabled: bool) -> AutomataResult {
+        let result = self.flush();
+        let result = self.present(result);
+        self.direct_commit = enabled;
+        result
+    }
+
+    /// 처리 결과를 프런트엔드에 전달할 형태로 바꾼다.
+    ///
+    /// 직접 확정 모드에서는 조합 중 텍스트를 marked text로 남기지 않고 확정 텍스트로 보낸다.
+    /// 이미 문서에 넣은 조합 텍스트와 새 텍스트의 공통 앞부분은 그대로 두고,
+    /// 달라진 부분만 `delete_before`글자 지운 뒤 다시 넣는다.
+    pub fn present(&mut self, mut result: AutomataResult) -> AutomataResult {
+        if !self.direct_commit {
+            return result;
         }
+        let composing = result.composing.take().unwrap_or_default();
+        let mut text = result.committed.take().unwrap_or_default();
+        text.push_str(&composing);
+
+        let common = self
+            .direct_shown
+            .chars()
+            .zip(text.chars())
+            .take_while(|(a, b)| a == b)
+            .count();
+        let insert: String = text.chars().skip(common).collect();
+        result.delete_before += (self.direct_shown.chars().count() - common) as u32;
+        result.committed = (!insert.is_empty()).then_some(insert);
+        self.direct_shown = composing;
+        result
     }
 
     /// 현재 조합을 폐기한다.
@@ -99,10 +592,215 @@ impl EngineState {
             // flush하고 결과를 버린다
             let _ = a.flush();
         }
+        self.recent_word.clear();
+        self.chord.clear();
+        // 직접 확정 모드에서 이미 넣은 글자는 문서에 남는다
+        self.direct_shown.clear();
+        self.clear_word();
+    }
+
+    /// 처리 결과를 최근 확정 단어에 반영한다.
+    /// 한글 음절이 아닌 글자가 확정되거나 키가 시스템에 위임되면 단어가 끊긴다.
+    fn track_result(&mut self, result: &AutomataResult) {
+        if let Some(text) = &result.committed {
+            for ch in text.chars() {
+                if unicode::is_syllable(ch) {
+                    self.recent_word.push(ch);
+                } else {
+                    self.recent_word.clear();
+                }
+            }
+        }
+        if !result.handled {
+            self.recent_word.clear();
+        }
+    }
+
+    /// 영어 단어 감지기를 설정한다. None이면 자동 한영 전환을 끈다.
+    /// 먼저 설정한 임계값과 예외 단어를 적용한다.
+    pub fn set_english_detector(&mut self, detector: Option<EnglishDetector>) {
+        self.english_detector = detector.map(|mut detector| {
+            detector.set_threshold(self.english_threshold);
+            detector.set_exceptions(&self.english_exceptions);
+            Arc::new(detector)
+        });
+        self.clear_word();
+    }
+
+    /// 설정된 영어 단어 감지기 (없으면 None)
+    pub fn english_detector(&self) -> Option<Arc<EnglishDetector>> {
+        self.english_detector.clone()
+    }
+
+    /// 자동 한영 전환 임계값 (단어 목록 로드 여부와 무관)
+    pub fn english_detection_threshold(&self) -> f32 {
+        self.english_threshold
+    }
+
+    /// 자동 한영 전환 예외 단어 (설정한 그대로)
+    pub fn english_detection_exceptions(&self) -> &[String] {
+        &self.english_exceptions
+    }
+
+    /// 자동 한영 전환 임계값(0.0 ~ 1.0)을 설정한다. 단어 목록을 나중에 로드해도 유지된다.
+    pub fn set_english_detection_threshold(&mut self, threshold: f32) {
+        self.english_threshold = threshold.clamp(0.0, 1.0);
+        if let Some(detector) = self.english_detector.as_mut() {
+            Arc::make_mut(detector).set_threshold(self.english_threshold);
+        }
+    }
+
+    /// 자동 한영 전환 예외 단어를 설정한다. 단어 목록을 나중에 로드해도 유지된다.
+    pub fn set_english_detection_exceptions(&mut self, words: Vec<String>) {
+        self.english_exceptions = words;
+        if let Some(detector) = self.english_detector.as_mut() {
+            Arc::make_mut(detector).set_exceptions(&self.english_exceptions);
+        }
+    }
+
+    /// 마지막 처리 결과에 딸린 입력 모드 전환 제안을 꺼낸다.
+    pub fn take_suggested_mode(&mut self) -> Option<InputMode> {
+        self.suggested_mode.take()
+    }
+
+    /// 자동 한영 전환: 단어가 끝나면 그 단어를 영어로 잘못 친 것인지 판단한다.
+    /// 영어로 보이면 문서에 확정된 한글을 지우고(`delete_before`) 영어로 바꾼 결과를 반환한다.
+    fn detect_english_word(
+        &mut self,
+        key: &str,
+        composing: Option<String>,
+        result: AutomataResult,
+    ) -> AutomataResult {
+        if self.english_detector.is_none() || self.mode != InputMode::Korean {
+            return result;
+        }
+        let is_jamo_key = result.handled
+            && self.layout.as_ref().is_some_and(|l| {
+                l.map_key(key)
+                    .is_some_and(|ch| automata::is_composable_jamo(ch, l))
+            });
+        if is_jamo_key {
+            self.track_word_key(key, &result);
+            return result;
+        }
+
+        // 단어 경계: 조합 중이던 글자는 결과의 확정 텍스트 앞부분에 있다
+        let composing = composing.unwrap_or_default();
+        let rest = result
+            .committed
+            .as_deref()
+            .unwrap_or_default()
+            .strip_prefix(composing.as_str())
+            .map(str::to_string);
+        let english = match &self.english_layout {
+            Some(layout) => convert::to_hangul(layout, &self.word_keys),
+            None => self.word_keys.clone(),
+        };
+        let hangul = format!("{}{composing}", self.word_text);
+        let replace = !self.word_broken
+            && result.delete_before == 0
+            && self
+                .english_detector
+                .as_ref()
+                .is_some_and(|d| d.is_english(&english, &hangul));
+        let deleted = self.word_text.chars().count() as u32;
+        self.clear_word();
+        match rest {
+            Some(rest) if replace => {
+                self.suggested_mode = Some(InputMode::English);
+                AutomataResult {
+                    committed: Some(english + &rest),
+                    composing: result.composing,
+                    handled: result.handled,
+                    delete_before: deleted,
+                }
+            }
+            _ => result,
+        }
+    }
+
+    /// 자동 한영 전환: 단어 안에서 누른 키와 그 결과를 기록한다.
+    fn track_word_key(&mut self, key: &str, result: &AutomataResult) {
+        if self.english_detector.is_none() {
+            return;
+        }
+        if key.len() == 1 && key.as_bytes()[0].is_ascii_alphabetic() {
+            self.word_keys.push_str(key);
+        } else {
+            self.word_broken = true;
+        }
+        for _ in 0..result.delete_before {
+            self.word_text.pop();
+        }
+        if let Some(text) = &result.committed {
+            self.word_text.push_str(text);
+        }
+    }
+
+    fn clear_word(&mut self) {
+        self.word_keys.clear();
+        self.word_text.clear();
+        self.word_broken = false;
+    }
+
+    /// 한자 사전을 설정한다.
+    pub fn set_hanja_dictionary(&mut self, dict: HanjaDictionary) {
+        self.hanja = Some(Arc::new(dict));
+    }
+
+    /// 읽기에 해당하는 한자 후보 (사전 미로드 시 빈 슬라이스)
+    pub fn hanja_candidates(&self, text: &str) -> &[HanjaEntry] {
+        match &self.hanja {
+            Some(dict) => dict.lookup(text),
+            None => &[],
+        }
+    }
+
+    /// 한자 변환 대상: 조합 중인 음절이 우선, 없으면 최근 확정 단어 중 후보가 있는 가장 긴 접미사.
+    pub fn hanja_target(&self) -> Option<HanjaTarget> {
+        let dict = self.hanja.as_ref()?;
+        if let Some(composing) = self.composing_text() {
+            return Some(HanjaTarget::Composing(composing));
+        }
+        dict.longest_suffix(&self.recent_word)
+            .map(|word| HanjaTarget::Committed(word.to_string()))
+    }
+
+    /// 변환 대상을 `index`번째 한자 후보로 바꾼다.
+    /// 조합 중 음절은 폐기되고, 최근 확정 단어는 비워진다.
+    pub fn convert_to_hanja(&mut self, index: usize) -> Option<(HanjaTarget, HanjaEntry)> {
+        let target = self.hanja_target()?;
+        let reading = match &target {
+            HanjaTarget::Composing(text) | HanjaTarget::Committed(text) => text,
+        };
+        let entry = self.hanja_candidates(reading).get(index)?.clone();
+        if let HanjaTarget::Composing(_) = target
+            && let Some(a) = &mut self.automata
+        {
+            let _ = a.flush();
+            self.direct_shown.clear();
+        }
+        // 한자 뒤에는 새 단어가 시작된다
+        self.recent_word.clear();
+        Some((target, entry))
     }
 
     /// 현재 조합 중인 텍스트
     pub fn composing_text(&self) -> Option<String> {
         self.automata.as_ref().and_then(|a| a.composing_text())
     }
+
+    /// 현재 오토마타 조합 상태 (레이아웃이 없으면 None)
+    pub fn automata_snapshot(&self) -> Option<AutomataSnapshot> {
+        self.automata.as_ref().map(|a| a.snapshot())
+    }
+}
+
+/// 두 공유 자원이 같은 인스턴스인지 (둘 다 없으면 같다)
+fn same_shared<T>(a: &Option<Arc<T>>, b: &Option<Arc<T>>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+        (None, None) => true,
+        _ => false,
+    }
 }
@@ -0,0 +1,211 @@
+//! 한글 모드에서 잘못 친 영어 단어 감지 (자동 한영 전환)
+//!
+//! 한 단어 동안 누른 키를 영어 단어 목록에 비추어 보고, 그 키로 만들어진 한글이
+//! 얼마나 그럴듯한지를 함께 따져 점수를 매긴다. ("git" → "햣", "commit" → "채ㅡㅡㅑㅅ")
+//! 내장 음절 빈도 모델과 로컬 단어 목록만 사용하므로 네트워크가 필요 없다.
+//!
+//! 단어 목록 형식: 한 줄에 한 단어. 빈 줄과 `#`으로 시작하는 줄은 무시하고 대소문자는 구분하지 않는다.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::unicode;
+
+/// 기본 임계값: 단어 목록에 있고 한글로는 어색할 때 영어로 본다
+pub const DEFAULT_THRESHOLD: f32 = 0.6;
+
+/// 감지 대상 최소 키 수 (한 글자는 자음 단독 입력 "ㅋ" 등과 구분할 수 없다)
+const MIN_WORD_LEN: usize = 2;
+
+/// 자주 쓰는 한글 음절. 여기에 없는 완성 음절은 절반만 그럴듯한 것으로 본다.
+const COMMON_SYLLABLES: &str = "\
+가각간갈감갑값갓강같개객거건걸검것게겠겨격결경계고곡곤골곳공과관광괴교구국군굴권귀규그극근글금급기긴길김깊까깨께꼭꽃꾸끄끝끼\
+나난날남납낮내냐너널넘넣네녀년념녕노논놀농높놓누눈뉴느는늘능니님\
+다단달담답당대댁더덕던덜데도독돈돌동되된될됩두둘뒤드득든들듯등디따때떠떤떻또뚜뜨뜻띠\
+라락란람랑래랜략량러런럼렇레려력련렵령례로록론료루류륙률르른를름릅리린림립\
+마막만많말맞매맥머먹먼멀메며면명모목몰몸못무문물뭐므미민밀및\
+바박밖반받발밝방배백버번벌범법벗베벽변별병보복본볼봉부북분불비빌빛빠빨뿐\
+사산살삼상새색생서석선설섬성세소속손솔송수숙순술숨쉬스습승시식신실심십싶싸쌀써쓰씨\
+아악안않알암압았앞애야약양어억언얼엄업없었엇에여역연열염였영예오옥온올옷와완왔왜외요욕용우운울움웃원월위유육윤으은을음응의이익인일읽임입있잊\
+자작잔잘잠잡장재저적전절점접정제조족존졸종좋좌죠주죽준줄중즈즉즐증지직진질짐집짓짜째쪽찌\
+차착찬찰참창찾채책처천철첫청체초촌총최추축출충취츠측치친칠침\
+카커케코콘크큰키타탁태터텔토통투트특티팀\
+파판팔패퍼페편평포표품프피필\
+하학한할함합항해했행향허험혀현형혜호혹혼화확환활황회효후훈휴흐흔흘희히힘";
+
+/// 한글 모드 영어 단어 감지기
+#[derive(Debug, Clone)]
+pub struct EnglishDetector {
+    words: HashSet<String>,
+    exceptions: HashSet<String>,
+    threshold: f32,
+}
+
+impl EnglishDetector {
+    /// 단어 목록 텍스트를 파싱한다.
+    pub fn parse(text: &str) -> Self {
+        let words = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_lowercase)
+            .collect();
+        EnglishDetector {
+            words,
+            exceptions: HashSet::new(),
+            threshold: DEFAULT_THRESHOLD,
+        }
+    }
+
+    /// 단어로 감지기를 만든다. (대소문자 구분 없음)
+    pub fn from_words<I, S>(words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        EnglishDetector {
+            words: words
+                .into_iter()
+                .map(|w| w.as_ref().trim().to_lowercase())
+                .filter(|w| !w.is_empty())
+                .collect(),
+            exceptions: HashSet::new(),
+            threshold: DEFAULT_THRESHOLD,
+        }
+    }
+
+    /// 단어 목록 (정렬 순, 기록용)
+    pub fn words(&self) -> Vec<String> {
+        let mut words: Vec<String> = self.words.iter().cloned().collect();
+        words.sort_unstable();
+        words
+    }
+
+    /// 단어 목록 파일을 읽어 파싱한다.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Cannot read word list {}: {e}", path.display()))?;
+        Ok(Self::parse(&text))
+    }
+
+    /// 영어로 판단할 점수 임계값 (0.0 ~ 1.0)
+    pub fn threshold(&self) -> f32 {
+        self.threshold
+    }
+
+    pub fn set_threshold(&mut self, threshold: f32) {
+        self.threshold = threshold.clamp(0.0, 1.0);
+    }
+
+    /// 영어로 바꾸지 않을 단어 목록을 설정한다. (대소문자 구분 없음)
+    pub fn set_exceptions<I, S>(&mut self, words: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.exceptions = words
+            .into_iter()
+            .map(|w| w.as_ref().trim().to_lowercase())
+            .collect();
+    }
+
+    /// 영어일 가능성 점수 (0.0 ~ 1.0).
+    /// 단어 목록에 있으면 0.5, 한글이 어색할수록 최대 0.5를 더한다.
+    pub fn score(&self, english: &str, hangul: &str) -> f32 {
+        let in_list = if self.words.contains(&english.to_lowercase()) {
+            0.5
+        } else {
+            0.0
+        };
+        in_list + 0.5 * (1.0 - hangul_plausibility(hangul))
+    }
+
+    /// 단어 경계에서 `english`를 영어로 바꿔야 하는지 판단한다.
+    pub fn is_english(&self, english: &str, hangul: &str) -> bool {
+        english.chars().count() >= MIN_WORD_LEN
+            && !self.exceptions.contains(&english.to_lowercase())
+            && self.score(english, hangul) >= self.threshold
+    }
+}
+
+/// 한글 문자열이 실제 한국어처럼 보이는 정도 (0.0 ~ 1.0).
+/// 자주 쓰는 음절은 1, 그 밖의 완성 음절은 0.5, 낱자모와 그 밖의 글자는 0.
+pub fn hangul_plausibility(text: &str) -> f32 {
+    let (sum, count) = text.chars().fold((0.0, 0), |(sum, count), ch| {
+        let weight = if COMMON_SYLLABLES.contains(ch) {
+            1.0
+        } else if unicode::is_syllable(ch) {
+            0.5
+        } else {
+            0.0
+        };
+        (sum + weight, count + 1)
+    });
+    if count == 0 { 0.0 } else { sum / count as f32 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORDS: &str = "\
+# 테스트 단어 목록
+git
+commit
+the
+Go
+";
+
+    #[test]
+    fn test_parse_ignores_comments_and_case() {
+        let detector = EnglishDetector::parse(WORDS);
+        assert_eq!(detector.score("GIT", "햣"), detector.score("git", "햣"));
+        assert!(detector.score("go", "해") >= 0.5);
+        assert!(detector.score("#", "") < 0.6);
+    }
+
+    #[test]
+    fn test_garbage_hangul_is_english() {
+        let detector = EnglishDetector::parse(WORDS);
+        assert!(detector.is_english("git", "햣"));
+        assert!(detector.is_english("commit", "채ㅡㅡㅑㅅ"));
+        assert!(detector.is_english("the", "솓"));
+    }
+
+    #[test]
+    fn test_plausible_hangul_stays() {
+        let detector = EnglishDetector::parse(WORDS);
+        // 목록에 있어도 한글로 자연스러우면 그대로 둔다: go → "해"
+        assert!(!detector.is_english("go", "해"));
+        // 목록에 없으면 한글이 어색해도 그대로 둔다: zzz → "ㅋㅋㅋ"
+        assert!(!detector.is_english("zzz", "ㅋㅋㅋ"));
+        // 한국어 단어
+        assert!(!detector.is_english("dkssud", "안녕"));
+    }
+
+    #[test]
+    fn test_threshold_and_exceptions() {
+        let mut detector = EnglishDetector::parse(WORDS);
+        detector.set_threshold(0.5);
+        assert!(detector.is_english("go", "해"));
+        detector.set_exceptions(["GO"]);
+        assert!(!detector.is_english("go", "해"));
+        detector.set_threshold(2.0);
+        assert_eq!(detector.threshold(), 1.0);
+        assert!(!detector.is_english("git", "햣"));
+    }
+
+    #[test]
+    fn test_single_key_is_never_english() {
+        let detector = EnglishDetector::parse("a\ni\n");
+        assert!(!detector.is_english("i", "ㅑ"));
+    }
+
+    #[test]
+    fn test_hangul_plausibility() {
+        assert_eq!(hangul_plausibility("한국"), 1.0);
+        assert_eq!(hangul_plausibility("햣"), 0.5);
+        assert_eq!(hangul_plausibility("ㅋㅋ"), 0.0);
+        assert_eq!(hangul_plausibility(""), 0.0);
+    }
+}
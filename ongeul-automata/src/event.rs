@@ -0,0 +1,362 @@
+//! 플랫폼 중립 키 이벤트와 키 누름 라우팅
+//!
+//! 프런트엔드는 OS 키 이벤트를 [`KeyEvent`]로 옮겨 엔진에 넘기고, 엔진이 정한 [`KeyAction`]에 따라
+//! 처리 결과를 적용하거나 키를 시스템에 넘긴다. 키 코드는 USB HID usage ID를 쓴다.
+
+use crate::engine::InputMode;
+use crate::layout::physical;
+use crate::toggle::ModifierFlags;
+
+use serde::{Deserialize, Serialize};
+
+/// 키 누름 이벤트
+#[derive(uniffi::Record, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyEvent {
+    /// USB HID Keyboard page usage ID (예: Enter = 0x28, Q 위치 = 0x14)
+    pub keycode: u32,
+    /// OS가 만든 문자열 (Shift/CapsLock이 반영된 값, 문자가 없는 키는 None)
+    pub characters: Option<String>,
+    /// 눌려 있는 modifier
+    pub modifiers: ModifierFlags,
+    /// CapsLock 켜짐 여부
+    pub caps_lock: bool,
+}
+
+/// 엔진이 키를 처리한 뒤 프런트엔드가 할 일
+#[derive(uniffi::Enum, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyAction {
+    /// 결과를 적용한다. `handled`가 false이면 키를 시스템에 넘긴다.
+    Apply,
+    /// 결과를 적용하지 않고 키를 시스템에 넘긴다. (영문 모드)
+    PassToSystem,
+    /// Shift+Space 한/영 전환. 엔진 상태는 그대로이며 프런트엔드가 모드를 전환한다.
+    ToggleMode,
+    /// Enter: 결과를 적용하고 키를 시스템에 넘긴다.
+    /// `repost`이면 조합 확정과 같은 이벤트의 Enter를 무시하는 앱을 위해 Enter를 다시 보낸다.
+    Enter { repost: bool },
+    /// Escape(Control+[ 포함): 결과를 적용하고 키를 시스템에 넘긴다. 설정에 따라 영문으로 전환한다.
+    Escape,
+}
+
+/// 키 누름 라우팅 결정. 부수 효과 없이 어떤 동작을 수행할지만 결정한다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyRoute {
+    /// Shift+Space 한/영 전환 (Shift+Space가 전환 키일 때)
+    ToggleMode,
+    /// 영문 모드: 시스템에 위임
+    PassToSystem,
+    /// 단축키, 방향키, 키패드, 알 수 없는 키: flush 후 시스템에 위임
+    FlushAndPassToSystem,
+    /// Backspace: 엔진에 위임
+    Backspace,
+    /// Enter: flush 후 시스템에 위임 (단어 경계)
+    Enter,
+    /// Space: flush 후 시스템에 위임 (단어 경계)
+    Space,
+    /// Escape: 조합 확정 (+ 옵션: 영문 전환)
+    Escape,
+    /// 일반 키: 엔진에 위임
+    ProcessKey(String),
+}
+
+/// 키 이벤트의 속성으로부터 수행할 동작을 결정한다.
+///
+/// `english_layout`은 영문 배열이 로드되어 영문 모드에서도 엔진이 문자를 입력하는지 여부.
+pub fn route_key_down(
+    event: &KeyEvent,
+    mode: InputMode,
+    shift_space_toggle: bool,
+    english_layout: bool,
+) -> KeyRoute {
+    let m = &event.modifiers;
+
+    // Shift+Space → 한/영 전환
+    if shift_space_toggle
+        && event.keycode == physical::SPACE
+        && m.shift
+        && !m.option
+        && !m.command
+        && !m.control
+    {
+        return KeyRoute::ToggleMode;
+    }
+
+    // 영문 모드: 영문 배열의 문자 키만 처리하고 나머지는 시스템에 위임
+    if mode == InputMode::English {
+        return match event.characters.as_deref() {
+            Some(chars) if english_layout && !m.command && !m.control => {
+                // 영문 모드에서는 CapsLock의 대문자화를 그대로 둔다
+                key_label(chars, false, false).map_or(KeyRoute::PassToSystem, KeyRoute::ProcessKey)
+            }
+            _ => KeyRoute::PassToSystem,
+        };
+    }
+
+    // Control+[ → Vim ESC 등가
+    if m.control && !m.command && !m.option && event.keycode == physical::BRACKET_LEFT {
+        return KeyRoute::Escape;
+    }
+
+    // 시스템 단축키 → flush 후 통과
+    if m.command || m.control {
+        return KeyRoute::FlushAndPassToSystem;
+    }
+
+    match event.keycode {
+        physical::BACKSPACE => return KeyRoute::Backspace,
+        physical::ENTER | physical::NUMPAD_ENTER => return KeyRoute::Enter,
+        physical::SPACE => return KeyRoute::Space,
+        physical::ESCAPE => return KeyRoute::Escape,
+        // 키패드는 3벌식에서 숫자가 한글로 매핑되는 것을 막는다
+        usage if physical::is_arrow(usage) || physical::is_numpad(usage) => {
+            return KeyRoute::FlushAndPassToSystem;
+        }
+        _ => {}
+    }
+
+    // 일반 키 → 엔진에 위임
+    event
+        .characters
+        .as_deref()
+        .and_then(|chars| key_label(chars, event.caps_lock, m.shift))
+        .map_or(KeyRoute::FlushAndPassToSystem, KeyRoute::ProcessKey)
+}
+
+/// 키 이벤트의 문자열과 modifier 상태로부터 엔진에 전달할 키 레이블을 추출한다.
+///
+/// 한글 모드에서 CapsLock의 영향을 무효화한다:
+/// - CapsLock ON + Shift 없음 → 소문자로 변환 (CapsLock의 대문자화 취소)
+/// - CapsLock ON + Shift → 대문자로 변환 (Shift 우선)
+///
+/// ASCII 문자/숫자/기호만 반환하며, 그 외에는 None을 반환한다.
+pub fn key_label(characters: &str, caps_lock: bool, shift: bool) -> Option<String> {
+    let ch = characters.chars().next()?;
+    if !ch.is_ascii_graphic() {
+        return None;
+    }
+    let ch = match (caps_lock && ch.is_ascii_alphabetic(), shift) {
+        (true, false) => ch.to_ascii_lowercase(),
+        (true, true) => ch.to_ascii_uppercase(),
+        (false, _) => ch,
+    };
+    Some(ch.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY_A: u32 = 0x04;
+    const KEY_G: u32 = 0x0A;
+
+    fn event(keycode: u32, characters: Option<&str>, modifiers: ModifierFlags) -> KeyEvent {
+        KeyEvent {
+            keycode,
+            characters: characters.map(str::to_string),
+            modifiers,
+            caps_lock: false,
+        }
+    }
+
+    fn shift() -> ModifierFlags {
+        ModifierFlags {
+            shift: true,
+            ..Default::default()
+        }
+    }
+
+    fn korean(event: &KeyEvent) -> KeyRoute {
+        route_key_down(event, InputMode::Korean, false, false)
+    }
+
+    // ── 라우팅 ──
+
+    #[test]
+    fn test_shift_space_toggle() {
+        let e = event(physical::SPACE, Some(" "), shift());
+        assert_eq!(
+            route_key_down(&e, InputMode::Korean, true, false),
+            KeyRoute::ToggleMode
+        );
+        // 영문 모드에서도 전환
+        assert_eq!(
+            route_key_down(&e, InputMode::English, true, false),
+            KeyRoute::ToggleMode
+        );
+        // Shift+Space가 전환 키가 아니면 Space
+        assert_eq!(korean(&e), KeyRoute::Space);
+    }
+
+    #[test]
+    fn test_english_mode_passthrough() {
+        let e = event(KEY_G, Some("g"), ModifierFlags::default());
+        assert_eq!(
+            route_key_down(&e, InputMode::English, false, false),
+            KeyRoute::PassToSystem
+        );
+    }
+
+    #[test]
+    fn test_english_layout_processes_characters() {
+        let mut e = event(KEY_G, Some("G"), ModifierFlags::default());
+        e.caps_lock = true;
+        assert_eq!(
+            route_key_down(&e, InputMode::English, false, true),
+            KeyRoute::ProcessKey("G".to_string())
+        );
+        let cmd = event(
+            KEY_A,
+            Some("a"),
+            ModifierFlags {
+                command: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            route_key_down(&cmd, InputMode::English, false, true),
+            KeyRoute::PassToSystem
+        );
+    }
+
+    #[test]
+    fn test_shortcuts_flush_and_pass() {
+        for modifiers in [
+            ModifierFlags {
+                command: true,
+                ..Default::default()
+            },
+            ModifierFlags {
+                control: true,
+                ..Default::default()
+            },
+        ] {
+            let e = event(KEY_A, Some("a"), modifiers);
+            assert_eq!(korean(&e), KeyRoute::FlushAndPassToSystem);
+        }
+    }
+
+    #[test]
+    fn test_control_bracket_is_escape() {
+        let control = ModifierFlags {
+            control: true,
+            ..Default::default()
+        };
+        let e = event(physical::BRACKET_LEFT, Some("["), control);
+        assert_eq!(korean(&e), KeyRoute::Escape);
+        // Command가 함께 눌리면 단축키
+        let e = event(
+            physical::BRACKET_LEFT,
+            Some("["),
+            ModifierFlags {
+                command: true,
+                ..control
+            },
+        );
+        assert_eq!(korean(&e), KeyRoute::FlushAndPassToSystem);
+    }
+
+    #[test]
+    fn test_special_keys() {
+        let none = ModifierFlags::default();
+        assert_eq!(
+            korean(&event(physical::BACKSPACE, None, none)),
+            KeyRoute::Backspace
+        );
+        assert_eq!(
+            korean(&event(physical::ENTER, Some("\r"), none)),
+            KeyRoute::Enter
+        );
+        assert_eq!(
+            korean(&event(physical::NUMPAD_ENTER, Some("\u{3}"), none)),
+            KeyRoute::Enter
+        );
+        assert_eq!(
+            korean(&event(physical::SPACE, Some(" "), none)),
+            KeyRoute::Space
+        );
+        assert_eq!(
+            korean(&event(physical::ESCAPE, Some("\u{1b}"), none)),
+            KeyRoute::Escape
+        );
+    }
+
+    #[test]
+    fn test_arrows_and_numpad_flush_and_pass() {
+        let none = ModifierFlags::default();
+        for usage in [
+            physical::ARROW_LEFT,
+            physical::ARROW_RIGHT,
+            physical::ARROW_DOWN,
+            physical::ARROW_UP,
+        ] {
+            assert_eq!(
+                korean(&event(usage, None, none)),
+                KeyRoute::FlushAndPassToSystem
+            );
+        }
+        // 키패드 1(0x59), 0(0x62), +(0x57): 문자가 있어도 엔진에 넘기지 않는다
+        for (usage, ch) in [(0x59, "1"), (0x62, "0"), (0x57, "+")] {
+            assert_eq!(
+                korean(&event(usage, Some(ch), none)),
+                KeyRoute::FlushAndPassToSystem
+            );
+        }
+    }
+
+    #[test]
+    fn test_main_row_digit_is_processed() {
+        // 숫자행 "1"은 키패드가 아니므로 엔진에 넘긴다 (3벌식에서 한글로 변환되는 정상 경로)
+        let e = event(0x1E, Some("1"), ModifierFlags::default());
+        assert_eq!(korean(&e), KeyRoute::ProcessKey("1".to_string()));
+    }
+
+    #[test]
+    fn test_normal_key_with_caps_lock() {
+        let mut e = event(KEY_G, Some("G"), ModifierFlags::default());
+        e.caps_lock = true;
+        assert_eq!(korean(&e), KeyRoute::ProcessKey("g".to_string()));
+    }
+
+    #[test]
+    fn test_unknown_key_flushes() {
+        let none = ModifierFlags::default();
+        assert_eq!(
+            korean(&event(0x2B, Some("\t"), none)),
+            KeyRoute::FlushAndPassToSystem
+        );
+        assert_eq!(
+            korean(&event(0x3A, None, none)),
+            KeyRoute::FlushAndPassToSystem
+        );
+    }
+
+    // ── 키 레이블 ──
+
+    #[test]
+    fn test_key_label() {
+        assert_eq!(key_label("g", false, false).as_deref(), Some("g"));
+        assert_eq!(key_label("G", false, true).as_deref(), Some("G"));
+        assert_eq!(key_label("1", false, false).as_deref(), Some("1"));
+        assert_eq!(key_label(";", false, false).as_deref(), Some(";"));
+        assert_eq!(key_label("=", false, false).as_deref(), Some("="));
+    }
+
+    #[test]
+    fn test_key_label_caps_lock() {
+        // CapsLock ON → OS가 대문자를 보내지만 한글 모드에서는 소문자로
+        assert_eq!(key_label("G", true, false).as_deref(), Some("g"));
+        // CapsLock + Shift → OS가 소문자를 보내지만 Shift 우선으로 대문자
+        assert_eq!(key_label("g", true, true).as_deref(), Some("G"));
+        // 문자가 아니면 그대로
+        assert_eq!(key_label("!", true, true).as_deref(), Some("!"));
+    }
+
+    #[test]
+    fn test_key_label_rejects_non_ascii() {
+        assert_eq!(key_label("\u{1234}", false, false), None);
+        assert_eq!(key_label("", false, false), None);
+        assert_eq!(key_label(" ", false, false), None);
+        assert_eq!(key_label("\r", false, false), None);
+    }
+}
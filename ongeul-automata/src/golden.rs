@@ -0,0 +1,149 @@
+//! 골든 테스트 벡터 형식
+//!
+//! 한 줄에 벡터 하나: `<키 입력열> -> <확정 텍스트>|<조합 중 텍스트>`.
+//! 키 입력열은 [`keyseq`](crate::keyseq) 표기이고 앞뒤 공백은 무시하므로 공백 키는 `{space}`로 쓴다.
+//! 기대값은 처음부터 입력한 뒤의 문서(확정 텍스트)와 조합 중 텍스트이며, 마지막 `|`로 나눈다.
+//! 조합 중 텍스트가 없으면 `|` 뒤를 비우거나 `|`를 생략한다. (확정 텍스트에 `|`가 있으면 생략할 수 없다)
+//!
+//! `@layout <ID>` 줄이 이후 벡터의 자판을 정한다. `#`로 시작하는 줄과 빈 줄은 무시한다.
+//!
+//! ```text
+//! @layout 2-standard
+//! # 종성이 다음 음절 초성으로 넘어간다
+//! dkssud          -> 안|녕
+//! dkssud{bs}{bs}  -> 안|ㄴ
+//! rk{flush}       -> 가|
+//! ```
+
+use crate::keyseq::{self, Document, KeyToken};
+use crate::{HangulEngine, InputMode};
+
+/// 테스트 벡터 하나
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Vector {
+    /// 파일에서의 줄 번호 (1부터)
+    pub line: usize,
+    pub layout: String,
+    pub keys: Vec<KeyToken>,
+    pub committed: String,
+    pub composing: Option<String>,
+}
+
+/// 벡터 파일을 읽는다. 잘못된 줄이 있으면 줄 번호와 함께 에러.
+pub fn parse(text: &str) -> Result<Vec<Vector>, String> {
+    let mut layout: Option<String> = None;
+    let mut vectors = Vec::new();
+    for (index, raw) in text.lines().enumerate() {
+        let line = index + 1;
+        let trimmed = raw.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if let Some(id) = trimmed.strip_prefix("@layout") {
+            let id = id.trim();
+            if id.is_empty() {
+                return Err(format!("line {line}: missing layout ID"));
+            }
+            layout = Some(id.to_string());
+            continue;
+        }
+        let layout = layout
+            .clone()
+            .ok_or_else(|| format!("line {line}: vector before @layout"))?;
+        let (keys, expected) = raw
+            .split_once(" -> ")
+            .ok_or_else(|| format!("line {line}: expected '<keys> -> <committed>|<composing>'"))?;
+        let keys = keyseq::parse(keys.trim()).map_err(|e| format!("line {line}: {e}"))?;
+        let (committed, composing) = match expected.rsplit_once('|') {
+            Some((committed, composing)) => (committed, composing),
+            None => (expected, ""),
+        };
+        vectors.push(Vector {
+            line,
+            layout,
+            keys,
+            committed: committed.to_string(),
+            composing: (!composing.is_empty()).then(|| composing.to_string()),
+        });
+    }
+    Ok(vectors)
+}
+
+/// 벡터 한 줄 표기 (`keys`를 입력한 결과 `doc`를 기대값으로)
+pub fn format_line(keys: &[KeyToken], doc: &Document) -> String {
+    format!(
+        "{} -> {}|{}",
+        keyseq::format(keys),
+        doc.text,
+        doc.composing.as_deref().unwrap_or_default()
+    )
+}
+
+/// 새 엔진(한글 모드)에 키 입력열을 입력한 결과
+pub fn run(keys: &[KeyToken], layout_json: &str) -> Result<Document, String> {
+    let engine = HangulEngine::new();
+    engine
+        .load_layout(layout_json.to_string())
+        .map_err(|e| e.to_string())?;
+    engine.set_mode(InputMode::Korean);
+    let mut doc = Document::default();
+    for token in keys {
+        doc.input(&engine, token);
+    }
+    Ok(doc)
+}
+
+impl Vector {
+    /// 벡터를 실행해 기대값과 비교한다. 다르면 기대값과 실제 결과를 설명하는 에러.
+    pub fn check(&self, layout_json: &str) -> Result<(), String> {
+        let doc = run(&self.keys, layout_json)?;
+        if doc.text == self.committed && doc.composing == self.composing {
+            return Ok(());
+        }
+        Err(format!(
+            "{}: expected {:?}|{:?}, got {:?}|{:?}",
+            keyseq::format(&self.keys),
+            self.committed,
+            self.composing.as_deref().unwrap_or_default(),
+            doc.text,
+            doc.composing.as_deref().unwrap_or_default()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_vectors() {
+        let text = "\
+# 주석
+@layout 2-standard
+
+dkssud        -> 안|녕
+rk{flush}     -> 가|
+{space}a|b -> a|b|
+r -> ㄱ
+";
+        let vectors = parse(text).unwrap();
+        assert_eq!(vectors.len(), 4);
+        assert_eq!(vectors[0].line, 4);
+        assert_eq!(vectors[0].layout, "2-standard");
+        assert_eq!(vectors[0].committed, "안");
+        assert_eq!(vectors[0].composing.as_deref(), Some("녕"));
+        assert_eq!(vectors[1].composing, None);
+        // 마지막 |로 나눈다
+        assert_eq!(vectors[2].keys[0], KeyToken::Key(" ".into()));
+        assert_eq!(vectors[2].committed, "a|b");
+        assert_eq!(vectors[3].committed, "ㄱ");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("dkssud -> 안|녕").is_err());
+        assert!(parse("@layout\n").is_err());
+        assert!(parse("@layout 2-standard\ndkssud 안녕").is_err());
+        assert!(parse("@layout 2-standard\n{nope} -> |").is_err());
+    }
+}
@@ -0,0 +1,138 @@
+//! 한자 사전 로딩 및 후보 조회
+//!
+//! 사전 파일 형식 (libhangul `hanja.txt` 호환): 한 줄에 `읽기:한자:뜻`.
+//! 빈 줄과 `#`으로 시작하는 줄은 무시한다. 같은 읽기의 후보는 파일에 나온 순서를 유지한다.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+/// 한자 사전 항목
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HanjaEntry {
+    /// 한자 (예: "韓國")
+    pub hanja: String,
+    /// 한글 읽기 (예: "한국")
+    pub reading: String,
+    /// 뜻 (없으면 빈 문자열)
+    pub meaning: String,
+}
+
+/// 읽기 → 한자 후보 사전
+#[derive(Debug, Clone, Default)]
+pub struct HanjaDictionary {
+    entries: HashMap<String, Vec<HanjaEntry>>,
+}
+
+impl HanjaDictionary {
+    /// 사전 텍스트를 파싱한다.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut entries: HashMap<String, Vec<HanjaEntry>> = HashMap::new();
+        for (idx, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut fields = line.splitn(3, ':');
+            let (Some(reading), Some(hanja)) = (fields.next(), fields.next()) else {
+                return Err(format!("Invalid dictionary line {}: {line}", idx + 1));
+            };
+            let (reading, hanja) = (reading.trim(), hanja.trim());
+            if reading.is_empty() || hanja.is_empty() {
+                return Err(format!("Invalid dictionary line {}: {line}", idx + 1));
+            }
+            entries
+                .entry(reading.to_string())
+                .or_default()
+                .push(HanjaEntry {
+                    hanja: hanja.to_string(),
+                    reading: reading.to_string(),
+                    meaning: fields.next().unwrap_or("").trim().to_string(),
+                });
+        }
+        Ok(HanjaDictionary { entries })
+    }
+
+    /// 사전 파일을 읽어 파싱한다.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Cannot read dictionary {}: {e}", path.display()))?;
+        Self::parse(&text)
+    }
+
+    /// 읽기에 해당하는 후보 목록 (없으면 빈 슬라이스)
+    pub fn lookup(&self, reading: &str) -> &[HanjaEntry] {
+        self.entries.get(reading).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// 후보가 있는 가장 긴 접미사를 찾는다.
+    /// 확정된 단어("오늘대한민국")에서 변환 가능한 부분("대한민국")을 고를 때 사용.
+    pub fn longest_suffix<'a>(&self, text: &'a str) -> Option<&'a str> {
+        text.char_indices()
+            .map(|(i, _)| &text[i..])
+            .find(|suffix| self.entries.contains_key(*suffix))
+    }
+
+    /// 등록된 읽기 수
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+# 테스트 사전
+한:韓:나라 이름 한
+한:漢:한수 한
+국:國:나라 국
+한국:韓國:대한민국의 약칭
+대한민국:大韓民國
+";
+
+    #[test]
+    fn test_parse_and_lookup() {
+        let dict = HanjaDictionary::parse(SAMPLE).unwrap();
+        assert_eq!(dict.len(), 4);
+        let han = dict.lookup("한");
+        assert_eq!(han.len(), 2);
+        assert_eq!(han[0].hanja, "韓");
+        assert_eq!(han[0].meaning, "나라 이름 한");
+        assert_eq!(han[1].hanja, "漢");
+        assert!(dict.lookup("없음").is_empty());
+    }
+
+    #[test]
+    fn test_missing_meaning_is_empty() {
+        let dict = HanjaDictionary::parse(SAMPLE).unwrap();
+        let entry = &dict.lookup("대한민국")[0];
+        assert_eq!(entry.hanja, "大韓民國");
+        assert_eq!(entry.meaning, "");
+    }
+
+    #[test]
+    fn test_meaning_may_contain_colon() {
+        let dict = HanjaDictionary::parse("시:時:때 시: 시간").unwrap();
+        assert_eq!(dict.lookup("시")[0].meaning, "때 시: 시간");
+    }
+
+    #[test]
+    fn test_invalid_line() {
+        let err = HanjaDictionary::parse("한:韓\n잘못된줄").unwrap_err();
+        assert!(err.contains("line 2"));
+        assert!(HanjaDictionary::parse(":韓").is_err());
+    }
+
+    #[test]
+    fn test_longest_suffix() {
+        let dict = HanjaDictionary::parse(SAMPLE).unwrap();
+        assert_eq!(dict.longest_suffix("오늘대한민국"), Some("대한민국"));
+        assert_eq!(dict.longest_suffix("우리한국"), Some("한국"));
+        assert_eq!(dict.longest_suffix("없음"), None);
+    }
+}
@@ -0,0 +1,147 @@
+//! 키 입력열 표기와 실행 (스크립트·골든 파일용)
+//!
+//! 키 레이블을 한 글자씩 이어 쓰고, 특수 키는 중괄호 토큰으로 쓴다.
+//! `{bs}` 백스페이스, `{flush}` 조합 확정, `{toggle}` 한/영 전환, `{space}` 공백. 여는 중괄호 자체는 `{{`.
+//! 예: 두벌식 `dkssud{bs}{flush}` → "안녀"
+
+use std::fmt;
+
+use crate::{HangulEngine, ProcessResult};
+
+/// 입력열의 키 하나
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyToken {
+    /// 키 레이블 (`process_key`로 보낸다)
+    Key(String),
+    Backspace,
+    Flush,
+    ToggleMode,
+}
+
+impl KeyToken {
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "bs" | "backspace" => Some(KeyToken::Backspace),
+            "flush" => Some(KeyToken::Flush),
+            "toggle" => Some(KeyToken::ToggleMode),
+            "space" => Some(KeyToken::Key(" ".to_string())),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for KeyToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyToken::Key(key) if key == " " => f.write_str("{space}"),
+            KeyToken::Key(key) => f.write_str(&key.replace('{', "{{")),
+            KeyToken::Backspace => f.write_str("{bs}"),
+            KeyToken::Flush => f.write_str("{flush}"),
+            KeyToken::ToggleMode => f.write_str("{toggle}"),
+        }
+    }
+}
+
+/// 입력열을 토큰으로 나눈다. 알 수 없거나 닫히지 않은 토큰은 에러.
+pub fn parse(text: &str) -> Result<Vec<KeyToken>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '{' {
+            tokens.push(KeyToken::Key(ch.to_string()));
+            continue;
+        }
+        let rest = chars.as_str();
+        if let Some(after) = rest.strip_prefix('{') {
+            tokens.push(KeyToken::Key("{".to_string()));
+            chars = after.chars();
+            continue;
+        }
+        let (name, after) = rest
+            .split_once('}')
+            .ok_or_else(|| format!("Unclosed token: {{{rest}"))?;
+        let token =
+            KeyToken::from_name(name).ok_or_else(|| format!("Unknown token: {{{name}}}"))?;
+        tokens.push(token);
+        chars = after.chars();
+    }
+    Ok(tokens)
+}
+
+/// 토큰열을 입력열 표기로 되돌린다.
+pub fn format(tokens: &[KeyToken]) -> String {
+    tokens.iter().map(KeyToken::to_string).collect()
+}
+
+/// 키 입력 결과를 반영한 문서: 확정 텍스트와 조합 중 텍스트
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Document {
+    pub text: String,
+    pub composing: Option<String>,
+}
+
+impl Document {
+    /// 토큰 하나를 엔진에 보내고 결과를 문서에 반영한다.
+    /// 엔진이 처리하지 않은 키는 시스템처럼 키 레이블을 넣거나(일반 키) 앞 글자를 지운다(백스페이스).
+    pub fn input(&mut self, engine: &HangulEngine, token: &KeyToken) -> ProcessResult {
+        let result = match token {
+            KeyToken::Key(key) => engine.process_key(key.clone()),
+            KeyToken::Backspace => engine.backspace(),
+            KeyToken::Flush => engine.flush(),
+            KeyToken::ToggleMode => engine.toggle_mode(),
+        };
+        self.apply(&result);
+        if !result.handled {
+            match token {
+                KeyToken::Key(key) => self.text.push_str(key),
+                KeyToken::Backspace => {
+                    self.text.pop();
+                }
+                KeyToken::Flush | KeyToken::ToggleMode => {}
+            }
+        }
+        result
+    }
+
+    /// 엔진 결과를 반영한다: 앞 글자를 지우고, 확정 텍스트를 붙이고, 조합 중 텍스트를 바꾼다.
+    pub fn apply(&mut self, result: &ProcessResult) {
+        for _ in 0..result.delete_before {
+            self.text.pop();
+        }
+        if let Some(committed) = &result.committed {
+            self.text.push_str(committed);
+        }
+        self.composing = result.composing.clone();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tokens() {
+        let tokens = parse("r{bs}{FLUSH}{toggle}{{a{space}").unwrap();
+        assert_eq!(
+            tokens,
+            [
+                KeyToken::Key("r".into()),
+                KeyToken::Backspace,
+                KeyToken::Flush,
+                KeyToken::ToggleMode,
+                KeyToken::Key("{".into()),
+                KeyToken::Key("a".into()),
+                KeyToken::Key(" ".into()),
+            ]
+        );
+        assert_eq!(format(&tokens), "r{bs}{flush}{toggle}{{a{space}");
+        // 닫는 중괄호는 그대로 키
+        assert_eq!(parse("}").unwrap(), [KeyToken::Key("}".into())]);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("{bs").is_err());
+        assert!(parse("{enter}").is_err());
+    }
+}
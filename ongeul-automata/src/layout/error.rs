@@ -0,0 +1,143 @@
+//! 레이아웃 로드 에러 (UniFFI → Swift 전달용)
+//!
+//! 레이아웃 편집기가 문제 위치를 표시할 수 있도록 종류별로 나누고,
+//! 알 수 있으면 원본의 줄·열(1부터)이나 JSON 경로(`keymap["q"].choseong`, `combinations[2].first`)를 담는다.
+//! 줄·열을 알 수 없으면 None이다.
+
+use serde::Deserialize;
+use serde::de::{IgnoredAny, IntoDeserializer, value};
+
+use super::schema::{LayoutSchema, LayoutType};
+
+#[derive(uniffi::Error, Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum LayoutError {
+    /// JSON5 문법 오류
+    #[error("JSON5 parse error{}: {message}", at(line, column))]
+    Parse {
+        line: Option<u32>,
+        column: Option<u32>,
+        message: String,
+    },
+    /// XML 문법 오류 (libhangul 자판 가져오기)
+    #[error("XML parse error{}: {message}", at(line, column))]
+    Xml {
+        line: Option<u32>,
+        column: Option<u32>,
+        message: String,
+    },
+    /// 알 수 없는 자판 타입 (`type` 값)
+    #[error("Unknown layout type '{value}'{}", at(line, column))]
+    UnknownType {
+        value: String,
+        line: Option<u32>,
+        column: Option<u32>,
+    },
+    /// 16진수 코드포인트가 아니거나 유효한 문자가 아닌 값
+    #[error("Invalid hex at {path}: {value}")]
+    InvalidHex { path: String, value: String },
+    /// 스키마 위반 (필수 필드 누락, 값 타입 오류, 알 수 없는 상태 이름 등).
+    /// 경로를 알 수 없으면 `path`가 빈 문자열.
+    #[error("{}", schema_message(path, line, column, message))]
+    Schema {
+        path: String,
+        line: Option<u32>,
+        column: Option<u32>,
+        message: String,
+    },
+    /// `extends`의 부모 레이아웃을 찾을 수 없음
+    #[error("Unknown parent layout '{id}'")]
+    UnknownParent { id: String },
+    /// `extends` 순환 (처음과 끝이 같은 ID 목록)
+    #[error("Layout inheritance cycle: {}", ids.join(" → "))]
+    Cycle { ids: Vec<String> },
+    /// 부모 레이아웃 자체를 읽을 수 없음
+    #[error("In parent layout '{id}': {message}")]
+    Parent { id: String, message: String },
+    /// 문법은 맞지만 요청한 용도에 맞지 않는 자판 (한글 배열 자리에 latin 배열 등)
+    #[error("Layout '{id}' {message}")]
+    WrongType { id: String, message: String },
+    /// 동봉 레이아웃에도 등록한 레이아웃에도 없는 ID
+    #[error("Unknown layout '{id}'")]
+    UnknownLayout { id: String },
+    /// 이미 있는 레이아웃과 ID가 같음
+    #[error("Duplicate layout id '{id}'")]
+    Duplicate { id: String },
+}
+
+/// 메시지에 붙일 위치 (" at 줄:열", 모르면 빈 문자열)
+fn at(line: &Option<u32>, column: &Option<u32>) -> String {
+    match (line, column) {
+        (Some(line), Some(column)) => format!(" at {line}:{column}"),
+        _ => String::new(),
+    }
+}
+
+fn schema_message(path: &str, line: &Option<u32>, column: &Option<u32>, message: &str) -> String {
+    let mut text = format!("Invalid layout{}", at(line, column));
+    if !path.is_empty() {
+        text.push_str(&format!(" in {path}"));
+    }
+    format!("{text}: {message}")
+}
+
+/// json5 에러의 (줄, 열, 메시지)
+fn split_json5_error(error: json5::Error) -> (Option<(u32, u32)>, String) {
+    let json5::Error::Message { msg, location } = error;
+    let location = location.map(|l| (l.line as u32, l.column as u32));
+    // pest 문법 에러는 소스 발췌가 붙은 여러 줄: 마지막 `= ...` 줄이 설명
+    let message = match msg.lines().last() {
+        Some(last) if msg.contains('\n') => last.trim().trim_start_matches("= ").to_string(),
+        _ => msg,
+    };
+    (location, message)
+}
+
+/// `type` 값만 읽는다. (나머지 필드는 무시)
+#[derive(Deserialize)]
+struct TypeProbe<T> {
+    #[serde(rename = "type")]
+    layout_type: Option<T>,
+}
+
+/// JSON5 원본을 스키마로 읽는다. 에러는 문법, 자판 타입, 스키마 순으로 구분한다.
+/// 원본은 한 번만 읽고, 실패했을 때만 에러 종류를 가리려고 다시 읽는다.
+pub(super) fn parse_schema(json: &str) -> Result<LayoutSchema, LayoutError> {
+    json5::from_str(json).map_err(|e| classify(json, e))
+}
+
+/// 스키마로 읽지 못한 원본의 에러 종류를 가린다.
+fn classify(json: &str, error: json5::Error) -> LayoutError {
+    if let Err(e) = json5::from_str::<IgnoredAny>(json) {
+        let (location, message) = split_json5_error(e);
+        return LayoutError::Parse {
+            line: location.map(|(line, _)| line),
+            column: location.map(|(_, column)| column),
+            message,
+        };
+    }
+    if let Ok(TypeProbe {
+        layout_type: Some(value),
+    }) = json5::from_str::<TypeProbe<String>>(json)
+        && LayoutType::deserialize(IntoDeserializer::<value::Error>::into_deserializer(
+            value.as_str(),
+        ))
+        .is_err()
+    {
+        // 스키마 에러는 다른 필드를 가리킬 수 있으므로 `type`만 읽어 위치를 얻는다
+        let location = json5::from_str::<TypeProbe<LayoutType>>(json)
+            .err()
+            .and_then(|e| split_json5_error(e).0);
+        return LayoutError::UnknownType {
+            value,
+            line: location.map(|(line, _)| line),
+            column: location.map(|(_, column)| column),
+        };
+    }
+    let (location, message) = split_json5_error(error);
+    LayoutError::Schema {
+        path: String::new(),
+        line: location.map(|(line, _)| line),
+        column: location.map(|(_, column)| column),
+        message,
+    }
+}
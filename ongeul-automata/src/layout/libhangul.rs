@@ -0,0 +1,629 @@
+//! libhangul 자판 XML(`hangul-keyboard-*.xml`) 가져오기·내보내기
+//!
+//! ibus-hangul, nabi 등이 쓰는 형식이다. 키 매핑(`map`)과 조합 규칙(`combination`)은 id 0만 쓰고,
+//! `<include file="..."/>`는 포함 디렉터리에서 차례로 찾는다.
+//! libhangul 두벌식(`jamo`)은 위치 자모로 적으므로 호환 자모로 바꾼다. 두벌식 오토마타는 초성을
+//! 조합하지 않으므로 초성 조합 규칙은 가져오지 않고, 내보낼 때 자음 조합은 종성 조합으로 쓴다.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use roxmltree::{Document, Node};
+
+use super::resolve::to_json5;
+use super::schema::{CombinationEntry, KeymapValue, LayoutSchema, LayoutType};
+use super::{KeyboardLayout, LayoutError, parse_hex_char};
+use crate::unicode;
+
+/// `include` 중첩 한도 (서로 포함하는 파일 방지)
+const MAX_INCLUDE_DEPTH: usize = 8;
+
+/// libhangul `type` 값 → 자판 타입. (`jamo-yet`, `romaja`는 오토마타가 달라 가져오지 않는다)
+fn layout_type(value: &str) -> Option<LayoutType> {
+    match value {
+        "jamo" => Some(LayoutType::Jamo),
+        "jaso" => Some(LayoutType::Jaso),
+        "jaso-yet" => Some(LayoutType::OldHangul),
+        _ => None,
+    }
+}
+
+/// 자판 타입 → libhangul `type` 값
+fn xml_type(layout_type: LayoutType) -> Option<&'static str> {
+    match layout_type {
+        LayoutType::Jamo => Some("jamo"),
+        LayoutType::Jaso => Some("jaso"),
+        LayoutType::OldHangul => Some("jaso-yet"),
+        LayoutType::ShinJaso | LayoutType::Romaja | LayoutType::Latin => None,
+    }
+}
+
+/// 코드 값: libhangul처럼 `0x`로 시작하면 16진수, 아니면 10진수
+fn parse_code(value: &str) -> Option<char> {
+    let code = match value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+    {
+        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+        None => value.parse().ok()?,
+    };
+    char::from_u32(code)
+}
+
+fn hex(ch: char) -> String {
+    format!("0x{:04X}", ch as u32)
+}
+
+/// 위치 자모를 호환 자모로 (위치 자모가 아니면 그대로)
+fn to_compat(ch: char) -> char {
+    let code = ch as u32;
+    let compat = if unicode::is_choseong(ch) {
+        unicode::choseong_to_compat(code - unicode::L_BASE)
+    } else if unicode::is_jungseong(ch) {
+        unicode::jungseong_to_compat(code - unicode::V_BASE)
+    } else if unicode::is_jongseong(ch) {
+        unicode::jongseong_to_compat(code - unicode::T_BASE)
+    } else {
+        None
+    };
+    compat.unwrap_or(ch)
+}
+
+/// 호환 자모 자음은 초성, 모음은 중성 위치 자모로 (호환 자모가 아니면 그대로)
+fn to_conjoining(ch: char) -> char {
+    let code = if unicode::is_compat_vowel(ch) {
+        unicode::compat_to_jungseong(ch).map(|v| unicode::V_BASE + v)
+    } else {
+        unicode::compat_to_choseong(ch).map(|l| unicode::L_BASE + l)
+    };
+    code.and_then(char::from_u32).unwrap_or(ch)
+}
+
+/// 두벌식 조합 규칙 (위치 자모) → 호환 자모. 중성끼리, 종성끼리의 조합만 옮긴다.
+fn compat_combination(first: char, second: char, result: char) -> Option<(char, char, char)> {
+    let all = |f: fn(char) -> bool| f(first) && f(second) && f(result);
+    (all(unicode::is_jungseong) || all(unicode::is_jongseong))
+        .then(|| (to_compat(first), to_compat(second), to_compat(result)))
+}
+
+/// 두벌식 조합 규칙 (호환 자모) → 위치 자모. 모음은 중성, 자음은 종성 조합으로 쓴다.
+fn conjoining_combination(first: char, second: char, result: char) -> Option<(char, char, char)> {
+    let convert = |ch: char| {
+        let code = if unicode::is_compat_vowel(ch) {
+            unicode::V_BASE + unicode::compat_to_jungseong(ch)?
+        } else {
+            unicode::T_BASE + unicode::compat_to_jongseong(ch)?
+        };
+        char::from_u32(code)
+    };
+    let triple = (convert(first)?, convert(second)?, convert(result)?);
+    let vowels = [first, second, result]
+        .iter()
+        .filter(|&&ch| unicode::is_compat_vowel(ch))
+        .count();
+    // 모음과 자음이 섞인 규칙은 옮길 수 없다
+    matches!(vowels, 0 | 3).then_some(triple)
+}
+
+/// 가져오는 중인 자판
+#[derive(Default)]
+struct Collected {
+    id: Option<String>,
+    layout_type: Option<LayoutType>,
+    /// (`xml:lang`, 이름)
+    names: Vec<(Option<String>, String)>,
+    keymap: Vec<(String, char)>,
+    combinations: Vec<(char, char, char)>,
+}
+
+/// XML 문서 하나를 읽는 중인 상태 (에러 위치 계산용)
+struct Reader<'a, 'input> {
+    doc: &'a Document<'input>,
+    include_dirs: &'a [PathBuf],
+    depth: usize,
+}
+
+impl<'a, 'input> Reader<'a, 'input> {
+    fn error(&self, node: Node, path: String, message: impl Into<String>) -> LayoutError {
+        let pos = self.doc.text_pos_at(node.range().start);
+        LayoutError::Schema {
+            path,
+            line: Some(pos.row),
+            column: Some(pos.col),
+            message: message.into(),
+        }
+    }
+
+    fn attribute(
+        &self,
+        node: Node<'a, 'input>,
+        path: &str,
+        name: &str,
+    ) -> Result<&'a str, LayoutError> {
+        node.attribute(name).ok_or_else(|| {
+            self.error(
+                node,
+                path.to_string(),
+                format!("missing attribute `{name}`"),
+            )
+        })
+    }
+
+    fn code(&self, node: Node<'a, 'input>, path: &str, name: &str) -> Result<char, LayoutError> {
+        let value = self.attribute(node, path, name)?;
+        parse_code(value).ok_or_else(|| LayoutError::InvalidHex {
+            path: format!("{path}.{name}"),
+            value: value.to_string(),
+        })
+    }
+
+    /// 루트 또는 포함 파일의 자식 요소를 읽는다.
+    fn read_children(
+        &self,
+        parent: Node<'a, 'input>,
+        out: &mut Collected,
+    ) -> Result<(), LayoutError> {
+        for node in parent.children().filter(Node::is_element) {
+            match node.tag_name().name() {
+                "name" => {
+                    let lang = node
+                        .attribute((roxmltree::NS_XML_URI, "lang"))
+                        .map(str::to_string);
+                    let text = node.text().unwrap_or_default().trim().to_string();
+                    out.names.push((lang, text));
+                }
+                "map" if node.attribute("id").is_none_or(|id| id == "0") => {
+                    self.read_map(node, out)?
+                }
+                "combination" if node.attribute("id").is_none_or(|id| id == "0") => {
+                    self.read_combination(node, out)?
+                }
+                "include" => self.read_include(node, out)?,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn read_map(&self, map: Node<'a, 'input>, out: &mut Collected) -> Result<(), LayoutError> {
+        for (i, item) in map
+            .children()
+            .filter(|n| n.has_tag_name("item"))
+            .enumerate()
+        {
+            let path = format!("map[0].item[{i}]");
+            let key = self.code(item, &path, "key")?;
+            if !key.is_ascii_graphic() {
+                return Err(self.error(
+                    item,
+                    path,
+                    format!("key {} is not a printable ASCII character", hex(key)),
+                ));
+            }
+            let value = self.code(item, &path, "value")?;
+            out.keymap.push((key.to_string(), value));
+        }
+        Ok(())
+    }
+
+    fn read_combination(
+        &self,
+        combination: Node<'a, 'input>,
+        out: &mut Collected,
+    ) -> Result<(), LayoutError> {
+        for (i, item) in combination
+            .children()
+            .filter(|n| n.has_tag_name("item"))
+            .enumerate()
+        {
+            let path = format!("combination[0].item[{i}]");
+            out.combinations.push((
+                self.code(item, &path, "first")?,
+                self.code(item, &path, "second")?,
+                self.code(item, &path, "result")?,
+            ));
+        }
+        Ok(())
+    }
+
+    fn read_include(
+        &self,
+        include: Node<'a, 'input>,
+        out: &mut Collected,
+    ) -> Result<(), LayoutError> {
+        let file = self.attribute(include, "include", "file")?;
+        let path = format!("include[\"{file}\"]");
+        if self.depth >= MAX_INCLUDE_DEPTH {
+            return Err(self.error(include, path, "includes nested too deeply"));
+        }
+        // 파일 이름만 허용한다 (포함 디렉터리 밖을 읽지 않도록)
+        let source = (!file.contains(['/', '\\']) && !file.starts_with('.'))
+            .then(|| {
+                self.include_dirs
+                    .iter()
+                    .find_map(|dir| std::fs::read_to_string(dir.join(file)).ok())
+            })
+            .flatten()
+            .ok_or_else(|| self.error(include, path.clone(), format!("cannot find '{file}'")))?;
+        read_document(&source, self.include_dirs, self.depth + 1, out)
+            .map_err(|e| self.error(include, path, e.to_string()))
+    }
+}
+
+fn read_document(
+    xml: &str,
+    include_dirs: &[PathBuf],
+    depth: usize,
+    out: &mut Collected,
+) -> Result<(), LayoutError> {
+    let doc = Document::parse(xml).map_err(|e| {
+        let pos = e.pos();
+        LayoutError::Xml {
+            line: Some(pos.row),
+            column: Some(pos.col),
+            message: e.to_string(),
+        }
+    })?;
+    let reader = Reader {
+        doc: &doc,
+        include_dirs,
+        depth,
+    };
+    let root = doc.root_element();
+    if root.has_tag_name("hangul-keyboard") && depth == 0 {
+        out.id = root.attribute("id").map(str::to_string);
+        let value = reader.attribute(root, "type", "type")?;
+        out.layout_type = Some(layout_type(value).ok_or_else(|| {
+            reader.error(
+                root,
+                "type".to_string(),
+                format!("unsupported libhangul keyboard type '{value}'"),
+            )
+        })?);
+    }
+    reader.read_children(root, out)
+}
+
+/// libhangul 자판 XML을 레이아웃 스키마로 가져온다. `include`는 `include_dirs`에서 차례로 찾는다.
+/// 이름은 `xml:lang="ko"`인 것, 없으면 언어 표시가 없는 것을 쓴다.
+pub fn import(xml: &str, include_dirs: &[PathBuf]) -> Result<LayoutSchema, LayoutError> {
+    let mut collected = Collected::default();
+    read_document(xml, include_dirs, 0, &mut collected)?;
+    let Collected {
+        id,
+        layout_type,
+        names,
+        keymap,
+        combinations,
+    } = collected;
+    let id = id.ok_or_else(|| super::resolve::missing_field("id"))?;
+    let name = ["ko", ""]
+        .iter()
+        .find_map(|lang| {
+            names
+                .iter()
+                .find(|(l, _)| l.as_deref().unwrap_or_default() == *lang)
+        })
+        .or(names.first())
+        .map_or_else(|| id.clone(), |(_, name)| name.clone());
+
+    let jamo = layout_type == Some(LayoutType::Jamo);
+    let keymap = keymap
+        .into_iter()
+        .map(|(key, value)| {
+            let value = if jamo { to_compat(value) } else { value };
+            (key, Some(KeymapValue::Code(hex(value))))
+        })
+        .collect::<HashMap<_, _>>();
+    let combinations = combinations
+        .into_iter()
+        .filter_map(|(first, second, result)| {
+            if jamo {
+                compat_combination(first, second, result)
+            } else {
+                Some((first, second, result))
+            }
+        })
+        .map(|(first, second, result)| CombinationEntry {
+            first: hex(first),
+            second: hex(second),
+            result: Some(hex(result)),
+        })
+        .collect();
+
+    let schema = LayoutSchema {
+        id,
+        extends: None,
+        name: Some(name),
+        description: None,
+        experimental: None,
+        layout_type,
+        keymap,
+        combinations,
+        options: None,
+    };
+    KeyboardLayout::from_schema(schema.clone())?;
+    Ok(schema)
+}
+
+/// libhangul 자판 XML을 `extends` 없는 JSON5 레이아웃으로 가져온다.
+pub fn import_json5(xml: &str, include_dirs: &[PathBuf]) -> Result<String, LayoutError> {
+    Ok(to_json5(&import(xml, include_dirs)?))
+}
+
+/// XML 속성 값 이스케이프
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn export_error(path: String, message: impl Into<String>) -> LayoutError {
+    LayoutError::Schema {
+        path,
+        line: None,
+        column: None,
+        message: message.into(),
+    }
+}
+
+/// 상속을 푼 레이아웃 스키마를 libhangul 자판 XML로 내보낸다.
+/// 키는 한 글자 ASCII, 값은 상태와 무관해야 한다. (신세벌식·로마자·latin 배열은 내보낼 수 없다)
+pub fn export(schema: &LayoutSchema) -> Result<String, LayoutError> {
+    // 잘못된 값은 내보내기 전에 보고한다
+    let layout = KeyboardLayout::from_schema(schema.clone())?;
+    let type_value = xml_type(layout.layout_type).ok_or_else(|| {
+        export_error(
+            "type".to_string(),
+            format!(
+                "'{}' layouts cannot be exported to libhangul",
+                layout.layout_type.as_str()
+            ),
+        )
+    })?;
+    let jamo = layout.layout_type == LayoutType::Jamo;
+
+    let mut keys = Vec::new();
+    for (key, value) in &schema.keymap {
+        let Some(value) = value else {
+            continue;
+        };
+        let path = format!("keymap[\"{key}\"]");
+        let code = match (key.chars().next(), key.chars().count()) {
+            (Some(ch), 1) if ch.is_ascii_graphic() => ch,
+            _ => {
+                return Err(export_error(
+                    path,
+                    "libhangul keys must be single printable ASCII characters",
+                ));
+            }
+        };
+        let KeymapValue::Code(value) = value else {
+            return Err(export_error(
+                path,
+                "state-dependent values cannot be exported to libhangul",
+            ));
+        };
+        let Some(ch) = parse_hex_char(value) else {
+            return Err(LayoutError::InvalidHex {
+                path,
+                value: value.clone(),
+            });
+        };
+        keys.push((code, if jamo { to_conjoining(ch) } else { ch }));
+    }
+    keys.sort();
+
+    let mut combinations = Vec::new();
+    for (i, entry) in schema.combinations.iter().enumerate() {
+        let path = format!("combinations[{i}]");
+        let Some(result) = &entry.result else {
+            continue;
+        };
+        let [first, second, result] =
+            [&entry.first, &entry.second, result].map(|v| parse_hex_char(v));
+        let (Some(first), Some(second), Some(result)) = (first, second, result) else {
+            return Err(export_error(path, "invalid hex"));
+        };
+        let triple = if jamo {
+            conjoining_combination(first, second, result).ok_or_else(|| {
+                export_error(path, "has no libhangul jungseong or jongseong equivalent")
+            })?
+        } else {
+            (first, second, result)
+        };
+        combinations.push(triple);
+    }
+
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        out,
+        "<hangul-keyboard id=\"{}\" type=\"{type_value}\">",
+        escape(&layout.id)
+    );
+    let _ = writeln!(out, "    <name>{}</name>", escape(&layout.name));
+    out.push_str("    <map id=\"0\">\n");
+    for (key, value) in keys {
+        let _ = writeln!(
+            out,
+            "        <item key=\"0x{:02x}\" value=\"0x{:04x}\"/>",
+            key as u32, value as u32
+        );
+    }
+    out.push_str("    </map>\n");
+    out.push_str("    <combination id=\"0\">\n");
+    for (first, second, result) in combinations {
+        let _ = writeln!(
+            out,
+            "        <item first=\"0x{:04x}\" second=\"0x{:04x}\" result=\"0x{:04x}\"/>",
+            first as u32, second as u32, result as u32
+        );
+    }
+    out.push_str("    </combination>\n");
+    out.push_str("</hangul-keyboard>\n");
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::LayoutResolver;
+
+    const LAYOUTS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/layouts");
+
+    /// libhangul 두벌식 형식 (위치 자모, 종성 조합, 초성 조합 포함)
+    const DUBEOLSIK: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<hangul-keyboard id="2" type="jamo">
+    <name>Dubeolsik</name>
+    <name xml:lang="ko">두벌식</name>
+    <map id="0">
+        <item key="0x72" value="0x1100"/>
+        <item key="0x74" value="0x1109"/>
+        <item key="0x6b" value="0x1161"/>
+        <item key="0x68" value="0x1169"/>
+        <item key="0x21" value="33"/>
+    </map>
+    <map id="1">
+        <item key="0x72" value="0x3131"/>
+    </map>
+    <combination id="0">
+        <item first="0x1100" second="0x1100" result="0x1101"/>
+        <item first="0x1169" second="0x1161" result="0x116a"/>
+        <item first="0x11a8" second="0x11ba" result="0x11aa"/>
+    </combination>
+</hangul-keyboard>
+"#;
+
+    #[test]
+    fn test_import_jamo() {
+        let layout = KeyboardLayout::from_schema(import(DUBEOLSIK, &[]).unwrap()).unwrap();
+        assert_eq!(layout.id, "2");
+        assert_eq!(layout.name, "두벌식");
+        assert_eq!(layout.layout_type, LayoutType::Jamo);
+        // 위치 자모는 호환 자모로, id 0이 아닌 map은 무시
+        assert_eq!(layout.map_key("r"), Some('ㄱ'));
+        assert_eq!(layout.map_key("k"), Some('ㅏ'));
+        assert_eq!(layout.map_key("!"), Some('!'));
+        // 초성 조합은 버리고 중성·종성 조합은 호환 자모로
+        assert_eq!(layout.combinations.len(), 2);
+        assert_eq!(layout.combine('ㅗ', 'ㅏ'), Some('ㅘ'));
+        assert_eq!(layout.combine('ㄱ', 'ㅅ'), Some('ㄳ'));
+        assert_eq!(layout.combine('ㄱ', 'ㄱ'), None);
+    }
+
+    #[test]
+    fn test_import_include() {
+        let dir = std::env::temp_dir().join(format!("ongeul-libhangul-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("hangul-combination-test.xml"),
+            r#"<hangul-combination>
+    <combination id="0">
+        <item first="0x1100" second="0x1100" result="0x1101"/>
+    </combination>
+</hangul-combination>"#,
+        )
+        .unwrap();
+        let xml = r#"<hangul-keyboard id="3t" type="jaso">
+    <name>Test</name>
+    <map id="0"><item key="0x6b" value="0x1100"/></map>
+    <include file="hangul-combination-test.xml"/>
+</hangul-keyboard>"#;
+        let layout =
+            KeyboardLayout::from_schema(import(xml, std::slice::from_ref(&dir)).unwrap()).unwrap();
+        assert_eq!(layout.name, "Test");
+        assert_eq!(layout.combine('\u{1100}', '\u{1100}'), Some('\u{1101}'));
+        // 포함 파일을 찾을 수 없음
+        assert!(matches!(
+            import(xml, &[]),
+            Err(LayoutError::Schema { path, line: Some(4), .. })
+                if path == "include[\"hangul-combination-test.xml\"]"
+        ));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_import_json5_escapes_name() {
+        // 이름의 줄바꿈·탭·따옴표도 JSON5로 내보낸 뒤 그대로 로드된다
+        let xml = r#"<hangul-keyboard id="x" type="jamo">
+    <name>두&#10;"벌"&#9;식</name>
+    <map id="0"><item key="0x72" value="0x1100"/></map>
+</hangul-keyboard>"#;
+        let layout = KeyboardLayout::from_json(&import_json5(xml, &[]).unwrap()).unwrap();
+        assert_eq!(layout.name, "두\n\"벌\"\t식");
+    }
+
+    #[test]
+    fn test_import_errors() {
+        assert!(matches!(
+            import("<hangul-keyboard id=\"x\" type=\"jamo\">", &[]),
+            Err(LayoutError::Xml { line: Some(1), .. })
+        ));
+        assert!(matches!(
+            import("<hangul-keyboard id=\"ro\" type=\"romaja\"/>", &[]),
+            Err(LayoutError::Schema { path, .. }) if path == "type"
+        ));
+        let xml = r#"<hangul-keyboard id="x" type="jaso">
+    <map id="0"><item key="0x6b" value="0xzz"/></map>
+</hangul-keyboard>"#;
+        assert_eq!(
+            import(xml, &[]).unwrap_err(),
+            LayoutError::InvalidHex {
+                path: "map[0].item[0].value".into(),
+                value: "0xzz".into()
+            }
+        );
+        let xml = r#"<hangul-keyboard id="x" type="jaso">
+    <map id="0"><item key="0x20" value="0x1100"/></map>
+</hangul-keyboard>"#;
+        assert!(matches!(
+            import(xml, &[]),
+            Err(LayoutError::Schema { path, line: Some(2), .. }) if path == "map[0].item[0]"
+        ));
+    }
+
+    #[test]
+    fn test_export_round_trip() {
+        // 동봉 한글 배열은 내보냈다 가져와도 같은 레이아웃이다
+        for id in ["2-standard", "3-390", "3-final", "3-390-old"] {
+            let json = std::fs::read_to_string(format!("{LAYOUTS_DIR}/{id}.json5")).unwrap();
+            let schema = LayoutResolver::default().resolve(&json).unwrap();
+            let original = KeyboardLayout::from_schema(schema.clone()).unwrap();
+            let xml = export(&schema).unwrap();
+            let layout = KeyboardLayout::from_schema(import(&xml, &[]).unwrap()).unwrap();
+            assert_eq!(layout.id, original.id);
+            assert_eq!(layout.name, original.name, "{id}");
+            assert_eq!(layout.layout_type, original.layout_type, "{id}");
+            assert_eq!(layout.keymap, original.keymap, "{id}");
+            assert_eq!(layout.combinations, original.combinations, "{id}");
+        }
+    }
+
+    #[test]
+    fn test_export_jamo_uses_conjoining() {
+        let json = std::fs::read_to_string(format!("{LAYOUTS_DIR}/2-standard.json5")).unwrap();
+        let xml = export(&LayoutResolver::default().resolve(&json).unwrap()).unwrap();
+        assert!(xml.contains(r#"<item key="0x72" value="0x1100"/>"#));
+        assert!(xml.contains(r#"<item first="0x1169" second="0x1161" result="0x116a"/>"#));
+        assert!(xml.contains(r#"<item first="0x11a8" second="0x11ba" result="0x11aa"/>"#));
+    }
+
+    #[test]
+    fn test_export_unsupported() {
+        for (id, path) in [
+            ("3-shin", "type"),
+            ("romaja", "type"),
+            ("en-dvorak", "type"),
+        ] {
+            let json = std::fs::read_to_string(format!("{LAYOUTS_DIR}/{id}.json5")).unwrap();
+            let schema = LayoutResolver::default().resolve(&json).unwrap();
+            assert!(
+                matches!(export(&schema), Err(LayoutError::Schema { path: p, .. }) if p == path),
+                "{id}"
+            );
+        }
+    }
+}
@@ -1,20 +1,52 @@
 /// 자판 레이아웃 로딩 및 키맵/조합 조회
+mod error;
+pub mod libhangul;
+pub mod physical;
+pub mod registry;
+mod resolve;
 pub mod schema;
+pub mod validate;
 
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
-use schema::{LayoutSchema, LayoutType};
+pub use error::LayoutError;
+pub use resolve::LayoutResolver;
+use schema::{KeymapValue, LayoutOptions, LayoutSchema, LayoutType};
+
+use crate::automata::AutomataState;
 
 /// 파싱된 자판 레이아웃
 #[derive(Debug, Clone)]
 pub struct KeyboardLayout {
     pub id: String,
     pub name: String,
+    /// 한 줄 설명 (없으면 빈 문자열)
+    pub description: String,
+    /// 검증되지 않은 실험용 레이아웃
+    pub experimental: bool,
     pub layout_type: LayoutType,
-    /// 키 레이블 → 자모 char 매핑
+    /// 키 레이블 → 자모 char 매핑 (상태와 무관한 기본값)
     keymap: HashMap<String, char>,
+    /// 키 레이블 → 오토마타 상태별 대안 (신세벌식 갈마들이 등)
+    contextual: HashMap<String, Vec<(AutomataState, char)>>,
     /// (첫째 자모, 둘째 자모) → 결합 결과
     combinations: HashMap<(char, char), char>,
+    /// 레이아웃 옵션
+    pub options: LayoutOptions,
+}
+
+/// keymap 상태 이름을 오토마타 상태로 변환
+fn parse_state(name: &str) -> Option<AutomataState> {
+    match name {
+        "empty" => Some(AutomataState::Empty),
+        "choseong" => Some(AutomataState::Choseong),
+        "jungseong" => Some(AutomataState::Jungseong),
+        "jungseong2" => Some(AutomataState::Jungseong2),
+        "jongseong" => Some(AutomataState::Jongseong),
+        "jongseong2" => Some(AutomataState::Jongseong2),
+        _ => None,
+    }
 }
 
 /// 16진수 문자열("0x3131" 등)을 char로 변환
@@ -26,34 +58,79 @@ fn parse_hex_char(s: &str) -> Option<char> {
 
 impl KeyboardLayout {
     /// JSON5 문자열에서 레이아웃을 파싱한다.
-    pub fn from_json(json: &str) -> Result<Self, String> {
-        let schema: LayoutSchema =
-            json5::from_str(json).map_err(|e| format!("JSON5 parse error: {e}"))?;
+    /// `extends`의 부모는 동봉 레이아웃에서 찾는다. ([`LayoutResolver::default`])
+    /// 동봉되지 않은 부모는 [`LayoutError::UnknownParent`]이므로 디렉터리에서 찾으려면 [`LayoutResolver::new`]를 쓴다.
+    pub fn from_json(json: &str) -> Result<Self, LayoutError> {
+        LayoutResolver::default().load(json)
+    }
+
+    /// 상속을 푼 스키마에서 레이아웃을 만든다.
+    fn from_schema(schema: LayoutSchema) -> Result<Self, LayoutError> {
+        let hex = |path: String, value: &str| {
+            parse_hex_char(value).ok_or_else(|| LayoutError::InvalidHex {
+                path,
+                value: value.to_string(),
+            })
+        };
 
         let mut keymap = HashMap::with_capacity(schema.keymap.len());
-        for (key, hex) in &schema.keymap {
-            let ch = parse_hex_char(hex)
-                .ok_or_else(|| format!("Invalid hex in keymap: {key} → {hex}"))?;
-            keymap.insert(key.clone(), ch);
+        let mut contextual = HashMap::new();
+        for (key, value) in &schema.keymap {
+            let path = format!("keymap[{key:?}]");
+            let Some(value) = value else {
+                continue;
+            };
+            match value {
+                KeymapValue::Code(value) => {
+                    keymap.insert(key.clone(), hex(path, value)?);
+                }
+                KeymapValue::Contextual(alternatives) => {
+                    let mut by_state = Vec::with_capacity(alternatives.len());
+                    for (state_name, value) in alternatives {
+                        let path = format!("{path}.{state_name}");
+                        let ch = hex(path.clone(), value)?;
+                        if state_name == "default" {
+                            keymap.insert(key.clone(), ch);
+                        } else {
+                            let state =
+                                parse_state(state_name).ok_or_else(|| LayoutError::Schema {
+                                    path,
+                                    line: None,
+                                    column: None,
+                                    message: format!("unknown state '{state_name}'"),
+                                })?;
+                            by_state.push((state, ch));
+                        }
+                    }
+                    contextual.insert(key.clone(), by_state);
+                }
+            }
         }
 
         let mut combinations = HashMap::with_capacity(schema.combinations.len());
-        for entry in &schema.combinations {
-            let first = parse_hex_char(&entry.first)
-                .ok_or_else(|| format!("Invalid hex in combination first: {}", entry.first))?;
-            let second = parse_hex_char(&entry.second)
-                .ok_or_else(|| format!("Invalid hex in combination second: {}", entry.second))?;
-            let result = parse_hex_char(&entry.result)
-                .ok_or_else(|| format!("Invalid hex in combination result: {}", entry.result))?;
+        for (i, entry) in schema.combinations.iter().enumerate() {
+            let path = |field: &str| format!("combinations[{i}].{field}");
+            let first = hex(path("first"), &entry.first)?;
+            let second = hex(path("second"), &entry.second)?;
+            let Some(result) = &entry.result else {
+                continue;
+            };
+            let result = hex(path("result"), result)?;
             combinations.insert((first, second), result);
         }
 
         Ok(KeyboardLayout {
             id: schema.id,
-            name: schema.name,
-            layout_type: schema.layout_type,
+            name: schema.name.ok_or_else(|| resolve::missing_field("name"))?,
+            description: schema.description.unwrap_or_default(),
+            experimental: schema.experimental.unwrap_or_default(),
+            layout_type: schema
+                .layout_type
+                .ok_or_else(|| resolve::missing_field("type"))?,
             keymap,
+            contextual,
             combinations,
+            options: schema.options.unwrap_or_default().into(),
         })
     }
 
@@ -74,10 +151,151 @@ impl KeyboardLayout {
         None
     }
 
+    /// 오토마타 상태에 따라 키 레이블로 자모를 조회.
+    /// 상태별 대안이 없으면 [`map_key`](Self::map_key)와 같다. Shift 폴백 규칙도 동일.
+    pub fn map_key_in_state(&self, key: &str, state: AutomataState) -> Option<char> {
+        let lookup = |k: &str| {
+            self.contextual
+                .get(k)
+                .and_then(|alts| alts.iter().find(|(s, _)| *s == state))
+                .map(|&(_, ch)| ch)
+        };
+        if let Some(ch) = lookup(key) {
+            return Some(ch);
+        }
+        if !self.keymap.contains_key(key) && key.len() == 1 {
+            let b = key.as_bytes()[0];
+            if b.is_ascii_uppercase()
+                && let Some(ch) = lookup(&((b | 0x20) as char).to_string())
+            {
+                return Some(ch);
+            }
+        }
+        self.map_key(key)
+    }
+
+    /// 상태별 대안이 있는 키인지 (Shift 폴백 규칙은 [`map_key`](Self::map_key)와 동일)
+    pub fn is_contextual(&self, key: &str) -> bool {
+        if self.contextual.contains_key(key) {
+            return true;
+        }
+        !self.keymap.contains_key(key)
+            && key.len() == 1
+            && key.as_bytes()[0].is_ascii_uppercase()
+            && self.contextual.contains_key(&key.to_ascii_lowercase())
+    }
+
+    /// 여러 글자 키 조회: `input` 앞부분과 일치하는 가장 긴 키를 찾아 (키 길이(바이트), 자모)를 반환.
+    /// 로마자 배열처럼 "ng", "eo" 같은 여러 글자 키를 쓰는 레이아웃용.
+    pub fn longest_key_match(&self, input: &str) -> Option<(usize, char)> {
+        self.keymap
+            .iter()
+            .filter(|(key, _)| !key.is_empty() && input.starts_with(key.as_str()))
+            .max_by_key(|(key, _)| key.len())
+            .map(|(key, &ch)| (key.len(), ch))
+    }
+
+    /// `input`으로 시작하는 키가 있는지 (여러 글자 키를 입력하는 중인지 판단)
+    pub fn is_key_prefix(&self, input: &str) -> bool {
+        self.keymap.keys().any(|key| key.starts_with(input))
+    }
+
+    /// 역 키맵: 어느 상태에서든 `ch`를 내는 키 레이블 목록.
+    /// 상태와 무관한 키를 먼저, 그다음 짧은 키, 같은 길이는 사전 순으로 정렬한다.
+    pub fn keys_for(&self, ch: char) -> Vec<&str> {
+        let mut keys: Vec<&str> = self
+            .keymap
+            .iter()
+            .filter(|&(_, &c)| c == ch)
+            .map(|(key, _)| key.as_str())
+            .chain(
+                self.contextual
+                    .iter()
+                    .filter(|(_, alts)| alts.iter().any(|&(_, c)| c == ch))
+                    .map(|(key, _)| key.as_str()),
+            )
+            .collect();
+        keys.sort_by_key(|key| (self.contextual.contains_key(*key), key.len(), *key));
+        keys.dedup();
+        keys
+    }
+
+    /// 영문(latin) 배열에서 키 레이블을 변환.
+    /// Shift 매핑이 없으면 소문자 매핑 결과를 대문자로 바꾼다. (자모 폴백과 달리 대소문자 보존)
+    pub fn map_latin_key(&self, key: &str) -> Option<char> {
+        if let Some(&ch) = self.keymap.get(key) {
+            return Some(ch);
+        }
+        if key.len() == 1 {
+            let b = key.as_bytes()[0];
+            if b.is_ascii_uppercase() {
+                let lower = (b | 0x20) as char;
+                return self
+                    .keymap
+                    .get(&lower.to_string())
+                    .map(|ch| ch.to_ascii_uppercase());
+            }
+        }
+        None
+    }
+
+    /// 물리 키(USB HID usage)로 자모를 조회.
+    /// 시스템 영문 배열과 무관하게 US QWERTY 위치 기준 keymap을 적용한다.
+    pub fn map_physical_key(&self, usage: u32, shift: bool) -> Option<char> {
+        let key = physical::from_usage(usage)?;
+        self.map_key(key.label(shift))
+    }
+
     /// 두 자모의 조합 결과를 조회
     pub fn combine(&self, first: char, second: char) -> Option<char> {
         self.combinations.get(&(first, second)).copied()
     }
+
+    /// 조합 결과를 만드는 (첫째 자모, 둘째 자모)를 역조회
+    pub fn split(&self, combined: char) -> Option<(char, char)> {
+        self.combinations
+            .iter()
+            .filter(|&(_, &result)| result == combined)
+            .map(|(&pair, _)| pair)
+            .min()
+    }
+}
+
+/// 레이아웃 디렉터리에서 읽은 파일 하나
+#[derive(Debug)]
+pub struct LayoutFile {
+    pub path: PathBuf,
+    /// 로드할 JSON5 (`extends`가 있으면 같은 디렉터리의 부모로 상속을 푼 결과, 풀 수 없으면 원본)
+    pub json: String,
+    /// 파싱 결과
+    pub layout: Result<KeyboardLayout, LayoutError>,
+}
+
+/// 디렉터리의 레이아웃 파일(`*.json5`)을 파일 이름 순으로 읽는다.
+/// 읽을 수 없는 파일은 건너뛰고, 파싱에 실패한 파일은 에러와 함께 돌려준다.
+pub fn read_dir(dir: &Path) -> Result<Vec<LayoutFile>, String> {
+    let resolver = LayoutResolver::new(vec![dir.to_path_buf()]);
+    let entries = std::fs::read_dir(dir).map_err(|e| format!("{}: {e}", dir.display()))?;
+    let mut paths: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json5"))
+        .collect();
+    paths.sort();
+    Ok(paths
+        .into_iter()
+        .filter_map(|path| {
+            let source = std::fs::read_to_string(&path).ok()?;
+            let (json, layout) = match resolver.expand(&source) {
+                Ok(json) => {
+                    let layout = KeyboardLayout::from_json(&json);
+                    (json, layout)
+                }
+                Err(e) => (source, Err(e)),
+            };
+            Some(LayoutFile { path, json, layout })
+        })
+        .collect())
 }
 
 #[cfg(test)]
@@ -114,6 +332,145 @@ mod tests {
         assert_eq!(layout.map_key("z"), None);
     }
 
+    #[test]
+    fn test_map_physical_key() {
+        let layout = KeyboardLayout::from_json(MINIMAL_JAMO_JSON).unwrap();
+        // HID 0x14 = Q 위치, 0x0E = K 위치
+        assert_eq!(layout.map_physical_key(0x14, false), Some('ㅂ'));
+        assert_eq!(layout.map_physical_key(0x0E, false), Some('ㅏ'));
+        // Shift 매핑이 없으면 소문자로 폴백
+        assert_eq!(layout.map_physical_key(0x14, true), Some('ㅂ'));
+        // 알 수 없는 usage
+        assert_eq!(layout.map_physical_key(0xFFFF, false), None);
+    }
+
+    #[test]
+    fn test_map_latin_key() {
+        let json = r#"{
+            id: "test-latin", name: "test", type: "latin",
+            keymap: { "s": "0x0072", "S": "0x0052", "k": "0x0065" },
+        }"#;
+        let layout = KeyboardLayout::from_json(json).unwrap();
+        assert_eq!(layout.layout_type, LayoutType::Latin);
+        assert_eq!(layout.map_latin_key("s"), Some('r'));
+        assert_eq!(layout.map_latin_key("S"), Some('R'));
+        // Shift 매핑 없음 → 소문자 매핑을 대문자로
+        assert_eq!(layout.map_latin_key("K"), Some('E'));
+        assert_eq!(layout.map_latin_key("a"), None);
+    }
+
+    #[test]
+    fn test_map_key_in_state() {
+        let json = r#"{
+            id: "test-shin", name: "test", type: "shin-jaso",
+            keymap: {
+                "q": { default: "0x11BA", choseong: "0x1168" },  // ㅅ종 / 초성 뒤 ㅖ
+                "k": "0x1100",
+                "x": { choseong: "0x1169" },  // 초성 뒤에서만 ㅗ
+            },
+        }"#;
+        let layout = KeyboardLayout::from_json(json).unwrap();
+        assert_eq!(layout.layout_type, LayoutType::ShinJaso);
+        assert_eq!(layout.map_key("q"), Some('\u{11BA}'));
+        assert_eq!(
+            layout.map_key_in_state("q", AutomataState::Empty),
+            Some('\u{11BA}')
+        );
+        assert_eq!(
+            layout.map_key_in_state("q", AutomataState::Choseong),
+            Some('\u{1168}')
+        );
+        // 대문자 → 소문자 대안으로 폴백
+        assert_eq!(
+            layout.map_key_in_state("Q", AutomataState::Choseong),
+            Some('\u{1168}')
+        );
+        // 상태 대안이 없는 일반 키
+        assert_eq!(
+            layout.map_key_in_state("k", AutomataState::Choseong),
+            Some('\u{1100}')
+        );
+        // 기본값 없는 상태 대안
+        assert_eq!(layout.map_key_in_state("x", AutomataState::Empty), None);
+        assert_eq!(
+            layout.map_key_in_state("x", AutomataState::Choseong),
+            Some('\u{1169}')
+        );
+        assert!(layout.is_contextual("q"));
+        assert!(layout.is_contextual("Q"));
+        assert!(!layout.is_contextual("k"));
+    }
+
+    #[test]
+    fn test_contextual_keymap_errors() {
+        let json = r#"{
+            id: "test", name: "test", type: "shin-jaso",
+            keymap: { "q": { default: "0x11BA", sometimes: "0x1168" } },
+        }"#;
+        assert_eq!(
+            KeyboardLayout::from_json(json).unwrap_err(),
+            LayoutError::Schema {
+                path: "keymap[\"q\"].sometimes".to_string(),
+                line: None,
+                column: None,
+                message: "unknown state 'sometimes'".to_string(),
+            }
+        );
+
+        let json = r#"{
+            id: "test", name: "test", type: "shin-jaso",
+            keymap: { "q": { choseong: "bad" } },
+        }"#;
+        assert_eq!(
+            KeyboardLayout::from_json(json).unwrap_err(),
+            LayoutError::InvalidHex {
+                path: "keymap[\"q\"].choseong".to_string(),
+                value: "bad".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_longest_key_match() {
+        let json = r#"{
+            id: "test-romaja", name: "test", type: "romaja",
+            keymap: { "n": "0x3134", "ng": "0x3147", "e": "0x3154", "eo": "0x3153" },
+            options: { final_only: ["ng"] },
+        }"#;
+        let layout = KeyboardLayout::from_json(json).unwrap();
+        assert_eq!(layout.layout_type, LayoutType::Romaja);
+        assert_eq!(layout.longest_key_match("ngu"), Some((2, 'ㅇ')));
+        assert_eq!(layout.longest_key_match("nu"), Some((1, 'ㄴ')));
+        assert_eq!(layout.longest_key_match("eon"), Some((2, 'ㅓ')));
+        assert_eq!(layout.longest_key_match("x"), None);
+        assert!(layout.is_key_prefix("n"));
+        assert!(!layout.is_key_prefix("x"));
+        assert_eq!(layout.options.final_only, vec!["ng".to_string()]);
+    }
+
+    #[test]
+    fn test_split() {
+        let layout = KeyboardLayout::from_json(MINIMAL_JAMO_JSON).unwrap();
+        assert_eq!(layout.split('ㅘ'), Some(('ㅗ', 'ㅏ')));
+        assert_eq!(layout.split('ㅏ'), None);
+    }
+
+    #[test]
+    fn test_keys_for() {
+        let json = r#"{
+            id: "test-shin", name: "test", type: "shin-jaso",
+            keymap: {
+                "/": "0x1169",  // ㅗ
+                "v": { default: "0x11B8", choseong: "0x1169" },  // ㅂ종 / 초성 뒤 ㅗ
+                "V": "0x1169",
+            },
+        }"#;
+        let layout = KeyboardLayout::from_json(json).unwrap();
+        assert_eq!(layout.keys_for('\u{1169}'), vec!["/", "V", "v"]);
+        assert_eq!(layout.keys_for('\u{11B8}'), vec!["v"]);
+        assert!(layout.keys_for('\u{1100}').is_empty());
+    }
+
     #[test]
     fn test_combine() {
         let layout = KeyboardLayout::from_json(MINIMAL_JAMO_JSON).unwrap();
@@ -160,9 +517,20 @@ mod tests {
             keymap: { "a": "not_hex" },
             combinations: [],
         }"#;
-        let result = KeyboardLayout::from_json(json);
-        assert!(result.is_err());
-        assert!(result.unwrap_err().contains("Invalid hex"));
+        assert_eq!(
+            KeyboardLayout::from_json(json).unwrap_err(),
+            LayoutError::InvalidHex {
+                path: "keymap[\"a\"]".to_string(),
+                value: "not_hex".to_string(),
+            }
+        );
+    }
+
+    fn invalid_hex_path(result: Result<KeyboardLayout, LayoutError>) -> String {
+        match result {
+            Err(LayoutError::InvalidHex { path, .. }) => path,
+            other => panic!("expected InvalidHex, got {other:?}"),
+        }
     }
 
     #[test]
@@ -173,10 +541,9 @@ mod tests {
             keymap: {},
             combinations: [{ first: "bad", second: "0x3131", result: "0x3132" }],
         }"#;
-        assert!(
-            KeyboardLayout::from_json(json)
-                .unwrap_err()
-                .contains("Invalid hex")
+        assert_eq!(
+            invalid_hex_path(KeyboardLayout::from_json(json)),
+            "combinations[0].first"
         );
 
         // second가 잘못된 hex
@@ -185,10 +552,9 @@ mod tests {
             keymap: {},
             combinations: [{ first: "0x3131", second: "bad", result: "0x3132" }],
         }"#;
-        assert!(
-            KeyboardLayout::from_json(json)
-                .unwrap_err()
-                .contains("Invalid hex")
+        assert_eq!(
+            invalid_hex_path(KeyboardLayout::from_json(json)),
+            "combinations[0].second"
         );
 
         // result가 잘못된 hex
@@ -197,10 +563,9 @@ mod tests {
             keymap: {},
             combinations: [{ first: "0x3131", second: "0x3132", result: "bad" }],
         }"#;
-        assert!(
-            KeyboardLayout::from_json(json)
-                .unwrap_err()
-                .contains("Invalid hex")
+        assert_eq!(
+            invalid_hex_path(KeyboardLayout::from_json(json)),
+            "combinations[0].result"
         );
     }
 
@@ -222,7 +587,44 @@ mod tests {
             keymap: {},
             combinations: [],
         }"#;
-        assert!(KeyboardLayout::from_json(json).is_err());
+        match KeyboardLayout::from_json(json) {
+            Err(LayoutError::UnknownType { value, line, .. }) => {
+                assert_eq!(value, "unknown");
+                assert_eq!(line, Some(2));
+            }
+            other => panic!("expected UnknownType, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_error_positions() {
+        // 문법 오류: 3번째 줄의 닫히지 않은 문자열
+        let json = "{\n  id: \"test\",\n  name: \"test,\n}";
+        match KeyboardLayout::from_json(json) {
+            Err(LayoutError::Parse { line, column, .. }) => {
+                assert_eq!((line, column), (Some(3), Some(15)))
+            }
+            other => panic!("expected Parse, got {other:?}"),
+        }
+
+        // 스키마 위반: 값 타입 오류는 위치와 함께
+        let json = "{\n  id: \"test\", name: \"test\", type: \"jamo\",\n  keymap: [],\n}";
+        match KeyboardLayout::from_json(json) {
+            Err(LayoutError::Schema { line, .. }) => assert_eq!(line, Some(3)),
+            other => panic!("expected Schema, got {other:?}"),
+        }
+        let error =
+            KeyboardLayout::from_json(r#"{ name: "test", type: "jamo", keymap: {} }"#).unwrap_err();
+        assert!(matches!(error, LayoutError::Schema { .. }));
+        assert!(error.to_string().contains("missing field `id`"), "{error}");
+
+        // 위치를 모르면 메시지에 위치를 붙이지 않는다
+        let error = LayoutError::Parse {
+            line: None,
+            column: None,
+            message: "EOF".into(),
+        };
+        assert_eq!(error.to_string(), "JSON5 parse error: EOF");
     }
 
     #[test]
@@ -253,4 +655,21 @@ mod tests {
         let layout = KeyboardLayout::from_json(json).unwrap();
         assert_eq!(layout.map_key("a"), Some('ㄴ'));
     }
+
+    #[test]
+    fn test_read_bundled_layouts() {
+        let dir = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/layouts"));
+        let files = read_dir(dir).unwrap();
+        assert!(files.windows(2).all(|w| w[0].path < w[1].path));
+        // 동봉 레이아웃은 모두 파싱되고 ID가 파일 이름과 같다
+        for file in &files {
+            let layout = file.layout.as_ref().unwrap();
+            assert_eq!(
+                Some(layout.id.as_str()),
+                file.path.file_stem().and_then(|s| s.to_str())
+            );
+        }
+        assert!(files.iter().any(|f| f.path.ends_with("2-standard.json5")));
+        assert!(read_dir(&dir.join("missing")).is_err());
+    }
 }
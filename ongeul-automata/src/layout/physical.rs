@@ -0,0 +1,187 @@
+//! 물리 키 위치 → US ANSI(QWERTY) 키 레이블 변환
+//!
+//! 레이아웃 keymap은 US QWERTY 키 레이블로 정의되어 있다. 시스템 영문 배열이
+//! Dvorak/Colemak/AZERTY이면 OS가 만든 문자 레이블이 달라지므로, 플랫폼 중립적인
+//! 물리 키 식별자(USB HID Keyboard page usage 또는 W3C `KeyboardEvent.code` 이름)를
+//! QWERTY 레이블로 바꾼 뒤 keymap을 조회한다.
+
+/// 물리 키 한 개의 정의
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PhysicalKey {
+    /// USB HID usage ID (Keyboard/Keypad page 0x07)
+    pub usage: u32,
+    /// W3C UI Events `code` 이름 (예: "KeyQ")
+    pub code: &'static str,
+    /// US QWERTY에서 Shift 없이 입력되는 레이블
+    pub label: &'static str,
+    /// US QWERTY에서 Shift와 함께 입력되는 레이블
+    pub shifted_label: &'static str,
+}
+
+impl PhysicalKey {
+    /// Shift 여부에 따른 QWERTY 레이블
+    pub fn label(&self, shift: bool) -> &'static str {
+        if shift {
+            self.shifted_label
+        } else {
+            self.label
+        }
+    }
+}
+
+macro_rules! key {
+    ($usage:expr, $code:expr, $label:expr, $shifted:expr) => {
+        PhysicalKey {
+            usage: $usage,
+            code: $code,
+            label: $label,
+            shifted_label: $shifted,
+        }
+    };
+}
+
+/// 문자 입력에 쓰이는 ANSI 키 (HID usage 순)
+static PHYSICAL_KEYS: &[PhysicalKey] = &[
+    key!(0x04, "KeyA", "a", "A"),
+    key!(0x05, "KeyB", "b", "B"),
+    key!(0x06, "KeyC", "c", "C"),
+    key!(0x07, "KeyD", "d", "D"),
+    key!(0x08, "KeyE", "e", "E"),
+    key!(0x09, "KeyF", "f", "F"),
+    key!(0x0A, "KeyG", "g", "G"),
+    key!(0x0B, "KeyH", "h", "H"),
+    key!(0x0C, "KeyI", "i", "I"),
+    key!(0x0D, "KeyJ", "j", "J"),
+    key!(0x0E, "KeyK", "k", "K"),
+    key!(0x0F, "KeyL", "l", "L"),
+    key!(0x10, "KeyM", "m", "M"),
+    key!(0x11, "KeyN", "n", "N"),
+    key!(0x12, "KeyO", "o", "O"),
+    key!(0x13, "KeyP", "p", "P"),
+    key!(0x14, "KeyQ", "q", "Q"),
+    key!(0x15, "KeyR", "r", "R"),
+    key!(0x16, "KeyS", "s", "S"),
+    key!(0x17, "KeyT", "t", "T"),
+    key!(0x18, "KeyU", "u", "U"),
+    key!(0x19, "KeyV", "v", "V"),
+    key!(0x1A, "KeyW", "w", "W"),
+    key!(0x1B, "KeyX", "x", "X"),
+    key!(0x1C, "KeyY", "y", "Y"),
+    key!(0x1D, "KeyZ", "z", "Z"),
+    key!(0x1E, "Digit1", "1", "!"),
+    key!(0x1F, "Digit2", "2", "@"),
+    key!(0x20, "Digit3", "3", "#"),
+    key!(0x21, "Digit4", "4", "$"),
+    key!(0x22, "Digit5", "5", "%"),
+    key!(0x23, "Digit6", "6", "^"),
+    key!(0x24, "Digit7", "7", "&"),
+    key!(0x25, "Digit8", "8", "*"),
+    key!(0x26, "Digit9", "9", "("),
+    key!(0x27, "Digit0", "0", ")"),
+    key!(0x2C, "Space", " ", " "),
+    key!(0x2D, "Minus", "-", "_"),
+    key!(0x2E, "Equal", "=", "+"),
+    key!(0x2F, "BracketLeft", "[", "{"),
+    key!(0x30, "BracketRight", "]", "}"),
+    key!(0x31, "Backslash", "\\", "|"),
+    key!(0x33, "Semicolon", ";", ":"),
+    key!(0x34, "Quote", "'", "\""),
+    key!(0x35, "Backquote", "`", "~"),
+    key!(0x36, "Comma", ",", "<"),
+    key!(0x37, "Period", ".", ">"),
+    key!(0x38, "Slash", "/", "?"),
+];
+
+/// 문자 입력에 쓰이지 않는 키의 HID usage (키 이벤트 라우팅용)
+pub const ENTER: u32 = 0x28;
+pub const ESCAPE: u32 = 0x29;
+pub const BACKSPACE: u32 = 0x2A;
+pub const SPACE: u32 = 0x2C;
+pub const BRACKET_LEFT: u32 = 0x2F;
+pub const ARROW_RIGHT: u32 = 0x4F;
+pub const ARROW_LEFT: u32 = 0x50;
+pub const ARROW_DOWN: u32 = 0x51;
+pub const ARROW_UP: u32 = 0x52;
+pub const NUMPAD_ENTER: u32 = 0x58;
+
+/// 방향키인지
+pub fn is_arrow(usage: u32) -> bool {
+    (ARROW_RIGHT..=ARROW_UP).contains(&usage)
+}
+
+/// 키패드(텐키) 키인지. Enter(0x58)는 일반 Enter와 같이 처리하므로 제외한다.
+/// (Num Lock/Clear, / * - +, 1~9, 0, ., =)
+pub fn is_numpad(usage: u32) -> bool {
+    ((0x53..=0x63).contains(&usage) && usage != NUMPAD_ENTER) || usage == 0x67
+}
+
+/// HID usage로 물리 키를 찾는다.
+pub fn from_usage(usage: u32) -> Option<&'static PhysicalKey> {
+    PHYSICAL_KEYS.iter().find(|k| k.usage == usage)
+}
+
+/// W3C `code` 이름으로 물리 키를 찾는다.
+pub fn from_code(code: &str) -> Option<&'static PhysicalKey> {
+    PHYSICAL_KEYS.iter().find(|k| k.code == code)
+}
+
+/// QWERTY 레이블로 물리 키를 찾는다. (Shift 레이블이면 `(key, true)`)
+pub fn from_label(label: &str) -> Option<(&'static PhysicalKey, bool)> {
+    PHYSICAL_KEYS.iter().find_map(|k| {
+        if k.label == label {
+            Some((k, false))
+        } else if k.shifted_label == label {
+            Some((k, true))
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_usage() {
+        let q = from_usage(0x14).unwrap();
+        assert_eq!(q.code, "KeyQ");
+        assert_eq!(q.label(false), "q");
+        assert_eq!(q.label(true), "Q");
+        assert_eq!(from_usage(0x33).unwrap().label(true), ":");
+        // Enter(0x28)는 문자 키가 아님
+        assert!(from_usage(0x28).is_none());
+    }
+
+    #[test]
+    fn test_from_code() {
+        assert_eq!(from_code("Semicolon").unwrap().usage, 0x33);
+        assert_eq!(from_code("Digit0").unwrap().label(false), "0");
+        assert!(from_code("Enter").is_none());
+    }
+
+    #[test]
+    fn test_from_label() {
+        assert_eq!(
+            from_label("k").map(|(k, s)| (k.code, s)),
+            Some(("KeyK", false))
+        );
+        assert_eq!(
+            from_label("<").map(|(k, s)| (k.code, s)),
+            Some(("Comma", true))
+        );
+        assert!(from_label("가").is_none());
+    }
+
+    #[test]
+    fn test_table_consistency() {
+        // usage와 code, 레이블이 모두 유일해야 역조회가 결정적이다
+        for (i, a) in PHYSICAL_KEYS.iter().enumerate() {
+            for b in &PHYSICAL_KEYS[i + 1..] {
+                assert_ne!(a.usage, b.usage);
+                assert_ne!(a.code, b.code);
+                assert_ne!(a.label, b.label);
+            }
+        }
+    }
+}
@@ -0,0 +1,231 @@
+//! 레이아웃 목록: 크레이트에 포함된 동봉 레이아웃과 사용자 디렉터리의 레이아웃
+//!
+//! 동봉 레이아웃(`layouts/*.json5`)은 빌드할 때 포함되므로 프런트엔드가 파일을 찾지 않아도 된다.
+//! 사용자 레이아웃은 디렉터리 단위로 등록하며, `extends`는 같은 디렉터리와 동봉 레이아웃에서 찾는다.
+
+use std::path::{Path, PathBuf};
+
+use super::schema::LayoutType;
+use super::{KeyboardLayout, LayoutError, LayoutResolver};
+
+macro_rules! bundled {
+    ($($id:literal),* $(,)?) => {
+        &[$(($id, include_str!(concat!("../../layouts/", $id, ".json5")))),*]
+    };
+}
+
+/// 동봉 레이아웃 (ID, JSON5 원본)
+static BUILTIN: &[(&str, &str)] = bundled![
+    "2-standard",
+    "3-390",
+    "3-final",
+    "3-390-old",
+    "3-shin",
+    "romaja",
+    "en-dvorak",
+    "en-colemak",
+    "en-workman",
+];
+
+/// 레이아웃 목록 항목 (UniFFI → Swift 전달용)
+#[derive(uniffi::Record, Debug, Clone, PartialEq, Eq)]
+pub struct LayoutInfo {
+    pub id: String,
+    pub name: String,
+    pub layout_type: LayoutType,
+    /// 한 줄 설명 (없으면 빈 문자열)
+    pub description: String,
+    /// 동봉 레이아웃이면 true, 사용자 레이아웃이면 false
+    pub builtin: bool,
+    /// 실험용 레이아웃이면 true (환경설정 목록에서 뺀다)
+    pub experimental: bool,
+}
+
+impl LayoutInfo {
+    fn new(layout: &KeyboardLayout, builtin: bool) -> Self {
+        LayoutInfo {
+            id: layout.id.clone(),
+            name: layout.name.clone(),
+            layout_type: layout.layout_type,
+            description: layout.description.clone(),
+            builtin,
+            experimental: layout.experimental,
+        }
+    }
+}
+
+/// 등록하지 못한 사용자 레이아웃 파일 (UniFFI → Swift 전달용)
+#[derive(uniffi::Record, Debug, Clone, PartialEq, Eq)]
+pub struct LayoutFileError {
+    pub path: String,
+    pub error: LayoutError,
+}
+
+/// 동봉 레이아웃의 JSON5 원본
+pub fn builtin_json(id: &str) -> Option<&'static str> {
+    BUILTIN
+        .iter()
+        .find(|(builtin_id, _)| *builtin_id == id)
+        .map(|(_, json)| *json)
+}
+
+/// 동봉 레이아웃 목록 (동봉 순서)
+pub fn builtin_layouts() -> Vec<LayoutInfo> {
+    BUILTIN
+        .iter()
+        .filter_map(|(_, json)| KeyboardLayout::from_json(json).ok())
+        .map(|layout| LayoutInfo::new(&layout, true))
+        .collect()
+}
+
+/// 등록한 사용자 레이아웃
+#[derive(Debug, Clone)]
+struct UserLayout {
+    info: LayoutInfo,
+    /// 등록한 디렉터리
+    dir: PathBuf,
+    /// 로드할 JSON5 (상속을 푼 결과)
+    json: String,
+}
+
+/// 동봉 레이아웃과 등록한 사용자 레이아웃의 목록
+#[derive(Debug, Clone, Default)]
+pub struct Registry {
+    user: Vec<UserLayout>,
+}
+
+impl Registry {
+    /// 디렉터리의 레이아웃 파일(`*.json5`)을 등록한다. 같은 디렉터리를 다시 등록하면 목록을 새로 읽는다.
+    /// 동봉 레이아웃이나 다른 디렉터리의 레이아웃과 ID가 같으면 등록하지 않는다.
+    /// 등록하지 못한 파일과 그 에러를 반환한다.
+    pub fn register_dir(&mut self, dir: &Path) -> Result<Vec<LayoutFileError>, String> {
+        let files = super::read_dir(dir)?;
+        self.user.retain(|layout| layout.dir != dir);
+        let mut failed = Vec::new();
+        for file in files {
+            let layout = file.layout.and_then(|layout| {
+                if builtin_json(&layout.id).is_some()
+                    || self.user.iter().any(|user| user.info.id == layout.id)
+                {
+                    return Err(LayoutError::Duplicate { id: layout.id });
+                }
+                Ok(layout)
+            });
+            match layout {
+                Ok(layout) => self.user.push(UserLayout {
+                    info: LayoutInfo::new(&layout, false),
+                    dir: dir.to_path_buf(),
+                    json: file.json,
+                }),
+                Err(error) => failed.push(LayoutFileError {
+                    path: file.path.display().to_string(),
+                    error,
+                }),
+            }
+        }
+        Ok(failed)
+    }
+
+    /// 동봉 레이아웃, 그다음 사용자 레이아웃(등록 순)
+    pub fn list(&self) -> Vec<LayoutInfo> {
+        let mut layouts = builtin_layouts();
+        layouts.extend(self.user.iter().map(|layout| layout.info.clone()));
+        layouts
+    }
+
+    /// 로드할 JSON5 (`extends`는 풀어 둔 것)
+    pub fn json(&self, id: &str) -> Option<String> {
+        if let Some(json) = builtin_json(id) {
+            return Some(json.to_string());
+        }
+        self.user
+            .iter()
+            .find(|layout| layout.info.id == id)
+            .map(|layout| layout.json.clone())
+    }
+
+    /// ID로 레이아웃을 로드한다.
+    pub fn load(&self, id: &str) -> Result<KeyboardLayout, LayoutError> {
+        let json = self
+            .json(id)
+            .ok_or_else(|| LayoutError::UnknownLayout { id: id.to_string() })?;
+        LayoutResolver::default().load(&json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_matches_layouts_dir() {
+        // layouts/의 모든 파일이 동봉되어 있고 내용이 같다
+        let dir = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/layouts"));
+        let files = super::super::read_dir(dir).unwrap();
+        assert_eq!(files.len(), BUILTIN.len());
+        for file in files {
+            let layout = file.layout.unwrap();
+            assert_eq!(builtin_json(&layout.id), Some(file.json.as_str()));
+        }
+
+        let layouts = builtin_layouts();
+        assert_eq!(layouts.len(), BUILTIN.len());
+        assert_eq!(layouts[0].id, "2-standard");
+        assert_eq!(layouts[0].layout_type, LayoutType::Jamo);
+        assert!(
+            layouts
+                .iter()
+                .all(|l| l.builtin && !l.description.is_empty())
+        );
+        // 실험용 배열은 신세벌식 시험 배열뿐이다
+        let experimental: Vec<&str> = layouts
+            .iter()
+            .filter(|l| l.experimental)
+            .map(|l| l.id.as_str())
+            .collect();
+        assert_eq!(experimental, ["3-shin"]);
+        assert_eq!(builtin_json("nope"), None);
+    }
+
+    #[test]
+    fn test_register_dir() {
+        let dir = std::env::temp_dir().join(format!("ongeul-registry-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let write = |name: &str, json: &str| std::fs::write(dir.join(name), json).unwrap();
+        // 동봉 레이아웃을 상속한 사용자 레이아웃
+        write(
+            "mine.json5",
+            r#"{ id: "mine", extends: "2-standard", name: "내 두벌식", keymap: { "q": "0x3141" } }"#,
+        );
+        // 동봉 레이아웃과 같은 ID, 잘못된 파일
+        write("copy.json5", r#"{ id: "3-390", extends: "3-final" }"#);
+        write("broken.json5", "{ id: ");
+
+        let mut registry = Registry::default();
+        let mut failed = registry.register_dir(&dir).unwrap();
+        failed.sort_by(|a, b| a.path.cmp(&b.path));
+        assert_eq!(failed.len(), 2);
+        assert!(matches!(failed[0].error, LayoutError::Parse { .. }));
+        assert!(matches!(&failed[1].error, LayoutError::Duplicate { id } if id == "3-390"));
+
+        let list = registry.list();
+        assert_eq!(list.len(), BUILTIN.len() + 1);
+        let mine = list.last().unwrap();
+        assert_eq!((mine.id.as_str(), mine.builtin), ("mine", false));
+        assert_eq!(mine.description, builtin_layouts()[0].description);
+        assert_eq!(registry.load("mine").unwrap().map_key("q"), Some('ㅁ'));
+        assert_eq!(registry.load("3-final").unwrap().id, "3-final");
+        assert_eq!(
+            registry.load("nope").unwrap_err(),
+            LayoutError::UnknownLayout { id: "nope".into() }
+        );
+
+        // 다시 등록하면 새로 읽는다
+        std::fs::remove_file(dir.join("mine.json5")).unwrap();
+        registry.register_dir(&dir).unwrap();
+        assert_eq!(registry.list().len(), BUILTIN.len());
+        assert!(registry.register_dir(&dir.join("missing")).is_err());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
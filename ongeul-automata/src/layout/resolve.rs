@@ -0,0 +1,513 @@
+//! 레이아웃 상속 (`extends`) 풀기와 평탄화
+//!
+//! 부모 레이아웃은 검색 디렉터리에서 `<ID>.json5` 파일로 찾고, 없으면 동봉 레이아웃에서 찾는다. (앞 디렉터리가 우선)
+//! 상속을 푼 결과는 `extends`가 없는 JSON5로 내보낼 수 있다. ([`LayoutResolver::flatten`])
+
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use super::error::parse_schema;
+use super::registry::builtin_json;
+use super::schema::{CombinationEntry, KeymapValue, LayoutOptions, LayoutSchema};
+use super::{KeyboardLayout, LayoutError, parse_hex_char, physical};
+use crate::{quote, unicode};
+
+/// `extends`를 푸는 부모 레이아웃 검색기
+#[derive(Debug, Clone, Default)]
+pub struct LayoutResolver {
+    dirs: Vec<PathBuf>,
+}
+
+pub(super) fn missing_field(field: &str) -> LayoutError {
+    LayoutError::Schema {
+        path: field.to_string(),
+        line: None,
+        column: None,
+        message: format!("missing field `{field}`"),
+    }
+}
+
+/// 상속을 푼 스키마의 필수 필드를 확인한다.
+fn required(schema: &LayoutSchema) -> Result<(), LayoutError> {
+    if schema.name.is_none() {
+        return Err(missing_field("name"));
+    }
+    if schema.layout_type.is_none() {
+        return Err(missing_field("type"));
+    }
+    Ok(())
+}
+
+/// 조합 항목의 (first, second) 쌍. 16진수가 잘못되면 원문으로 비교한다.
+fn combination_pair(entry: &CombinationEntry) -> (Result<char, &str>, Result<char, &str>) {
+    (
+        parse_hex_char(&entry.first).ok_or(entry.first.as_str()),
+        parse_hex_char(&entry.second).ok_or(entry.second.as_str()),
+    )
+}
+
+/// 부모 스키마(상속을 푼 것) 위에 자식 스키마를 덮어쓴다.
+fn merge(parent: LayoutSchema, child: LayoutSchema) -> Result<LayoutSchema, LayoutError> {
+    if let (Some(parent_type), Some(child_type)) = (&parent.layout_type, &child.layout_type)
+        && parent_type != child_type
+    {
+        return Err(LayoutError::Schema {
+            path: "type".to_string(),
+            line: None,
+            column: None,
+            message: format!(
+                "type differs from parent layout '{}'",
+                child.extends.unwrap_or_default()
+            ),
+        });
+    }
+
+    let mut keymap = parent.keymap;
+    for (key, value) in child.keymap {
+        match value {
+            Some(value) => {
+                keymap.insert(key, Some(value));
+            }
+            None => {
+                keymap.remove(&key);
+            }
+        }
+    }
+
+    let mut combinations = parent.combinations;
+    for entry in child.combinations {
+        let pair = combination_pair(&entry);
+        let existing = combinations
+            .iter()
+            .position(|e| combination_pair(e) == pair);
+        match (existing, entry.result.is_some()) {
+            (Some(i), true) => combinations[i] = entry,
+            (Some(i), false) => {
+                combinations.remove(i);
+            }
+            (None, true) => combinations.push(entry),
+            (None, false) => {}
+        }
+    }
+
+    Ok(LayoutSchema {
+        id: child.id,
+        extends: None,
+        name: child.name.or(parent.name),
+        description: child.description.or(parent.description),
+        experimental: child.experimental.or(parent.experimental),
+        layout_type: child.layout_type.or(parent.layout_type),
+        keymap,
+        combinations,
+        options: match (child.options, parent.options) {
+            (Some(child), Some(parent)) => Some(child.merge(parent)),
+            (child, parent) => child.or(parent),
+        },
+    })
+}
+
+impl LayoutResolver {
+    /// 부모 레이아웃을 `dirs`에서 차례로, 그다음 동봉 레이아웃에서 찾는 검색기
+    pub fn new(dirs: Vec<PathBuf>) -> Self {
+        LayoutResolver { dirs }
+    }
+
+    /// 부모 레이아웃의 JSON5 원본
+    fn source(&self, id: &str) -> Result<String, LayoutError> {
+        // ID가 경로가 되지 않도록 파일 이름에 쓸 수 있는 글자만 허용한다
+        let valid = !id.is_empty()
+            && !id.starts_with('.')
+            && id
+                .chars()
+                .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.'));
+        let unknown = || LayoutError::UnknownParent { id: id.to_string() };
+        if !valid {
+            return Err(unknown());
+        }
+        self.dirs
+            .iter()
+            .find_map(|dir| std::fs::read_to_string(dir.join(format!("{id}.json5"))).ok())
+            .or_else(|| builtin_json(id).map(str::to_string))
+            .ok_or_else(unknown)
+    }
+
+    /// `chain`(자식부터 지금까지의 ID)에 이어 `schema`의 상속을 푼다.
+    fn resolve_chain(
+        &self,
+        schema: LayoutSchema,
+        chain: &mut Vec<String>,
+    ) -> Result<LayoutSchema, LayoutError> {
+        let Some(parent_id) = schema.extends.clone() else {
+            return Ok(schema);
+        };
+        if chain.contains(&parent_id) {
+            let mut ids = chain.clone();
+            ids.push(parent_id);
+            return Err(LayoutError::Cycle { ids });
+        }
+        let parent = parse_schema(&self.source(&parent_id)?).map_err(|e| LayoutError::Parent {
+            id: parent_id.clone(),
+            message: e.to_string(),
+        })?;
+        chain.push(parent_id);
+        let parent = self.resolve_chain(parent, chain)?;
+        merge(parent, schema)
+    }
+
+    /// JSON5 레이아웃의 상속을 풀어 `extends`가 없는 스키마를 만든다.
+    pub fn resolve(&self, json: &str) -> Result<LayoutSchema, LayoutError> {
+        let schema = parse_schema(json)?;
+        let mut chain = vec![schema.id.clone()];
+        let schema = self.resolve_chain(schema, &mut chain)?;
+        required(&schema)?;
+        Ok(schema)
+    }
+
+    /// JSON5 레이아웃의 상속을 풀어 로드한다.
+    pub fn load(&self, json: &str) -> Result<KeyboardLayout, LayoutError> {
+        KeyboardLayout::from_schema(self.resolve(json)?)
+    }
+
+    /// 로드할 수 있는 JSON5: `extends`가 없으면 원본 그대로, 있으면 [`flatten`](Self::flatten) 결과.
+    pub fn expand(&self, json: &str) -> Result<String, LayoutError> {
+        if parse_schema(json)?.extends.is_none() {
+            return Ok(json.to_string());
+        }
+        self.flatten(json)
+    }
+
+    /// 상속을 푼 레이아웃을 `extends` 없는 JSON5로 내보낸다.
+    /// 키는 물리 키 순서(그 밖의 키는 사전 순), 값은 대문자 16진수로 정규화한다.
+    pub fn flatten(&self, json: &str) -> Result<String, LayoutError> {
+        let schema = self.resolve(json)?;
+        // 잘못된 값은 내보내기 전에 보고한다
+        KeyboardLayout::from_schema(schema.clone())?;
+        Ok(to_json5(&schema))
+    }
+}
+
+/// 16진수 값 정규화 ("0x11ba" → "0x11BA")
+fn hex(value: &str) -> String {
+    parse_hex_char(value).map_or_else(|| value.to_string(), |ch| format!("0x{:04X}", ch as u32))
+}
+
+/// 주석에 쓸 글자: 위치 자모는 호환 자모로 보여준다.
+fn label(value: &str) -> String {
+    let Some(ch) = parse_hex_char(value) else {
+        return String::new();
+    };
+    let code = ch as u32;
+    let compat = if unicode::is_choseong(ch) {
+        unicode::choseong_to_compat(code - unicode::L_BASE)
+    } else if unicode::is_jungseong(ch) {
+        unicode::jungseong_to_compat(code - unicode::V_BASE)
+    } else if unicode::is_jongseong(ch) {
+        unicode::jongseong_to_compat(code - unicode::T_BASE)
+    } else {
+        None
+    };
+    match compat.unwrap_or(ch) {
+        ch if ch.is_control() || ch.is_whitespace() => String::new(),
+        ch => ch.to_string(),
+    }
+}
+
+/// 상태별 대안의 출력 순서 (default 먼저, 그다음 오토마타 상태 순)
+fn state_order(state: &str) -> usize {
+    [
+        "default",
+        "empty",
+        "choseong",
+        "jungseong",
+        "jungseong2",
+        "jongseong",
+        "jongseong2",
+    ]
+    .iter()
+    .position(|s| *s == state)
+    .unwrap_or(usize::MAX)
+}
+
+/// 상속을 푼 스키마를 JSON5로 쓴다.
+pub(super) fn to_json5(schema: &LayoutSchema) -> String {
+    let mut out = String::from("{\n");
+    let _ = writeln!(out, "  id: {},", quote(&schema.id));
+    let _ = writeln!(
+        out,
+        "  name: {},",
+        quote(schema.name.as_deref().unwrap_or_default())
+    );
+    if let Some(description) = &schema.description {
+        let _ = writeln!(out, "  description: {},", quote(description));
+    }
+    if let Some(experimental) = schema.experimental {
+        let _ = writeln!(out, "  experimental: {experimental},");
+    }
+    if let Some(layout_type) = &schema.layout_type {
+        let _ = writeln!(out, "  type: {},", quote(layout_type.as_str()));
+    }
+
+    out.push_str("\n  keymap: {\n");
+    let mut keys: Vec<(&String, &KeymapValue)> = schema
+        .keymap
+        .iter()
+        .filter_map(|(key, value)| Some((key, value.as_ref()?)))
+        .collect();
+    keys.sort_by_key(|(key, _)| {
+        let position = physical::from_label(key).map(|(k, shift)| (k.usage, shift));
+        (position.is_none(), position, key.as_str())
+    });
+    for (key, value) in keys {
+        let (value, comment) = match value {
+            KeymapValue::Code(code) => (quote(&hex(code)), label(code)),
+            KeymapValue::Contextual(alternatives) => {
+                let mut states: Vec<_> = alternatives.iter().collect();
+                states.sort_by_key(|(state, _)| (state_order(state), state.as_str()));
+                let fields: Vec<String> = states
+                    .iter()
+                    .map(|(state, code)| format!("{state}: {}", quote(&hex(code))))
+                    .collect();
+                let labels: Vec<String> = states.iter().map(|(_, code)| label(code)).collect();
+                (format!("{{ {} }}", fields.join(", ")), labels.join(" / "))
+            }
+        };
+        let _ = write!(out, "    {}: {value},", quote(key));
+        if !comment.trim_matches([' ', '/']).is_empty() {
+            let _ = write!(out, "  // {comment}");
+        }
+        out.push('\n');
+    }
+    out.push_str("  },\n");
+
+    out.push_str("\n  combinations: [\n");
+    for entry in &schema.combinations {
+        let Some(result) = &entry.result else {
+            continue;
+        };
+        let _ = writeln!(
+            out,
+            "    {{ first: {}, second: {}, result: {} }},  // {}+{}={}",
+            quote(&hex(&entry.first)),
+            quote(&hex(&entry.second)),
+            quote(&hex(result)),
+            label(&entry.first),
+            label(&entry.second),
+            label(result)
+        );
+    }
+    out.push_str("  ],\n");
+
+    let options = schema.options.clone().unwrap_or_default();
+    let LayoutOptions { final_only } = options.into();
+    if final_only.is_empty() {
+        out.push_str("\n  options: {},\n");
+    } else {
+        let keys: Vec<String> = final_only.iter().map(|k| quote(k)).collect();
+        let _ = writeln!(out, "\n  options: {{ final_only: [{}] }},", keys.join(", "));
+    }
+    out.push_str("}\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LAYOUTS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/layouts");
+
+    fn bundled() -> LayoutResolver {
+        LayoutResolver::new(vec![PathBuf::from(LAYOUTS_DIR)])
+    }
+
+    /// 테스트용 임시 디렉터리에 레이아웃 파일을 쓴다.
+    fn temp_dir(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("ongeul-resolve-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        for (id, json) in files {
+            std::fs::write(dir.join(format!("{id}.json5")), json).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn test_extends_overrides_and_removals() {
+        let json = r#"{
+            id: "3-final-mine", extends: "3-final",
+            keymap: {
+                "[": "0x300C",   // 「
+                "]": "0x300D",   // 」
+                "k": null,        // ㄱ 초성 제거
+            },
+            combinations: [
+                { first: "0x1100", second: "0x1100", result: null },  // ㄱ+ㄱ 제거
+            ],
+        }"#;
+        let layout = bundled().load(json).unwrap();
+        let base = bundled()
+            .load(&std::fs::read_to_string(format!("{LAYOUTS_DIR}/3-final.json5")).unwrap())
+            .unwrap();
+        assert_eq!(layout.id, "3-final-mine");
+        assert_eq!(layout.name, base.name);
+        assert_eq!(layout.layout_type, super::super::schema::LayoutType::Jaso);
+        assert_eq!(layout.map_key("["), Some('「'));
+        assert_eq!(layout.map_key("k"), None);
+        assert_eq!(layout.map_key("h"), base.map_key("h"));
+        assert_eq!(layout.combine('\u{1100}', '\u{1100}'), None);
+        assert_eq!(layout.combine('\u{1103}', '\u{1103}'), Some('\u{1104}'));
+        assert_eq!(layout.combinations.len(), base.combinations.len() - 1);
+    }
+
+    #[test]
+    fn test_extends_merges_options() {
+        // 자식의 options는 지정한 항목만 덮어쓴다
+        let load = |options: &str| {
+            let json = format!(r#"{{ id: "romaja-mine", extends: "romaja", options: {options} }}"#);
+            bundled().load(&json).unwrap().options.final_only
+        };
+        assert_eq!(load("{}"), ["ng"]);
+        assert_eq!(load("{ final_only: null }"), ["ng"]);
+        assert_eq!(load(r#"{ final_only: ["ng", "nk"] }"#), ["ng", "nk"]);
+        assert!(load("{ final_only: [] }").is_empty());
+
+        // 평탄화해도 물려받은 항목이 남는다
+        let flat = bundled()
+            .flatten(r#"{ id: "romaja-mine", extends: "romaja", options: {} }"#)
+            .unwrap();
+        assert!(
+            flat.contains(r#"options: { final_only: ["ng"] }"#),
+            "{flat}"
+        );
+    }
+
+    #[test]
+    fn test_extends_errors() {
+        let resolver = bundled();
+        let json = r#"{ id: "x", extends: "nope", keymap: {} }"#;
+        assert_eq!(
+            resolver.load(json).unwrap_err(),
+            LayoutError::UnknownParent { id: "nope".into() }
+        );
+        // 경로처럼 보이는 ID는 찾지 않는다
+        let json = r#"{ id: "x", extends: "../layouts/2-standard" }"#;
+        assert!(matches!(
+            resolver.load(json),
+            Err(LayoutError::UnknownParent { .. })
+        ));
+        // 부모와 다른 타입
+        let json = r#"{ id: "x", extends: "2-standard", type: "jaso" }"#;
+        assert!(matches!(
+            resolver.load(json),
+            Err(LayoutError::Schema { path, .. }) if path == "type"
+        ));
+        // extends가 없으면 name과 type이 필요하다
+        let json = r#"{ id: "x", type: "jamo", keymap: {} }"#;
+        assert_eq!(resolver.load(json).unwrap_err(), missing_field("name"));
+        // 부모 파일의 에러
+        let dir = temp_dir("parent", &[("broken", "{ id: \"broken\",, }")]);
+        let json = r#"{ id: "x", extends: "broken" }"#;
+        assert!(matches!(
+            LayoutResolver::new(vec![dir.clone()]).load(json),
+            Err(LayoutError::Parent { id, .. }) if id == "broken"
+        ));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_extends_cycle() {
+        let dir = temp_dir(
+            "cycle",
+            &[
+                ("a", r#"{ id: "a", extends: "b" }"#),
+                ("b", r#"{ id: "b", extends: "c" }"#),
+                ("c", r#"{ id: "c", extends: "a" }"#),
+            ],
+        );
+        let resolver = LayoutResolver::new(vec![dir.clone()]);
+        let json = std::fs::read_to_string(dir.join("a.json5")).unwrap();
+        assert_eq!(
+            resolver.load(&json).unwrap_err(),
+            LayoutError::Cycle {
+                ids: ["a", "b", "c", "a"].map(String::from).to_vec()
+            }
+        );
+        // 자기 자신
+        let json = r#"{ id: "d", extends: "d" }"#;
+        assert!(matches!(
+            resolver.load(json),
+            Err(LayoutError::Cycle { ids }) if ids == ["d", "d"]
+        ));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_extends_chain_and_search_order() {
+        // 앞 디렉터리가 우선하고, 부모의 부모도 같은 방식으로 찾는다
+        let user = temp_dir(
+            "chain",
+            &[(
+                "mine",
+                r#"{ id: "mine", extends: "2-standard", name: "내 두벌식", keymap: { "q": "0x3141" } }"#,
+            )],
+        );
+        let resolver = LayoutResolver::new(vec![user.clone(), PathBuf::from(LAYOUTS_DIR)]);
+        let json = r#"{ id: "mine2", extends: "mine", keymap: { "w": null } }"#;
+        let layout = resolver.load(json).unwrap();
+        assert_eq!(layout.name, "내 두벌식");
+        assert_eq!(layout.map_key("q"), Some('ㅁ'));
+        assert_eq!(layout.map_key("w"), None);
+        assert_eq!(layout.map_key("e"), Some('ㄷ'));
+        std::fs::remove_dir_all(user).unwrap();
+
+        // 디렉터리에 없으면 동봉 레이아웃에서 찾는다
+        let json = r#"{ id: "x", extends: "3-final", keymap: { "[": "0x300C" } }"#;
+        let layout = LayoutResolver::default().load(json).unwrap();
+        assert_eq!(layout.name, bundled().load(json).unwrap().name);
+    }
+
+    #[test]
+    fn test_flatten_round_trip() {
+        // 평탄화한 JSON5는 extends 없이 같은 레이아웃으로 로드된다
+        for file in super::super::read_dir(std::path::Path::new(LAYOUTS_DIR)).unwrap() {
+            let original = file.layout.unwrap();
+            let flat = bundled().flatten(&file.json).unwrap();
+            assert!(!flat.contains("extends"));
+            let layout = KeyboardLayout::from_json(&flat).unwrap();
+            assert_eq!(layout.id, original.id);
+            assert_eq!(layout.name, original.name);
+            assert_eq!(layout.description, original.description);
+            assert_eq!(layout.layout_type, original.layout_type);
+            assert_eq!(layout.keymap, original.keymap);
+            for (key, alternatives) in &original.contextual {
+                for &(state, ch) in alternatives {
+                    assert_eq!(layout.map_key_in_state(key, state), Some(ch));
+                }
+            }
+            assert_eq!(layout.contextual.len(), original.contextual.len());
+            assert_eq!(layout.combinations, original.combinations);
+            assert_eq!(layout.options.final_only, original.options.final_only);
+        }
+
+        let json = r#"{ id: "x", extends: "2-standard", keymap: { "q": "0x3141", "w": null } }"#;
+        let flat = bundled().flatten(json).unwrap();
+        assert!(flat.contains("\"q\": \"0x3141\",  // ㅁ\n"), "{flat}");
+        assert!(!flat.contains("\"w\":"));
+    }
+
+    #[test]
+    fn test_flatten_escapes_control_characters() {
+        // 이름·설명의 제어 문자와 따옴표도 평탄화한 뒤 그대로 로드된다
+        let json = r#"{
+            id: "x", extends: "2-standard",
+            name: "내\n\"자판\"\t\u0001",
+            description: "줄\r\n바꿈 \\ 역슬래시",
+        }"#;
+        let original = bundled().load(json).unwrap();
+        assert_eq!(original.name, "내\n\"자판\"\t\u{1}");
+        let flat = bundled().flatten(json).unwrap();
+        let layout = KeyboardLayout::from_json(&flat).unwrap();
+        assert_eq!(layout.name, original.name);
+        assert_eq!(layout.description, "줄\r\n바꿈 \\ 역슬래시");
+    }
+}
@@ -3,12 +3,46 @@ use std::collections::HashMap;
 
 use serde::Deserialize;
 
-/// 자판 타입: 두벌식(jamo) 또는 세벌식(jaso)
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+/// 자판 타입: 두벌식(jamo), 세벌식(jaso), 신세벌식(shin-jaso), 옛한글 세벌식(old-hangul),
+/// 로마자 소리 입력(romaja) 또는 영문 모드용 배열(latin)
+#[derive(uniffi::Enum, Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum LayoutType {
     Jamo,
     Jaso,
+    #[serde(rename = "shin-jaso")]
+    ShinJaso,
+    #[serde(rename = "old-hangul")]
+    OldHangul,
+    Romaja,
+    Latin,
+}
+
+impl LayoutType {
+    /// JSON5의 `type` 값
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LayoutType::Jamo => "jamo",
+            LayoutType::Jaso => "jaso",
+            LayoutType::ShinJaso => "shin-jaso",
+            LayoutType::OldHangul => "old-hangul",
+            LayoutType::Romaja => "romaja",
+            LayoutType::Latin => "latin",
+        }
+    }
+}
+
+/// keymap 값: 단일 코드포인트 또는 오토마타 상태별 대안
+///
+/// 상태별 대안 예: `{ default: "0x11BA", choseong: "0x1168" }` — 초성 뒤에서는 ㅖ, 그 외에는 ㅅ종.
+/// 상태 이름은 `default`, `empty`, `choseong`, `jungseong`, `jungseong2`, `jongseong`, `jongseong2`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum KeymapValue {
+    /// 16진수 문자열 (예: "0x3131")
+    Code(String),
+    /// 상태 이름 → 16진수 문자열
+    Contextual(HashMap<String, String>),
 }
 
 /// 조합 규칙 항목 (겹모음/겹종성)
@@ -18,32 +52,74 @@ pub struct CombinationEntry {
     pub first: String,
     /// 둘째 자모 (16진수 문자열, 예: "0x314F")
     pub second: String,
-    /// 결합 결과 (16진수 문자열, 예: "0x3158")
-    pub result: String,
+    /// 결합 결과 (16진수 문자열, 예: "0x3158").
+    /// 상속한 레이아웃에서 null이면 부모의 같은 (first, second) 조합을 지운다.
+    #[serde(deserialize_with = "Option::deserialize")]
+    pub result: Option<String>,
 }
 
 /// 레이아웃 옵션
-#[derive(Debug, Clone, Default, Deserialize)]
+#[derive(Debug, Clone, Default)]
 pub struct LayoutOptions {
-    // 향후 레이아웃별 옵션 추가 시 사용
+    /// 로마자 배열: 받침 자리에서만 쓰는 키 (뒤에 모음이 오면 짧은 키로 나눈다. 예: "ng")
+    pub final_only: Vec<String>,
+}
+
+/// JSON5 `options`. 생략한 항목은 부모 레이아웃을 따른다.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct OptionsSchema {
+    #[serde(default)]
+    pub final_only: Option<Vec<String>>,
+}
+
+impl OptionsSchema {
+    /// 항목 단위로 덮어쓴다. (자식이 지정한 항목이 우선)
+    pub fn merge(self, parent: OptionsSchema) -> OptionsSchema {
+        OptionsSchema {
+            final_only: self.final_only.or(parent.final_only),
+        }
+    }
+}
+
+impl From<OptionsSchema> for LayoutOptions {
+    fn from(options: OptionsSchema) -> Self {
+        LayoutOptions {
+            final_only: options.final_only.unwrap_or_default(),
+        }
+    }
 }
 
 /// JSON5 레이아웃 최상위 스키마
+///
+/// `extends`가 있으면 그 레이아웃을 부모로 삼아 덮어쓴다. ([`LayoutResolver`](super::LayoutResolver))
+/// `name`, `description`, `type`은 생략하면 부모를 따르고, `keymap`은 키 단위, `options`는 항목 단위,
+/// `combinations`는 (first, second) 쌍 단위로 덮어쓴다. null 값은 부모의 항목을 지운다.
 #[derive(Debug, Clone, Deserialize)]
 pub struct LayoutSchema {
     /// 레이아웃 식별자 (예: "2-standard")
     pub id: String,
-    /// 레이아웃 이름 (예: "두벌식 표준")
-    pub name: String,
-    /// 자판 타입
-    #[serde(rename = "type")]
-    pub layout_type: LayoutType,
-    /// 키 → 자모 매핑 (키 레이블 → 16진수 코드포인트)
-    pub keymap: HashMap<String, String>,
+    /// 부모 레이아웃 ID (예: "3-final")
+    #[serde(default)]
+    pub extends: Option<String>,
+    /// 레이아웃 이름 (예: "두벌식 표준"). `extends`가 없으면 필수.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// 한 줄 설명 (선택)
+    #[serde(default)]
+    pub description: Option<String>,
+    /// 검증되지 않은 실험용 레이아웃이면 true. 환경설정 목록에는 나오지 않는다.
+    #[serde(default)]
+    pub experimental: Option<bool>,
+    /// 자판 타입. `extends`가 없으면 필수, 있으면 부모와 같아야 한다.
+    #[serde(rename = "type", default)]
+    pub layout_type: Option<LayoutType>,
+    /// 키 → 자모 매핑 (키 레이블 → 16진수 코드포인트 또는 상태별 대안, null이면 부모의 키를 지운다)
+    #[serde(default)]
+    pub keymap: HashMap<String, Option<KeymapValue>>,
     /// 조합 규칙 (겹모음, 겹종성 등)
     #[serde(default)]
     pub combinations: Vec<CombinationEntry>,
     /// 옵션
     #[serde(default)]
-    pub options: LayoutOptions,
+    pub options: Option<OptionsSchema>,
 }
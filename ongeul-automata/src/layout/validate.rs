@@ -0,0 +1,590 @@
+//! 자판 레이아웃 검사 (lint)
+//!
+//! [`KeyboardLayout::from_json`](super::KeyboardLayout::from_json)은 문법과 16진수 오류만 잡는다.
+//! 여기서는 로드는 되지만 실행 중에 잘못 동작하는 레이아웃을 찾는다:
+//! 자판 타입과 맞지 않는 자모 종류, 결과가 다른 종류인 조합, 입력할 수 없는 조합,
+//! 현대 자모 누락, 중복된 키.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::de::{Deserializer, MapAccess, Visitor};
+use serde::{Deserialize, Serialize};
+
+use super::schema::{CombinationEntry, LayoutType};
+use super::{LayoutError, LayoutResolver, parse_hex_char};
+use crate::unicode;
+
+/// 진단 심각도
+#[derive(uniffi::Enum, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticSeverity {
+    /// 로드되지 않거나 입력이 잘못 조합된다
+    Error,
+    /// 로드되지만 의도와 다를 수 있다
+    Warning,
+}
+
+/// 레이아웃 진단 하나
+#[derive(uniffi::Record, Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LayoutDiagnostic {
+    pub severity: DiagnosticSeverity,
+    /// 문제가 있는 값의 JSON 경로 (예: `keymap["q"].choseong`, `combinations[3].result`)
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for LayoutDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            DiagnosticSeverity::Error => "error",
+            DiagnosticSeverity::Warning => "warning",
+        };
+        if self.path.is_empty() {
+            write!(f, "{severity}: {}", self.message)
+        } else {
+            write!(f, "{severity}: {}: {}", self.path, self.message)
+        }
+    }
+}
+
+/// 중복 키를 보존하는 JSON 객체 (선언 순서대로)
+struct Entries<V>(Vec<(String, V)>);
+
+impl<V> Entries<V> {
+    fn empty() -> Self {
+        Entries(Vec::new())
+    }
+}
+
+impl<'de, V: Deserialize<'de>> Deserialize<'de> for Entries<V> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct EntriesVisitor<V>(PhantomData<V>);
+
+        impl<'de, V: Deserialize<'de>> Visitor<'de> for EntriesVisitor<V> {
+            type Value = Entries<V>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("an object")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut entries = Vec::new();
+                while let Some(entry) = map.next_entry()? {
+                    entries.push(entry);
+                }
+                Ok(Entries(entries))
+            }
+        }
+
+        deserializer.deserialize_map(EntriesVisitor(PhantomData))
+    }
+}
+
+/// keymap 값 (스키마의 `KeymapValue`와 같되 상태 이름 중복을 보존)
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawValue {
+    Code(String),
+    Contextual(Entries<String>),
+}
+
+/// 검사에 필요한 부분만 읽은 레이아웃
+#[derive(Deserialize)]
+struct RawLayout {
+    #[serde(rename = "type")]
+    layout_type: LayoutType,
+    #[serde(default = "Entries::empty")]
+    keymap: Entries<Option<RawValue>>,
+    #[serde(default)]
+    combinations: Vec<CombinationEntry>,
+}
+
+/// 자모 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum JamoClass {
+    CompatConsonant,
+    CompatVowel,
+    Choseong,
+    Jungseong,
+    Jongseong,
+    /// 자모가 아닌 문자 (숫자, 문장부호 등)
+    Symbol,
+}
+
+impl JamoClass {
+    fn of(ch: char) -> Self {
+        if unicode::is_compat_consonant(ch) {
+            JamoClass::CompatConsonant
+        } else if unicode::is_compat_vowel(ch) {
+            JamoClass::CompatVowel
+        } else if unicode::is_old_choseong(ch) {
+            JamoClass::Choseong
+        } else if unicode::is_old_jungseong(ch) {
+            JamoClass::Jungseong
+        } else if unicode::is_old_jongseong(ch) {
+            JamoClass::Jongseong
+        } else {
+            JamoClass::Symbol
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            JamoClass::CompatConsonant => "compatibility consonant",
+            JamoClass::CompatVowel => "compatibility vowel",
+            JamoClass::Choseong => "choseong",
+            JamoClass::Jungseong => "jungseong",
+            JamoClass::Jongseong => "jongseong",
+            JamoClass::Symbol => "non-jamo character",
+        }
+    }
+
+    fn is_compat(self) -> bool {
+        matches!(self, JamoClass::CompatConsonant | JamoClass::CompatVowel)
+    }
+
+    fn is_positional(self) -> bool {
+        matches!(
+            self,
+            JamoClass::Choseong | JamoClass::Jungseong | JamoClass::Jongseong
+        )
+    }
+}
+
+/// 현대 자모가 아닌 첫가끝 자모 (옛한글, 채움 문자)
+fn is_old_jamo(ch: char) -> bool {
+    JamoClass::of(ch).is_positional()
+        && !(unicode::is_choseong(ch) || unicode::is_jungseong(ch) || unicode::is_jongseong(ch))
+}
+
+fn describe(ch: char) -> String {
+    format!("{ch} (U+{:04X})", ch as u32)
+}
+
+/// 자판 타입에서 keymap 값으로 쓸 수 없는 문자면 그 이유
+fn class_mismatch(layout_type: &LayoutType, ch: char) -> Option<String> {
+    let class = JamoClass::of(ch);
+    let ty = layout_type.as_str();
+    match layout_type {
+        LayoutType::Jamo | LayoutType::Romaja if class.is_positional() => Some(format!(
+            "{} is a conjoining {}; {ty} layouts use compatibility jamo (U+3131~U+3163)",
+            describe(ch),
+            class.name()
+        )),
+        LayoutType::Jaso | LayoutType::ShinJaso | LayoutType::OldHangul if class.is_compat() => {
+            Some(format!(
+                "{} is a {}; {ty} layouts use conjoining jamo (U+1100~U+11FF)",
+                describe(ch),
+                class.name()
+            ))
+        }
+        LayoutType::Jaso | LayoutType::ShinJaso if is_old_jamo(ch) => Some(format!(
+            "{} is an old Hangul {}; use type \"old-hangul\"",
+            describe(ch),
+            class.name()
+        )),
+        LayoutType::Latin if class != JamoClass::Symbol => Some(format!(
+            "{} is a {}; latin layouts map to Latin characters",
+            describe(ch),
+            class.name()
+        )),
+        _ => None,
+    }
+}
+
+/// 자판이 입력할 수 있어야 하는 현대 자모 (종류 이름, 자모 목록)
+fn modern_jamo(layout_type: &LayoutType) -> Vec<(&'static str, Vec<char>)> {
+    let positional = |base: u32, range: std::ops::Range<u32>| -> Vec<char> {
+        range.filter_map(|i| char::from_u32(base + i)).collect()
+    };
+    match layout_type {
+        LayoutType::Jamo | LayoutType::Romaja => {
+            let mut jongseong: Vec<char> = (1..unicode::T_COUNT)
+                .filter_map(unicode::jongseong_to_compat)
+                .collect();
+            // 초성과 겹치는 홑받침은 초성에서 보고한다
+            jongseong.retain(|&ch| unicode::compat_to_choseong(ch).is_none());
+            vec![
+                (
+                    "choseong",
+                    (0..unicode::L_COUNT)
+                        .filter_map(unicode::choseong_to_compat)
+                        .collect(),
+                ),
+                (
+                    "jungseong",
+                    (0..unicode::V_COUNT)
+                        .filter_map(unicode::jungseong_to_compat)
+                        .collect(),
+                ),
+                ("jongseong", jongseong),
+            ]
+        }
+        LayoutType::Jaso | LayoutType::ShinJaso | LayoutType::OldHangul => vec![
+            ("choseong", positional(unicode::L_BASE, 0..unicode::L_COUNT)),
+            (
+                "jungseong",
+                positional(unicode::V_BASE, 0..unicode::V_COUNT),
+            ),
+            (
+                "jongseong",
+                positional(unicode::T_BASE, 1..unicode::T_COUNT),
+            ),
+        ],
+        LayoutType::Latin => Vec::new(),
+    }
+}
+
+fn key_path(key: &str) -> String {
+    format!("keymap[{key:?}]")
+}
+
+struct Linter {
+    diagnostics: Vec<LayoutDiagnostic>,
+}
+
+impl Linter {
+    fn push(&mut self, severity: DiagnosticSeverity, path: String, message: String) {
+        self.diagnostics.push(LayoutDiagnostic {
+            severity,
+            path,
+            message,
+        });
+    }
+
+    /// keymap 값 하나의 자모 종류를 검사한다.
+    fn check_value(&mut self, layout_type: &LayoutType, path: String, ch: char) {
+        if let Some(message) = class_mismatch(layout_type, ch) {
+            self.push(DiagnosticSeverity::Error, path, message);
+        }
+    }
+
+    /// keymap: 자모 종류와 중복 키. 키로 입력할 수 있는 문자를 돌려준다.
+    fn check_keymap(&mut self, layout: &RawLayout) -> HashSet<char> {
+        let mut produced = HashSet::new();
+        let mut seen = HashSet::new();
+        for (key, value) in &layout.keymap.0 {
+            let path = key_path(key);
+            if !seen.insert(key.as_str()) {
+                self.push(
+                    DiagnosticSeverity::Warning,
+                    path.clone(),
+                    "duplicate key; the last value wins".to_string(),
+                );
+            }
+            match value {
+                // null은 상속한 레이아웃에서 부모의 키를 지운다
+                None => {}
+                Some(RawValue::Code(hex)) => {
+                    if let Some(ch) = parse_hex_char(hex) {
+                        self.check_value(&layout.layout_type, path, ch);
+                        produced.insert(ch);
+                    }
+                }
+                Some(RawValue::Contextual(alternatives)) => {
+                    let mut states = HashSet::new();
+                    for (state, hex) in &alternatives.0 {
+                        let state_path = format!("{path}.{state}");
+                        if !states.insert(state.as_str()) {
+                            self.push(
+                                DiagnosticSeverity::Warning,
+                                state_path.clone(),
+                                "duplicate state; the last value wins".to_string(),
+                            );
+                        }
+                        if let Some(ch) = parse_hex_char(hex) {
+                            self.check_value(&layout.layout_type, state_path, ch);
+                            produced.insert(ch);
+                        }
+                    }
+                }
+            }
+        }
+        produced
+    }
+
+    /// combinations: 자모 종류, 중복, 도달 가능성. 조합으로 입력할 수 있는 문자를 `produced`에 더한다.
+    fn check_combinations(&mut self, layout: &RawLayout, produced: &mut HashSet<char>) {
+        let entries: Vec<Option<(char, char, char)>> = layout
+            .combinations
+            .iter()
+            .map(|e| {
+                Some((
+                    parse_hex_char(&e.first)?,
+                    parse_hex_char(&e.second)?,
+                    parse_hex_char(e.result.as_deref()?)?,
+                ))
+            })
+            .collect();
+
+        let mut pairs: HashMap<(char, char), usize> = HashMap::new();
+        for (i, entry) in entries.iter().enumerate() {
+            let Some((first, second, result)) = *entry else {
+                continue;
+            };
+            let path = format!("combinations[{i}]");
+            if layout.layout_type == LayoutType::Latin {
+                self.push(
+                    DiagnosticSeverity::Warning,
+                    path,
+                    "latin layouts do not combine characters; entry is ignored".to_string(),
+                );
+                continue;
+            }
+            if let Some(previous) = pairs.insert((first, second), i) {
+                self.push(
+                    DiagnosticSeverity::Warning,
+                    path.clone(),
+                    format!("duplicate of combinations[{previous}]; the last entry wins"),
+                );
+            }
+            for (field, ch) in [("first", first), ("second", second), ("result", result)] {
+                self.check_value(&layout.layout_type, format!("{path}.{field}"), ch);
+            }
+            let class = JamoClass::of(first);
+            if class == JamoClass::Symbol {
+                self.push(
+                    DiagnosticSeverity::Error,
+                    format!("{path}.first"),
+                    format!("{} is not a jamo", describe(first)),
+                );
+            } else if JamoClass::of(second) != class {
+                self.push(
+                    DiagnosticSeverity::Error,
+                    format!("{path}.second"),
+                    format!(
+                        "{} is a {} but first is a {}",
+                        describe(second),
+                        JamoClass::of(second).name(),
+                        class.name()
+                    ),
+                );
+            } else if JamoClass::of(result) != class {
+                self.push(
+                    DiagnosticSeverity::Error,
+                    format!("{path}.result"),
+                    format!(
+                        "{} is a {} but combines two {}s",
+                        describe(result),
+                        JamoClass::of(result).name(),
+                        class.name()
+                    ),
+                );
+            }
+        }
+
+        // 키나 다른 조합으로 두 자모를 모두 입력할 수 있어야 도달한다
+        let mut reachable = vec![false; entries.len()];
+        loop {
+            let mut changed = false;
+            for (i, entry) in entries.iter().enumerate() {
+                if let Some((first, second, result)) = *entry
+                    && !reachable[i]
+                    && produced.contains(&first)
+                    && produced.contains(&second)
+                {
+                    reachable[i] = true;
+                    produced.insert(result);
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+        if layout.layout_type == LayoutType::Latin {
+            return;
+        }
+        for (i, entry) in entries.iter().enumerate() {
+            let Some((first, second, _)) = *entry else {
+                continue;
+            };
+            if reachable[i] {
+                continue;
+            }
+            let missing = if produced.contains(&first) {
+                second
+            } else {
+                first
+            };
+            self.push(
+                DiagnosticSeverity::Warning,
+                format!("combinations[{i}]"),
+                format!(
+                    "unreachable: no key or combination produces {}",
+                    describe(missing)
+                ),
+            );
+        }
+    }
+
+    /// 현대 자모를 모두 입력할 수 있는지 검사한다.
+    fn check_coverage(&mut self, layout_type: &LayoutType, produced: &HashSet<char>) {
+        for (name, jamo) in modern_jamo(layout_type) {
+            let missing: Vec<String> = jamo
+                .into_iter()
+                .filter(|ch| !produced.contains(ch))
+                .map(describe)
+                .collect();
+            if !missing.is_empty() {
+                self.push(
+                    DiagnosticSeverity::Warning,
+                    "keymap".to_string(),
+                    format!("no key produces modern {name}: {}", missing.join(", ")),
+                );
+            }
+        }
+    }
+}
+
+impl From<&LayoutError> for LayoutDiagnostic {
+    /// 로드 에러를 진단으로 (경로를 알면 경로와 함께)
+    fn from(error: &LayoutError) -> Self {
+        let path = match error {
+            LayoutError::InvalidHex { path, .. } | LayoutError::Schema { path, .. } => path.clone(),
+            _ => String::new(),
+        };
+        LayoutDiagnostic {
+            severity: DiagnosticSeverity::Error,
+            path,
+            message: error.to_string(),
+        }
+    }
+}
+
+/// JSON5 레이아웃을 검사한다. 문제가 없으면 빈 목록.
+/// 로드할 수 없는 레이아웃은 로드 에러 하나만 보고한다. (`extends`는 풀 수 없으므로 에러)
+pub fn validate(json: &str) -> Vec<LayoutDiagnostic> {
+    if let Err(e) = super::KeyboardLayout::from_json(json) {
+        return vec![LayoutDiagnostic::from(&e)];
+    }
+    let layout: RawLayout = match json5::from_str(json) {
+        Ok(layout) => layout,
+        Err(e) => {
+            return vec![LayoutDiagnostic {
+                severity: DiagnosticSeverity::Error,
+                path: String::new(),
+                message: format!("JSON5 parse error: {e}"),
+            }];
+        }
+    };
+
+    let mut linter = Linter {
+        diagnostics: Vec::new(),
+    };
+    let mut produced = linter.check_keymap(&layout);
+    linter.check_combinations(&layout, &mut produced);
+    linter.check_coverage(&layout.layout_type, &produced);
+    linter.diagnostics
+}
+
+/// `extends`를 `resolver`로 풀어 검사한다. 상속한 레이아웃은 상속을 푼 결과를 검사한다.
+pub fn validate_with(resolver: &LayoutResolver, json: &str) -> Vec<LayoutDiagnostic> {
+    match resolver.expand(json) {
+        Ok(json) => validate(&json),
+        Err(e) => vec![LayoutDiagnostic::from(&e)],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths(diagnostics: &[LayoutDiagnostic]) -> Vec<(DiagnosticSeverity, &str)> {
+        diagnostics
+            .iter()
+            .map(|d| (d.severity, d.path.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_bundled_layouts_are_clean() {
+        let dir = std::path::Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/layouts"));
+        for file in super::super::read_dir(dir).unwrap() {
+            let diagnostics = validate(&file.json);
+            assert!(
+                diagnostics.is_empty(),
+                "{}: {diagnostics:#?}",
+                file.path.display()
+            );
+        }
+    }
+
+    #[test]
+    fn test_load_error() {
+        let diagnostics = validate("{ id: \"x\",, }");
+        assert_eq!(paths(&diagnostics), [(DiagnosticSeverity::Error, "")]);
+        // 경로를 아는 로드 에러
+        let diagnostics = validate("{ id: \"x\" }");
+        assert_eq!(paths(&diagnostics), [(DiagnosticSeverity::Error, "name")]);
+    }
+
+    #[test]
+    fn test_class_mismatch() {
+        // 세벌식 자판에 호환 자모, 두벌식 자판에 위치 자모
+        let jaso = r#"{ id: "t", name: "t", type: "jaso",
+            keymap: { "k": "0x1100", "r": { default: "0x11A8", choseong: "0x314F" } } }"#;
+        let diagnostics = validate(jaso);
+        assert_eq!(diagnostics[0].path, "keymap[\"r\"].choseong");
+        assert_eq!(diagnostics[0].severity, DiagnosticSeverity::Error);
+
+        let jamo =
+            r#"{ id: "t", name: "t", type: "jamo", keymap: { "r": "0x1100", "1": "0x0031" } }"#;
+        let errors: Vec<_> = validate(jamo)
+            .into_iter()
+            .filter(|d| d.severity == DiagnosticSeverity::Error)
+            .collect();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, "keymap[\"r\"]");
+
+        // 옛한글 자모는 old-hangul 자판에서만
+        let old = r#"{ id: "t", name: "t", type: "jaso", keymap: { "G": "0x119E" } }"#;
+        assert!(validate(old)[0].message.contains("old-hangul"));
+    }
+
+    #[test]
+    fn test_combination_checks() {
+        let json = r#"{ id: "t", name: "t", type: "jamo",
+            keymap: { "h": "0x3157", "k": "0x314F", "r": "0x3131" },
+            combinations: [
+                { first: "0x3157", second: "0x314F", result: "0x3158" },
+                { first: "0x3157", second: "0x314F", result: "0x3158" },
+                { first: "0x3157", second: "0x314F", result: "0x3133" },
+                { first: "0x3131", second: "0x314F", result: "0x3133" },
+                { first: "0x315C", second: "0x3153", result: "0x315D" },
+            ] }"#;
+        let diagnostics: Vec<_> = validate(json)
+            .into_iter()
+            .filter(|d| d.path.starts_with("combinations"))
+            .collect();
+        assert_eq!(
+            paths(&diagnostics),
+            [
+                (DiagnosticSeverity::Warning, "combinations[1]"),
+                (DiagnosticSeverity::Warning, "combinations[2]"),
+                (DiagnosticSeverity::Error, "combinations[2].result"),
+                (DiagnosticSeverity::Error, "combinations[3].second"),
+                (DiagnosticSeverity::Warning, "combinations[4]"),
+            ]
+        );
+        assert!(diagnostics[4].message.contains("U+315C"));
+    }
+
+    #[test]
+    fn test_duplicate_keys_and_coverage() {
+        let json = r#"{ id: "t", name: "t", type: "jamo",
+            keymap: { "a": "0x3131", "a": "0x3134" } }"#;
+        let diagnostics = validate(json);
+        assert_eq!(diagnostics[0].path, "keymap[\"a\"]");
+        assert!(diagnostics[0].message.contains("duplicate"));
+        // ㄱ, ㄴ 외의 현대 자모가 없다
+        let coverage: Vec<_> = diagnostics
+            .iter()
+            .filter(|d| d.path == "keymap" && d.message.starts_with("no key"))
+            .collect();
+        assert_eq!(coverage.len(), 3);
+        assert!(!coverage[0].message.contains("ㄴ (U+3134)"));
+    }
+}
@@ -1,12 +1,33 @@
 pub mod automata;
+pub mod convert;
 pub mod engine;
+pub mod english;
+pub mod event;
+pub mod golden;
+pub mod hanja;
+pub mod keyseq;
 pub mod layout;
+pub mod policy;
+pub mod record;
+pub mod toggle;
 pub mod unicode;
 
-use std::sync::{Mutex, MutexGuard};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard, mpsc};
+use std::time::Duration;
 
-use automata::AutomataResult;
-use engine::EngineState;
+use automata::{AutomataResult, AutomataSnapshot};
+use engine::{EngineState, HanjaTarget};
+use english::EnglishDetector;
+use event::{KeyAction, KeyEvent};
+use hanja::{HanjaDictionary, HanjaEntry};
+use layout::registry::{LayoutFileError, LayoutInfo, Registry};
+use layout::schema::LayoutType;
+use policy::PolicyState;
+use record::{Call, Output, Recorder};
+use toggle::{ModifierFlags, ModifierKey, ToggleAction, ToggleState};
 
 uniffi::setup_scaffolding!();
 
@@ -16,6 +37,24 @@ pub(crate) fn warn_unexpected(context: &str, detail: impl std::fmt::Debug) {
     eprintln!("[ongeul] {context}: {detail:?}");
 }
 
+/// JSON5 문자열 리터럴로 감싼다. 제어 문자는 `\\uXXXX`로 이스케이프한다.
+pub(crate) fn quote(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for ch in text.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
 /// 키 처리 결과 (UniFFI → Swift 전달용)
 #[derive(uniffi::Record, Debug, Clone)]
 pub struct ProcessResult {
@@ -25,6 +64,12 @@ pub struct ProcessResult {
     pub composing: Option<String>,
     /// 키가 처리되었는지 (false면 시스템에 위임)
     pub handled: bool,
+    /// `committed`를 넣기 전에 커서 앞에서 지울 확정 글자 수 (직접 확정 모드에서 사용)
+    #[uniffi(default = 0)]
+    pub delete_before: u32,
+    /// 입력 모드 전환 제안 (자동 한영 전환이 영어 단어를 감지하면 English)
+    #[uniffi(default = None)]
+    pub suggested_mode: Option<InputMode>,
 }
 
 impl From<AutomataResult> for ProcessResult {
@@ -33,15 +78,70 @@ impl From<AutomataResult> for ProcessResult {
             committed: r.committed,
             composing: r.composing,
             handled: r.handled,
+            delete_before: r.delete_before,
+            suggested_mode: None,
+        }
+    }
+}
+
+/// 키 이벤트 처리 결과 (UniFFI → Swift 전달용)
+#[derive(uniffi::Record, Debug, Clone)]
+pub struct KeyEventResult {
+    /// 프런트엔드가 할 일
+    pub action: KeyAction,
+    /// 엔진 처리 결과 (`ToggleMode`이면 현재 상태 그대로)
+    pub result: ProcessResult,
+}
+
+/// 한자 후보 (UniFFI → Swift 전달용)
+#[derive(uniffi::Record, Debug, Clone, PartialEq, Eq)]
+pub struct HanjaCandidate {
+    /// 한자
+    pub hanja: String,
+    /// 한글 읽기
+    pub reading: String,
+    /// 뜻 (사전에 없으면 빈 문자열)
+    pub meaning: String,
+}
+
+impl From<&HanjaEntry> for HanjaCandidate {
+    fn from(e: &HanjaEntry) -> Self {
+        HanjaCandidate {
+            hanja: e.hanja.clone(),
+            reading: e.reading.clone(),
+            meaning: e.meaning.clone(),
         }
     }
 }
 
+/// 한자 변환 결과 (UniFFI → Swift 전달용)
+#[derive(uniffi::Record, Debug, Clone, PartialEq, Eq)]
+pub struct HanjaConversion {
+    /// 삽입할 한자
+    pub hanja: String,
+    /// 변환된 한글 읽기
+    pub reading: String,
+    /// 삽입 전에 커서 앞에서 지울 확정 글자 수 (조합 중 음절을 변환했으면 0, 직접 확정 모드 제외)
+    pub delete_before: u32,
+}
+
 /// 엔진 에러 (UniFFI → Swift 전달용)
 #[derive(uniffi::Error, Debug, thiserror::Error)]
 pub enum EngineError {
+    /// 레이아웃 로드 실패 (종류와 위치는 [`layout::LayoutError`])
+    #[error("{error}")]
+    LayoutError { error: layout::LayoutError },
+    #[error("{message}")]
+    DictionaryError { message: String },
     #[error("{message}")]
-    LayoutError { message: String },
+    PolicyError { message: String },
+    #[error("{message}")]
+    SnapshotError { message: String },
+    #[error("{message}")]
+    RecordError { message: String },
+    /// 사용자 레이아웃 디렉터리를 읽을 수 없음
+    #[error("{message}")]
+    RegistryError { message: String },
 }
 
 /// 입력 모드 (UniFFI enum)
@@ -69,10 +169,90 @@ impl From<InputMode> for engine::InputMode {
     }
 }
 
+/// W3C `KeyboardEvent.code` 이름(예: "KeyQ")을 USB HID usage ID로 변환한다.
+/// [`HangulEngine::process_physical_key`]에 넘길 값을 구할 때 사용.
+#[uniffi::export]
+pub fn physical_key_usage(code: String) -> Option<u32> {
+    layout::physical::from_code(&code).map(|k| k.usage)
+}
+
+/// OS 키 이벤트의 문자열에서 엔진에 넘길 키 레이블을 추출한다. (한글 모드 CapsLock 보정)
+/// ASCII 문자/숫자/기호가 아니면 None.
+#[uniffi::export]
+pub fn key_label(characters: String, caps_lock: bool, shift: bool) -> Option<String> {
+    event::key_label(&characters, caps_lock, shift)
+}
+
+/// `extends`로 상속한 JSON5 레이아웃을 `extends` 없는 JSON5로 풀어 쓴다.
+/// 부모 레이아웃은 `layout_dirs`에서 차례로 `<ID>.json5` 파일로 찾는다.
+#[uniffi::export]
+pub fn flatten_layout(
+    json: String,
+    layout_dirs: Vec<String>,
+) -> Result<String, layout::LayoutError> {
+    let dirs = layout_dirs.into_iter().map(PathBuf::from).collect();
+    layout::LayoutResolver::new(dirs).flatten(&json)
+}
+
+/// libhangul 자판 XML(`hangul-keyboard-*.xml`)을 JSON5 레이아웃으로 가져온다.
+/// `<include>` 파일은 `include_dirs`에서 차례로 찾는다.
+#[uniffi::export]
+pub fn import_libhangul_keyboard(
+    xml: String,
+    include_dirs: Vec<String>,
+) -> Result<String, layout::LayoutError> {
+    let dirs: Vec<PathBuf> = include_dirs.into_iter().map(PathBuf::from).collect();
+    layout::libhangul::import_json5(&xml, &dirs)
+}
+
+/// JSON5 레이아웃을 libhangul 자판 XML로 내보낸다. 부모 레이아웃은 `layout_dirs`에서 찾는다.
+#[uniffi::export]
+pub fn export_libhangul_keyboard(
+    json: String,
+    layout_dirs: Vec<String>,
+) -> Result<String, layout::LayoutError> {
+    let dirs = layout_dirs.into_iter().map(PathBuf::from).collect();
+    let schema = layout::LayoutResolver::new(dirs).resolve(&json)?;
+    layout::libhangul::export(&schema)
+}
+
+/// JSON5 레이아웃을 검사해 진단 목록을 반환한다. (문제가 없으면 빈 목록)
+#[uniffi::export]
+pub fn validate_layout(json: String) -> Vec<layout::validate::LayoutDiagnostic> {
+    layout::validate::validate(&json)
+}
+
+/// 동봉 레이아웃 목록 (ID, 이름, 타입, 설명)
+#[uniffi::export]
+pub fn list_layouts() -> Vec<LayoutInfo> {
+    layout::registry::builtin_layouts()
+}
+
+/// 동봉 레이아웃의 JSON5와 latin 배열 여부
+fn builtin_layout(id: &str) -> Result<(String, bool), EngineError> {
+    let json = layout::registry::builtin_json(id).ok_or_else(|| EngineError::LayoutError {
+        error: layout::LayoutError::UnknownLayout { id: id.to_string() },
+    })?;
+    let layout = layout::KeyboardLayout::from_json(json)
+        .map_err(|error| EngineError::LayoutError { error })?;
+    Ok((json.to_string(), layout.layout_type == LayoutType::Latin))
+}
+
+/// 키 처리 결과를 클라이언트에 전달할 형태로 바꾼다. (직접 확정 모드 반영, 모드 전환 제안 포함)
+fn key_result(state: &mut EngineState, result: AutomataResult) -> ProcessResult {
+    let suggested_mode = state.take_suggested_mode().map(InputMode::from);
+    ProcessResult {
+        suggested_mode,
+        ..state.present(result).into()
+    }
+}
+
 /// 한글 입력 엔진 (UniFFI object, thread-safe)
 #[derive(uniffi::Object)]
 pub struct HangulEngine {
     state: Mutex<EngineState>,
+    /// 키 입력 기록 (버그 보고용, 기록 중이 아니면 None)
+    recorder: Mutex<Option<Recorder>>,
 }
 
 impl Default for HangulEngine {
@@ -82,6 +262,78 @@ impl Default for HangulEngine {
 }
 
 impl HangulEngine {
+    fn with_state(state: EngineState) -> Self {
+        HangulEngine {
+            state: Mutex::new(state),
+            recorder: Mutex::new(None),
+        }
+    }
+
+    fn lock_recorder(&self) -> MutexGuard<'_, Option<Recorder>> {
+        self.recorder.lock().unwrap_or_else(|e| {
+            warn_unexpected("Mutex poisoned", "recovering");
+            e.into_inner()
+        })
+    }
+
+    /// 기록 중이면 호출과 결과를 기록한다. 호출 정보는 기록할 때만 만든다.
+    ///
+    /// 여러 스레드에서 호출해도 기록 순서가 처리 순서와 같도록 상태 lock을 쥔 채 기록하고 놓는다.
+    /// lock 순서는 항상 상태 → 기록기다.
+    fn record(
+        &self,
+        _state: MutexGuard<'_, EngineState>,
+        call: impl FnOnce() -> Call,
+        out: Option<Output>,
+    ) {
+        if let Some(recorder) = self.lock_recorder().as_mut() {
+            recorder.record(call(), out);
+        }
+    }
+
+    /// 결과를 반환하는 호출을 기록하고 결과를 그대로 돌려준다.
+    fn recorded(
+        &self,
+        state: MutexGuard<'_, EngineState>,
+        call: impl FnOnce() -> Call,
+        result: ProcessResult,
+    ) -> ProcessResult {
+        self.record(state, call, Some(Output::from(&result)));
+        result
+    }
+
+    /// 영어 단어 감지기를 설정한다. 단어 목록 파일 없이 재현할 수 있도록 단어를 그대로 기록한다.
+    fn set_english_detector(&self, detector: EnglishDetector) {
+        let mut state = self.lock_state();
+        state.set_english_detector(Some(detector));
+        let detector = state.english_detector();
+        self.record(
+            state,
+            || Call::LoadEnglishWords {
+                words: detector.map(|d| d.words()).unwrap_or_default(),
+            },
+            None,
+        );
+    }
+
+    /// 기록된 단어로 자동 한영 전환을 켠다. (재현용)
+    pub(crate) fn load_english_words(&self, words: Vec<String>) {
+        self.set_english_detector(EnglishDetector::from_words(words));
+    }
+
+    /// 재현할 수 없는 호출 뒤에 엔진 상태를 스냅샷으로 기록한다.
+    fn record_state(&self, state: MutexGuard<'_, EngineState>) {
+        if let Some(recorder) = self.lock_recorder().as_mut() {
+            let snapshot = state.snapshot();
+            recorder.record(Call::Restore { snapshot }, None);
+        }
+    }
+
+    /// 오토마타 상태와 조합 버퍼 인덱스를 본다. (터미널 도구용, UniFFI로 내보내지 않는다)
+    pub fn automata_snapshot(&self) -> Option<AutomataSnapshot> {
+        self.lock_state().automata_snapshot()
+    }
+
     /// Mutex lock을 안전하게 획득한다.
     /// Poison 발생 시 상태를 리셋하고 복구한다.
     fn lock_state(&self) -> MutexGuard<'_, EngineState> {
@@ -99,9 +351,7 @@ impl HangulEngine {
     /// 새 엔진을 생성한다. (English 모드, 레이아웃 미로드)
     #[uniffi::constructor]
     pub fn new() -> Self {
-        HangulEngine {
-            state: Mutex::new(EngineState::new()),
-        }
+        Self::with_state(EngineState::new())
     }
 
     /// JSON5 문자열로 자판 레이아웃을 로드한다.
@@ -109,7 +359,38 @@ impl HangulEngine {
         let mut state = self.lock_state();
         state
             .load_layout(&json)
-            .map_err(|e| EngineError::LayoutError { message: e })
+            .map_err(|error| EngineError::LayoutError { error })?;
+        let id = state.layout_id().unwrap_or_default().to_string();
+        self.record(state, || Call::LoadLayout { id }, None);
+        Ok(())
+    }
+
+    /// 영문 모드에 적용할 배열(JSON5, `type: "latin"`)을 로드한다.
+    /// 영문 모드에서 Dvorak/Colemak 등을 입력할 수 있다. 키는 US QWERTY 레이블 기준이므로
+    /// 시스템 배열이 QWERTY가 아니면 [`process_physical_key`](Self::process_physical_key)를 사용한다.
+    pub fn load_english_layout(&self, json: String) -> Result<(), EngineError> {
+        let mut state = self.lock_state();
+        state
+            .load_english_layout(&json)
+            .map_err(|error| EngineError::LayoutError { error })?;
+        let id = state.english_layout_id().unwrap_or_default().to_string();
+        self.record(state, || Call::LoadEnglishLayout { id }, None);
+        Ok(())
+    }
+
+    /// 동봉 레이아웃을 ID로 로드한다. latin 배열은 영문 모드 배열로 로드한다.
+    pub fn load_builtin_layout(&self, id: String) -> Result<(), EngineError> {
+        match builtin_layout(&id)? {
+            (json, true) => self.load_english_layout(json),
+            (json, false) => self.load_layout(json),
+        }
+    }
+
+    /// 영문 모드 배열을 해제한다. 이후 영문 모드는 키 레이블을 그대로 입력한다.
+    pub fn clear_english_layout(&self) {
+        let mut state = self.lock_state();
+        state.clear_english_layout();
+        self.record(state, || Call::ClearEnglishLayout, None);
     }
 
     /// 입력 모드를 설정한다.
@@ -127,9 +408,11 @@ impl HangulEngine {
         let mut state = self.lock_state();
         if state.mode == engine::InputMode::Korean && mode == InputMode::English {
             // 한→영 전환 시 잔여 조합을 폐기한다 (committed는 호출자가 별도 flush로 이미 적용했어야 함 — 위 계약 참조).
-            let _ = state.flush();
+            let result = state.flush();
+            let _ = state.present(result);
         }
         state.mode = mode.into();
+        self.record(state, || Call::SetMode { mode: mode.into() }, None);
     }
 
     /// 현재 입력 모드를 반환한다.
@@ -141,8 +424,9 @@ impl HangulEngine {
     /// 입력 모드를 토글한다. flush 결과를 포함한 ProcessResult를 반환한다.
     pub fn toggle_mode(&self) -> ProcessResult {
         let mut state = self.lock_state();
-        if state.mode == engine::InputMode::Korean {
+        let result = if state.mode == engine::InputMode::Korean {
             let result = state.flush();
+            let result = state.present(result);
             state.mode = engine::InputMode::English;
             result.into()
         } else {
@@ -151,31 +435,810 @@ impl HangulEngine {
                 committed: None,
                 composing: None,
                 handled: true,
+                delete_before: 0,
+                suggested_mode: None,
             }
-        }
+        };
+        self.recorded(state, || Call::ToggleMode, result)
     }
 
     /// 키 레이블을 처리한다. (예: "q", "Q", "k")
     pub fn process_key(&self, key: String) -> ProcessResult {
         let mut state = self.lock_state();
-        state.process_key(&key).into()
+        let result = state.process_key(&key);
+        let result = key_result(&mut state, result);
+        self.recorded(state, || Call::ProcessKey { key }, result)
+    }
+
+    /// 물리 키를 처리한다. `usage`는 USB HID Keyboard page usage ID (예: Q 위치 = 0x14).
+    ///
+    /// 시스템 영문 배열(Dvorak, Colemak, AZERTY 등)과 무관하게 레이아웃의 두벌식/세벌식 위치를 적용한다.
+    /// 영문 모드에서는 영문 배열([`load_english_layout`](Self::load_english_layout))이 있으면 그 배열을 적용하고,
+    /// 없으면 handled = false를 반환하므로 호출자는 키 레이블 경로([`process_key`](Self::process_key))나
+    /// 시스템 입력으로 넘긴다.
+    pub fn process_physical_key(&self, usage: u32, shift: bool) -> ProcessResult {
+        let mut state = self.lock_state();
+        let result = state.process_physical_key(usage, shift);
+        let result = key_result(&mut state, result);
+        self.recorded(state, || Call::ProcessPhysicalKey { usage, shift }, result)
+    }
+
+    /// 플랫폼 중립 키 누름 이벤트를 라우팅하고 처리한다.
+    ///
+    /// Backspace·Enter·Escape·단축키·방향키·CapsLock 보정을 엔진이 판단하므로 프런트엔드는
+    /// `action`에 따라 `result`를 적용하기만 한다. `shift_space_toggle`은 Shift+Space가 한/영 전환 키인지 여부.
+    pub fn handle_event(&self, event: KeyEvent, shift_space_toggle: bool) -> KeyEventResult {
+        let mut state = self.lock_state();
+        let (action, result) = state.handle_event(&event, shift_space_toggle);
+        let result = key_result(&mut state, result);
+        self.record(
+            state,
+            || Call::HandleEvent {
+                event,
+                shift_space_toggle,
+            },
+            Some(Output::new(Some(action), &result)),
+        );
+        KeyEventResult { action, result }
+    }
+
+    /// 키 누름 이벤트를 처리한다. `timestamp_ms`는 단조 증가하는 밀리초 시각.
+    ///
+    /// 세벌식 레이아웃에서는 허용 시간 안에 겹쳐 눌린 키를 모아치기 묶음으로 모았다가,
+    /// 모두 떼었을 때([`key_up`](Self::key_up)) 누른 순서와 무관하게 한 음절로 조합한다.
+    pub fn key_down(&self, key: String, timestamp_ms: u64) -> ProcessResult {
+        let mut state = self.lock_state();
+        let result = state.key_down(&key, timestamp_ms);
+        let result = key_result(&mut state, result);
+        self.recorded(state, || Call::KeyDown { key, timestamp_ms }, result)
+    }
+
+    /// 키 뗌 이벤트를 처리한다.
+    pub fn key_up(&self, key: String, timestamp_ms: u64) -> ProcessResult {
+        let mut state = self.lock_state();
+        let result = state.key_up(&key, timestamp_ms);
+        let result = key_result(&mut state, result);
+        self.recorded(state, || Call::KeyUp { key, timestamp_ms }, result)
+    }
+
+    /// 모아치기 허용 시간(밀리초)을 설정한다. 0이면 모아치기를 끈다.
+    pub fn set_chord_window(&self, window_ms: u64) {
+        let mut state = self.lock_state();
+        state.set_chord_window(window_ms);
+        self.record(state, || Call::SetChordWindow { window_ms }, None);
     }
 
     /// 백스페이스 처리 (오토마타 한 단계 되돌림)
     pub fn backspace(&self) -> ProcessResult {
         let mut state = self.lock_state();
-        state.backspace().into()
+        let result = state.backspace();
+        let result = state.present(result).into();
+        self.recorded(state, || Call::Backspace, result)
+    }
+
+    /// 커서 앞 텍스트를 참고하는 백스페이스.
+    /// 조합이 비어 있으면 커서 앞 음절을 다시 조합으로 되살려 자모 하나만 지운다.
+    /// 이때 `delete_before`가 1이 되므로 클라이언트는 앞 글자를 지우고 조합 텍스트를 표시한다.
+    pub fn backspace_with_context(&self, text_before_cursor: String) -> ProcessResult {
+        let mut state = self.lock_state();
+        let result = state.backspace_with_context(&text_before_cursor);
+        let result = state.present(result).into();
+        // 엔진은 커서 앞 마지막 글자만 쓰므로 그 글자만 기록한다
+        let text = || {
+            let text = text_before_cursor.chars().last().map(String::from);
+            Call::BackspaceWithContext {
+                text: text.unwrap_or_default(),
+            }
+        };
+        self.recorded(state, text, result)
     }
 
     /// 현재 조합을 확정한다.
     pub fn flush(&self) -> ProcessResult {
         let mut state = self.lock_state();
-        state.flush().into()
+        let result = state.flush();
+        let result = state.present(result).into();
+        self.recorded(state, || Call::Flush, result)
+    }
+
+    /// 직접 확정 모드를 켜거나 끈다. marked text(preedit)가 깨지는 터미널·원격 데스크톱 앱용.
+    ///
+    /// 켜져 있으면 조합 중인 글자도 `committed`로 바로 보내고 `composing`은 항상 None이다.
+    /// 글자가 바뀌면 호출자는 커서 앞 `delete_before`글자를 지운 뒤 `committed`를 넣는다.
+    /// 전환 전의 조합은 확정되어 반환된다.
+    pub fn set_direct_commit(&self, enabled: bool) -> ProcessResult {
+        let mut state = self.lock_state();
+        let result = state.set_direct_commit(enabled).into();
+        self.recorded(state, || Call::SetDirectCommit { enabled }, result)
     }
 
     /// 현재 조합을 폐기한다.
     pub fn reset(&self) {
         let mut state = self.lock_state();
         state.reset();
+        self.record(state, || Call::Reset, None);
+    }
+
+    /// 포커스를 잃을 때 호출한다. 조합 중인 글자는 남겨 두고 `composing`이 None인 결과를 반환하므로
+    /// 호출자는 marked text를 지우고 `committed`만 적용한다. 직접 확정 모드에서는 조합을 확정한다.
+    pub fn suspend(&self) -> ProcessResult {
+        let mut state = self.lock_state();
+        let result = state.suspend().into();
+        self.recorded(state, || Call::Suspend, result)
+    }
+
+    /// 포커스를 다시 얻을 때 호출한다. 남겨 둔 조합 중 텍스트를 `composing`으로 반환한다.
+    pub fn resume(&self) -> ProcessResult {
+        let state = self.lock_state();
+        let result = state.resume().into();
+        self.recorded(state, || Call::Resume, result)
+    }
+
+    /// 입력 모드와 조합 상태(조합 버퍼, 백스페이스 복원 정보)를 버전이 붙은 JSON 문자열로 저장한다.
+    /// 입력기 프로세스가 다시 시작될 때 조합을 이어 가거나, 버그 보고에 오토마타 상태를 첨부할 때 쓴다.
+    pub fn snapshot(&self) -> String {
+        self.lock_state().snapshot()
+    }
+
+    /// [`snapshot`](Self::snapshot)으로 저장한 상태를 되살린다. 같은 자판 레이아웃이 로드되어 있어야 한다.
+    /// 되살린 조합 중 텍스트를 `composing`으로 반환하므로 호출자는 marked text로 표시한다.
+    pub fn restore(&self, snapshot: String) -> Result<ProcessResult, EngineError> {
+        let mut state = self.lock_state();
+        let result = state
+            .restore(&snapshot)
+            .map(ProcessResult::from)
+            .map_err(|e| EngineError::SnapshotError { message: e })?;
+        self.record(state, || Call::Restore { snapshot }, None);
+        Ok(result)
+    }
+
+    /// 키 입력 기록을 시작한다. (버그 보고용, 기본은 꺼짐)
+    ///
+    /// 키 처리·백스페이스·확정·모드 전환 호출과 결과, 레이아웃 ID와 자동 한영 전환 설정을
+    /// 시각과 함께 `path`에 JSON Lines로 기록한다. 기록은 `ongeul-replay`로 현재 엔진에서 재현할 수 있다.
+    /// 이미 기록 중이면 새 파일로 바꾼다. 시작 시점의 엔진 상태도 함께 기록한다.
+    pub fn start_recording(&self, path: String) -> Result<(), EngineError> {
+        let recorder = Recorder::create(std::path::Path::new(&path))
+            .map_err(|e| EngineError::RecordError { message: e })?;
+        let state = self.lock_state();
+        let layouts = [
+            state
+                .layout_id()
+                .map(|id| Call::LoadLayout { id: id.to_string() }),
+            state
+                .english_layout_id()
+                .map(|id| Call::LoadEnglishLayout { id: id.to_string() }),
+        ];
+        // 자동 한영 전환 설정은 스냅샷에 없으므로 따로 기록한다
+        let detection = [
+            state
+                .english_detector()
+                .map(|d| Call::LoadEnglishWords { words: d.words() }),
+            (state.english_detection_threshold() != english::DEFAULT_THRESHOLD).then(|| {
+                Call::SetEnglishDetectionThreshold {
+                    threshold: state.english_detection_threshold(),
+                }
+            }),
+            (!state.english_detection_exceptions().is_empty()).then(|| {
+                Call::SetEnglishDetectionExceptions {
+                    words: state.english_detection_exceptions().to_vec(),
+                }
+            }),
+        ];
+        let snapshot = state.snapshot();
+
+        // 시작 상태와 첫 호출 사이에 다른 호출이 끼지 않도록 상태 lock을 쥔 채 기록기를 바꾼다
+        let mut slot = self.lock_recorder();
+        let recorder = slot.insert(recorder);
+        for call in layouts.into_iter().chain(detection).flatten() {
+            recorder.record(call, None);
+        }
+        recorder.record(Call::Restore { snapshot }, None);
+        Ok(())
+    }
+
+    /// 키 입력 기록을 멈춘다.
+    pub fn stop_recording(&self) {
+        self.lock_recorder().take();
+    }
+
+    /// 기록에서 제외할 구간(보안 입력 등)을 시작하거나 끝낸다.
+    /// 제외 구간에서는 아무것도 기록하지 않고, 끝날 때 엔진 상태만 스냅샷으로 기록한다.
+    pub fn set_recording_excluded(&self, excluded: bool) {
+        let state = self.lock_state();
+        if let Some(recorder) = self.lock_recorder().as_mut() {
+            recorder.set_excluded(excluded, || state.snapshot());
+        }
+    }
+
+    /// 자동 한영 전환용 영어 단어 목록 파일(한 줄에 한 단어)을 로드해 감지를 켠다.
+    ///
+    /// 한글 모드에서 단어가 끝날 때(공백, 기호, 시스템에 넘기는 키) 그 단어가 영어로 보이면
+    /// 확정된 한글을 `delete_before`로 지우고 영어로 바꾼 결과와 함께 `suggested_mode`를 반환한다.
+    pub fn load_english_word_list(&self, path: String) -> Result<(), EngineError> {
+        let detector = EnglishDetector::load(std::path::Path::new(&path))
+            .map_err(|e| EngineError::DictionaryError { message: e })?;
+        self.set_english_detector(detector);
+        Ok(())
+    }
+
+    /// 자동 한영 전환을 끈다.
+    pub fn clear_english_word_list(&self) {
+        let mut state = self.lock_state();
+        state.set_english_detector(None);
+        self.record(state, || Call::ClearEnglishWordList, None);
+    }
+
+    /// 자동 한영 전환 임계값(0.0 ~ 1.0, 기본 0.6)을 설정한다. 높을수록 덜 바꾼다.
+    /// 단어 목록 로드 전후 어느 때나 호출할 수 있다.
+    pub fn set_english_detection_threshold(&self, threshold: f32) {
+        let mut state = self.lock_state();
+        state.set_english_detection_threshold(threshold);
+        self.record(
+            state,
+            || Call::SetEnglishDetectionThreshold { threshold },
+            None,
+        );
+    }
+
+    /// 자동 한영 전환에서 영어로 바꾸지 않을 단어 목록을 설정한다.
+    /// 단어 목록 로드 전후 어느 때나 호출할 수 있다.
+    pub fn set_english_detection_exceptions(&self, words: Vec<String>) {
+        let mut state = self.lock_state();
+        state.set_english_detection_exceptions(words.clone());
+        self.record(
+            state,
+            || Call::SetEnglishDetectionExceptions { words },
+            None,
+        );
+    }
+
+    /// 한자 사전 파일(`읽기:한자:뜻` 형식)을 로드한다.
+    pub fn load_hanja_dictionary(&self, path: String) -> Result<(), EngineError> {
+        let dict = HanjaDictionary::load(std::path::Path::new(&path))
+            .map_err(|e| EngineError::DictionaryError { message: e })?;
+        let mut state = self.lock_state();
+        state.set_hanja_dictionary(dict);
+        Ok(())
+    }
+
+    /// 한글 읽기에 해당하는 한자 후보를 반환한다. (사전 미로드 시 빈 목록)
+    pub fn hanja_candidates(&self, text: String) -> Vec<HanjaCandidate> {
+        let state = self.lock_state();
+        state
+            .hanja_candidates(&text)
+            .iter()
+            .map(HanjaCandidate::from)
+            .collect()
+    }
+
+    /// 한자 변환 대상 읽기를 반환한다.
+    /// 조합 중인 음절이 우선이며, 없으면 방금 확정된 단어 중 사전에 있는 가장 긴 접미사.
+    pub fn hanja_conversion_target(&self) -> Option<String> {
+        let state = self.lock_state();
+        state.hanja_target().map(|target| match target {
+            HanjaTarget::Composing(text) | HanjaTarget::Committed(text) => text,
+        })
+    }
+
+    /// 변환 대상([`hanja_conversion_target`](Self::hanja_conversion_target))을
+    /// `index`번째 후보로 바꾼다. 대상이나 후보가 없으면 None.
+    ///
+    /// 조합 중 음절은 폐기되므로 호출자는 marked text를 지우고 `hanja`를 삽입한다.
+    /// 확정 단어를 변환했으면 먼저 커서 앞 `delete_before`글자를 지운다.
+    pub fn convert_composing_to_hanja(&self, index: u32) -> Option<HanjaConversion> {
+        let mut state = self.lock_state();
+        let (target, entry) = state.convert_to_hanja(index as usize)?;
+        let delete_before = match &target {
+            // 직접 확정 모드에서는 조합 중 음절도 이미 문서에 들어가 있다
+            HanjaTarget::Composing(word) if state.direct_commit() => word.chars().count() as u32,
+            HanjaTarget::Composing(_) => 0,
+            HanjaTarget::Committed(word) => word.chars().count() as u32,
+        };
+        // 한자 사전 없이 재현할 수 있도록 변환 뒤 상태를 기록한다
+        self.record_state(state);
+        Some(HanjaConversion {
+            hanja: entry.hanja,
+            reading: entry.reading,
+            delete_before,
+        })
+    }
+
+    /// 영문 모드로 잘못 친 문자열을 한글로 바꾼다. ("dkssudgktpdy" → "안녕하세요")
+    ///
+    /// 현재 한글 배열로 키를 다시 입력한 결과이며 조합 상태는 바뀌지 않는다.
+    /// 영문 배열이 설정되어 있으면 그 배열로 입력된 글자를 먼저 키 레이블로 되돌린다.
+    pub fn convert_to_hangul(&self, text: String) -> String {
+        self.lock_state().convert_to_hangul(&text)
+    }
+
+    /// 한글 모드로 잘못 친 문자열을 영문으로 되돌린다. ("안녕하세요" → "dkssudgktpdy")
+    ///
+    /// 한글 배열의 역 키맵으로 키 레이블을 구한 뒤 영문 배열이 있으면 그 배열로 바꾼다.
+    /// 키로 입력할 수 없는 글자는 그대로 둔다.
+    pub fn convert_to_latin(&self, text: String) -> String {
+        self.lock_state().convert_to_latin(&text)
+    }
+}
+
+/// 클라이언트별 조합 세션 관리자 (UniFFI object, thread-safe)
+///
+/// 창·입력 필드마다 [`HangulEngine`] 세션을 따로 두어, 다른 창으로 옮겨 갔다 돌아와도
+/// 조합 중이던 글자를 이어서 입력할 수 있다. 세션은 한 번 컴파일한 레이아웃과 사전을 공유하며,
+/// 관리자에서 바꾼 레이아웃·사전·설정은 모든 세션에 적용된다.
+#[derive(uniffi::Object)]
+pub struct SessionManager {
+    /// 새 세션의 원본 (레이아웃·사전·설정만 보관, 조합하지 않음)
+    template: Mutex<EngineState>,
+    sessions: Mutex<HashMap<String, Arc<HangulEngine>>>,
+}
+
+impl Default for SessionManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SessionManager {
+    fn lock_template(&self) -> MutexGuard<'_, EngineState> {
+        self.template.lock().unwrap_or_else(|e| {
+            warn_unexpected("Mutex poisoned", "recovering");
+            e.into_inner()
+        })
+    }
+
+    fn lock_sessions(&self) -> MutexGuard<'_, HashMap<String, Arc<HangulEngine>>> {
+        self.sessions.lock().unwrap_or_else(|e| {
+            warn_unexpected("Mutex poisoned", "recovering");
+            e.into_inner()
+        })
+    }
+
+    /// 원본 설정을 바꾸고 모든 세션에 적용한다. 실패하면 아무 세션도 바꾸지 않는다.
+    fn try_configure<E>(&self, f: impl FnOnce(&mut EngineState) -> Result<(), E>) -> Result<(), E> {
+        let mut template = self.lock_template();
+        f(&mut template)?;
+        for session in self.lock_sessions().values() {
+            session.lock_state().share_config(&template);
+        }
+        Ok(())
+    }
+
+    /// 원본 설정을 바꾸고 모든 세션에 적용한다.
+    fn configure(&self, f: impl FnOnce(&mut EngineState)) {
+        let _ = self.try_configure(|state| {
+            f(state);
+            Ok::<(), std::convert::Infallible>(())
+        });
+    }
+}
+
+#[uniffi::export]
+impl SessionManager {
+    /// 세션이 없는 관리자를 만든다. (English 모드, 레이아웃 미로드)
+    #[uniffi::constructor]
+    pub fn new() -> Self {
+        SessionManager {
+            template: Mutex::new(EngineState::new()),
+            sessions: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// `session_id`의 세션을 반환한다. 없으면 공유 레이아웃으로 새로 만든다.
+    pub fn session(&self, session_id: String) -> Arc<HangulEngine> {
+        let template = self.lock_template();
+        self.lock_sessions()
+            .entry(session_id)
+            .or_insert_with(|| Arc::new(HangulEngine::with_state(template.new_session())))
+            .clone()
+    }
+
+    /// 세션이 포커스를 잃을 때 호출한다. ([`HangulEngine::suspend`]) 세션이 없으면 None.
+    pub fn suspend(&self, session_id: String) -> Option<ProcessResult> {
+        let session = self.lock_sessions().get(&session_id).cloned()?;
+        Some(session.suspend())
+    }
+
+    /// 세션이 포커스를 얻을 때 호출한다. 세션이 없으면 새로 만들고,
+    /// 남겨 둔 조합 중 텍스트를 반환한다. ([`HangulEngine::resume`])
+    pub fn resume(&self, session_id: String) -> ProcessResult {
+        self.session(session_id).resume()
+    }
+
+    /// 세션을 닫는다. 조합 중이던 글자는 폐기된다. 세션이 있었으면 true.
+    pub fn drop_session(&self, session_id: String) -> bool {
+        self.lock_sessions().remove(&session_id).is_some()
+    }
+
+    /// 열린 세션 수
+    pub fn session_count(&self) -> u32 {
+        self.lock_sessions().len() as u32
+    }
+
+    /// 모든 세션의 한글 자판 레이아웃(JSON5)을 바꾼다. 조합 중이던 글자는 폐기된다.
+    pub fn load_layout(&self, json: String) -> Result<(), EngineError> {
+        self.try_configure(|state| state.load_layout(&json))
+            .map_err(|error| EngineError::LayoutError { error })
+    }
+
+    /// 모든 세션의 영문 모드 배열(JSON5, `type: "latin"`)을 바꾼다.
+    pub fn load_english_layout(&self, json: String) -> Result<(), EngineError> {
+        self.try_configure(|state| state.load_english_layout(&json))
+            .map_err(|error| EngineError::LayoutError { error })
+    }
+
+    /// 모든 세션에 동봉 레이아웃을 ID로 로드한다. latin 배열은 영문 모드 배열로 로드한다.
+    pub fn load_builtin_layout(&self, id: String) -> Result<(), EngineError> {
+        match builtin_layout(&id)? {
+            (json, true) => self.load_english_layout(json),
+            (json, false) => self.load_layout(json),
+        }
+    }
+
+    /// 모든 세션의 영문 모드 배열을 해제한다.
+    pub fn clear_english_layout(&self) {
+        self.configure(|state| {
+            state.clear_english_layout();
+        });
+    }
+
+    /// 모든 세션의 모아치기 허용 시간(밀리초)을 설정한다.
+    pub fn set_chord_window(&self, window_ms: u64) {
+        self.configure(|state| {
+            state.set_chord_window(window_ms);
+        });
+    }
+
+    /// 한자 사전 파일을 로드해 모든 세션이 공유한다.
+    pub fn load_hanja_dictionary(&self, path: String) -> Result<(), EngineError> {
+        let dict = HanjaDictionary::load(std::path::Path::new(&path))
+            .map_err(|e| EngineError::DictionaryError { message: e })?;
+        self.configure(|state| {
+            state.set_hanja_dictionary(dict);
+        });
+        Ok(())
+    }
+
+    /// 자동 한영 전환용 영어 단어 목록을 로드해 모든 세션이 공유한다.
+    pub fn load_english_word_list(&self, path: String) -> Result<(), EngineError> {
+        let detector = EnglishDetector::load(std::path::Path::new(&path))
+            .map_err(|e| EngineError::DictionaryError { message: e })?;
+        self.configure(|state| {
+            state.set_english_detector(Some(detector));
+        });
+        Ok(())
+    }
+
+    /// 모든 세션의 자동 한영 전환을 끈다.
+    pub fn clear_english_word_list(&self) {
+        self.configure(|state| {
+            state.set_english_detector(None);
+        });
+    }
+
+    /// 모든 세션의 자동 한영 전환 임계값(0.0 ~ 1.0)을 설정한다. 단어 목록 로드 전후 어느 때나 호출할 수 있다.
+    pub fn set_english_detection_threshold(&self, threshold: f32) {
+        self.configure(|state| {
+            state.set_english_detection_threshold(threshold);
+        });
+    }
+
+    /// 모든 세션의 자동 한영 전환 예외 단어 목록을 설정한다. 단어 목록 로드 전후 어느 때나 호출할 수 있다.
+    pub fn set_english_detection_exceptions(&self, words: Vec<String>) {
+        self.configure(|state| {
+            state.set_english_detection_exceptions(words);
+        });
+    }
+}
+
+/// 한영 전환 키 감지기 (UniFFI object, thread-safe)
+///
+/// modifier 탭(500ms 이내 누름→뗌)과 4키(좌우 Command + 좌우 Option) English Lock을 감지한다.
+/// 시각은 호출자가 단조 증가하는 밀리초로 넘긴다.
+#[derive(uniffi::Object, Default)]
+pub struct ToggleDetector {
+    state: Mutex<ToggleState>,
+}
+
+impl ToggleDetector {
+    fn lock_state(&self) -> MutexGuard<'_, ToggleState> {
+        self.state.lock().unwrap_or_else(|e| {
+            warn_unexpected("Mutex poisoned", "recovering");
+            let mut guard = e.into_inner();
+            *guard = ToggleState::default();
+            guard
+        })
+    }
+}
+
+#[uniffi::export]
+impl ToggleDetector {
+    #[uniffi::constructor]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 일반 키 입력 시 호출하여 진행 중인 감지 상태를 초기화한다.
+    pub fn cancel_on_key_down(&self) {
+        self.lock_state().cancel_on_key_down();
+    }
+
+    /// modifier 변화 이벤트를 처리하여 수행할 동작을 반환한다.
+    ///
+    /// `toggle_key`는 탭으로 전환할 modifier 키. Shift+Space나 CapsLock처럼 modifier 탭이 아닌
+    /// 전환 방식이면 None을 넘기며, 이때도 4키 English Lock은 감지한다.
+    pub fn handle_flags_changed(
+        &self,
+        key: ModifierKey,
+        flags: ModifierFlags,
+        toggle_key: Option<ModifierKey>,
+        timestamp_ms: u64,
+    ) -> ToggleAction {
+        self.lock_state()
+            .handle_flags_changed(key, flags, toggle_key, timestamp_ms)
+    }
+}
+
+/// 앱별 입력 모드 정책 (UniFFI object, thread-safe)
+///
+/// 앱별 마지막 모드, English Lock, 앱별 기본 모드를 관리한다. 파일로 열었으면
+/// 상태가 바뀐 뒤 잠시 모아서 저장 스레드가 JSON5 파일에 쓴다. (키 입력 경로에서는 쓰지 않는다)
+#[derive(uniffi::Object)]
+pub struct ModePolicy {
+    store: Arc<PolicyStore>,
+    /// 저장 스레드에 보내는 저장 요청 (메모리 정책이면 None)
+    saver: Option<mpsc::Sender<()>>,
+    saver_thread: Option<std::thread::JoinHandle<()>>,
+}
+
+/// 바뀐 정책을 모아 쓰기까지 기다리는 시간
+const POLICY_SAVE_DELAY: Duration = Duration::from_millis(500);
+
+/// 정책 상태와 저장 파일 (저장 스레드와 공유)
+struct PolicyStore {
+    /// 저장 파일 (None이면 메모리에만 보관)
+    path: Option<PathBuf>,
+    state: Mutex<PolicyState>,
+    /// 마지막으로 쓴 내용 (바뀌지 않았으면 쓰지 않는다)
+    written: Mutex<Option<String>>,
+}
+
+impl PolicyStore {
+    fn lock_state(&self) -> MutexGuard<'_, PolicyState> {
+        self.state.lock().unwrap_or_else(|e| {
+            warn_unexpected("Mutex poisoned", "recovering");
+            e.into_inner()
+        })
+    }
+
+    /// 현재 상태를 파일에 쓴다. 저장은 한 번에 하나씩 하므로 늦게 뜬 상태가 나중 상태를 덮지 않는다.
+    fn save(&self) -> Result<(), String> {
+        let Some(path) = &self.path else {
+            return Err("mode policy is not backed by a file".to_string());
+        };
+        let mut written = self.written.lock().unwrap_or_else(|e| e.into_inner());
+        let text = self.lock_state().to_json5();
+        if written.as_deref() == Some(text.as_str()) {
+            return Ok(());
+        }
+        let save = || -> std::io::Result<()> {
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            // 쓰는 도중 종료되어도 기존 파일이 깨지지 않도록 임시 파일에 쓴 뒤 교체한다
+            let tmp = path.with_extension("json5.tmp");
+            std::fs::write(&tmp, &text)?;
+            std::fs::rename(&tmp, path)
+        };
+        save().map_err(|e| format!("{}: {e}", path.display()))?;
+        *written = Some(text);
+        Ok(())
+    }
+
+    fn save_logged(&self) {
+        if let Err(e) = self.save() {
+            warn_unexpected("Cannot save mode policy", e);
+        }
+    }
+}
+
+impl ModePolicy {
+    fn with_store(store: PolicyStore) -> Self {
+        let store = Arc::new(store);
+        let (saver, saver_thread) = if store.path.is_some() {
+            let (tx, rx) = mpsc::channel::<()>();
+            let thread_store = Arc::clone(&store);
+            let thread = std::thread::Builder::new()
+                .name("ongeul-policy-save".to_string())
+                .spawn(move || {
+                    while rx.recv().is_ok() {
+                        // 연달아 바뀌면 잠잠해질 때까지 모아서 한 번에 쓴다
+                        while let Ok(()) = rx.recv_timeout(POLICY_SAVE_DELAY) {}
+                        thread_store.save_logged();
+                    }
+                    // 정책을 닫을 때 남은 변경을 쓴다
+                    thread_store.save_logged();
+                });
+            match thread {
+                Ok(thread) => (Some(tx), Some(thread)),
+                Err(e) => {
+                    warn_unexpected("Cannot start mode policy saver", e);
+                    (None, None)
+                }
+            }
+        } else {
+            (None, None)
+        };
+        ModePolicy {
+            store,
+            saver,
+            saver_thread,
+        }
+    }
+
+    fn lock_state(&self) -> MutexGuard<'_, PolicyState> {
+        self.store.lock_state()
+    }
+
+    /// 상태를 바꾸고 저장을 예약한다. 저장 스레드가 없으면 바로 쓴다.
+    fn update<T>(&self, f: impl FnOnce(&mut PolicyState) -> T) -> T {
+        let value = f(&mut self.lock_state());
+        match &self.saver {
+            Some(saver) => {
+                let _ = saver.send(());
+            }
+            None if self.store.path.is_some() => self.store.save_logged(),
+            None => {}
+        }
+        value
+    }
+}
+
+impl Drop for ModePolicy {
+    fn drop(&mut self) {
+        // 요청 채널을 닫으면 저장 스레드가 남은 변경을 쓰고 끝난다
+        self.saver.take();
+        if let Some(thread) = self.saver_thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[uniffi::export]
+impl ModePolicy {
+    /// 메모리에만 보관하는 빈 정책을 만든다.
+    #[uniffi::constructor]
+    pub fn new() -> Self {
+        Self::with_store(PolicyStore {
+            path: None,
+            state: Mutex::new(PolicyState::default()),
+            written: Mutex::new(None),
+        })
+    }
+
+    /// 정책 파일을 연다. 파일이 없으면 빈 정책으로 시작하고 처음 바뀔 때 만든다.
+    #[uniffi::constructor]
+    pub fn open(path: String) -> Result<Arc<Self>, EngineError> {
+        let path = PathBuf::from(path);
+        let state = if path.exists() {
+            PolicyState::load(&path).map_err(|e| EngineError::PolicyError { message: e })?
+        } else {
+            PolicyState::default()
+        };
+        Ok(Arc::new(Self::with_store(PolicyStore {
+            path: Some(path),
+            state: Mutex::new(state),
+            written: Mutex::new(None),
+        })))
+    }
+
+    /// 공유 설정 파일(같은 형식)의 잠금 목록과 기본 모드 규칙을 합친다. 공유 규칙은 저장하지 않는다.
+    pub fn load_shared(&self, path: String) -> Result<(), EngineError> {
+        let shared = PolicyState::load(std::path::Path::new(&path))
+            .map_err(|e| EngineError::PolicyError { message: e })?;
+        self.lock_state().merge_shared(shared);
+        Ok(())
+    }
+
+    /// 앱이 활성화될 때 적용할 모드를 반환한다. None이면 현재 모드를 유지한다.
+    /// 잠긴 앱은 English, 그 밖에는 마지막 모드 → 기본 모드 규칙 → `default_mode` 순.
+    pub fn activate_app(&self, bundle_id: String) -> Option<InputMode> {
+        self.lock_state()
+            .activate_app(&bundle_id)
+            .map(InputMode::from)
+    }
+
+    /// 앱의 마지막 모드 (기억된 값이 없으면 None)
+    pub fn last_mode(&self, bundle_id: String) -> Option<InputMode> {
+        self.lock_state().last_mode(&bundle_id).map(InputMode::from)
+    }
+
+    /// 앱의 현재 모드를 기억한다. (잠긴 앱은 무시)
+    pub fn save_mode(&self, bundle_id: String, mode: InputMode) {
+        self.update(|state| state.save_mode(&bundle_id, mode.into()));
+    }
+
+    /// English Lock 여부
+    pub fn is_locked(&self, bundle_id: String) -> bool {
+        self.lock_state().is_locked(&bundle_id)
+    }
+
+    /// 앱을 잠근다. `current_mode`는 잠금 해제 시 되돌릴 모드.
+    pub fn lock(&self, bundle_id: String, current_mode: InputMode) {
+        self.update(|state| state.lock(&bundle_id, current_mode.into()));
+    }
+
+    /// 앱의 잠금을 해제하고 적용할 모드를 반환한다.
+    pub fn unlock(&self, bundle_id: String) -> InputMode {
+        self.update(|state| state.unlock(&bundle_id)).into()
+    }
+
+    /// 잠금 해제 시 잠그기 전 모드로 되돌릴지 설정한다. (false면 한글)
+    pub fn set_restore_on_unlock(&self, enabled: bool) {
+        self.update(|state| state.set_restore_on_unlock(enabled));
+    }
+
+    /// 기다리는 변경을 바로 파일에 쓴다. 파일 없이 만든 정책이거나 쓰지 못하면 에러.
+    /// 다른 설정을 지우기 전에 정책이 저장되었는지 확인할 때 쓴다.
+    pub fn save(&self) -> Result<(), EngineError> {
+        self.store
+            .save()
+            .map_err(|message| EngineError::PolicyError { message })
+    }
+
+    /// 사용자 정책을 파일 형식(JSON5)으로 내보낸다. (백업용, 공유 규칙 제외)
+    pub fn export(&self) -> String {
+        self.lock_state().to_json5()
+    }
+}
+
+impl Default for ModePolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 동봉 레이아웃과 사용자 레이아웃 목록 (UniFFI object, thread-safe)
+///
+/// 사용자 레이아웃 디렉터리를 등록하면 동봉 레이아웃 뒤에 이어 나열한다.
+/// 로드할 JSON5는 [`layout_json`](Self::layout_json)으로 얻어 `load_layout` 등에 넘긴다.
+#[derive(uniffi::Object, Default)]
+pub struct LayoutRegistry {
+    state: Mutex<Registry>,
+}
+
+impl LayoutRegistry {
+    fn lock_state(&self) -> MutexGuard<'_, Registry> {
+        self.state.lock().unwrap_or_else(|e| {
+            warn_unexpected("Mutex poisoned", "recovering");
+            e.into_inner()
+        })
+    }
+}
+
+#[uniffi::export]
+impl LayoutRegistry {
+    /// 동봉 레이아웃만 있는 목록을 만든다.
+    #[uniffi::constructor]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 동봉 레이아웃, 그다음 등록한 사용자 레이아웃 (등록 순)
+    pub fn layouts(&self) -> Vec<LayoutInfo> {
+        self.lock_state().list()
+    }
+
+    /// 디렉터리의 `*.json5` 레이아웃을 등록하고, 등록하지 못한 파일과 에러를 반환한다.
+    /// `extends`는 같은 디렉터리와 동봉 레이아웃에서 찾으며, 같은 디렉터리를 다시 등록하면 새로 읽는다.
+    pub fn register_dir(&self, dir: String) -> Result<Vec<LayoutFileError>, EngineError> {
+        self.lock_state()
+            .register_dir(std::path::Path::new(&dir))
+            .map_err(|e| EngineError::RegistryError { message: e })
+    }
+
+    /// 레이아웃의 JSON5 (`extends`는 풀어 둔 것). 없는 ID면 None.
+    pub fn layout_json(&self, id: String) -> Option<String> {
+        self.lock_state().json(&id)
     }
 }
@@ -0,0 +1,479 @@
+//! 앱별 입력 모드 정책 (앱별 마지막 모드, English Lock, 앱별 기본 모드)
+//!
+//! 사용자가 편집·백업할 수 있는 버전 있는 JSON5 파일로 저장한다. 팀에서 관리하는 잠금 목록
+//! (원격 데스크톱, 터미널 등)은 같은 형식의 공유 설정 파일로 합칠 수 있으며, 공유 규칙은
+//! 사용자 파일에 다시 저장하지 않는다.
+//!
+//! 앱은 번들 ID 또는 `*` 와일드카드 패턴(예: `"com.microsoft.rdc.*"`)으로 지정하고
+//! 대소문자는 구분하지 않는다.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::engine::InputMode;
+use crate::quote;
+
+/// 정책 파일 형식 버전
+pub const POLICY_VERSION: u32 = 1;
+
+/// 앱별 기본 모드 규칙
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ModeRule {
+    /// 번들 ID 또는 `*` 패턴
+    pub app: String,
+    /// 처음 활성화할 때의 모드
+    pub mode: InputMode,
+}
+
+/// JSON5 정책 파일 스키마
+#[derive(Debug, Deserialize)]
+struct PolicyFile {
+    version: u32,
+    #[serde(default = "default_restore_on_unlock")]
+    restore_on_unlock: bool,
+    #[serde(default)]
+    default_mode: Option<InputMode>,
+    #[serde(default)]
+    defaults: Vec<ModeRule>,
+    #[serde(default)]
+    locked: Vec<String>,
+    #[serde(default)]
+    unlocked: BTreeSet<String>,
+    #[serde(default)]
+    last_modes: BTreeMap<String, InputMode>,
+    #[serde(default)]
+    lock_previous: BTreeMap<String, InputMode>,
+}
+
+fn default_restore_on_unlock() -> bool {
+    true
+}
+
+/// 앱별 입력 모드 정책 상태
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicyState {
+    /// 잠금 해제 시 잠그기 전 모드로 되돌릴지 (false면 한글)
+    restore_on_unlock: bool,
+    /// 기억된 모드나 규칙이 없는 앱의 모드 (None이면 현재 모드 유지)
+    default_mode: Option<InputMode>,
+    /// 앱별 기본 모드 (위에서부터 처음 맞는 규칙)
+    defaults: Vec<ModeRule>,
+    /// English Lock을 걸 앱 (번들 ID 또는 패턴)
+    locked: Vec<String>,
+    /// 잠금 패턴에 걸려도 잠그지 않을 앱 (사용자가 해제한 앱)
+    unlocked: BTreeSet<String>,
+    /// 앱별 마지막 모드
+    last_modes: BTreeMap<String, InputMode>,
+    /// 잠근 앱의 잠금 직전 모드
+    lock_previous: BTreeMap<String, InputMode>,
+    /// 공유 설정 파일의 기본 모드 규칙 (저장하지 않음)
+    shared_defaults: Vec<ModeRule>,
+    /// 공유 설정 파일의 잠금 목록 (저장하지 않음)
+    shared_locked: Vec<String>,
+}
+
+impl Default for PolicyState {
+    fn default() -> Self {
+        PolicyState {
+            restore_on_unlock: default_restore_on_unlock(),
+            default_mode: None,
+            defaults: Vec::new(),
+            locked: Vec::new(),
+            unlocked: BTreeSet::new(),
+            last_modes: BTreeMap::new(),
+            lock_previous: BTreeMap::new(),
+            shared_defaults: Vec::new(),
+            shared_locked: Vec::new(),
+        }
+    }
+}
+
+impl PolicyState {
+    /// JSON5 정책 파일을 파싱한다.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let file: PolicyFile =
+            json5::from_str(text).map_err(|e| format!("Invalid mode policy: {e}"))?;
+        if file.version == 0 || file.version > POLICY_VERSION {
+            return Err(format!(
+                "Unsupported mode policy version {} (expected {POLICY_VERSION})",
+                file.version
+            ));
+        }
+        Ok(PolicyState {
+            restore_on_unlock: file.restore_on_unlock,
+            default_mode: file.default_mode,
+            defaults: file.defaults,
+            locked: file.locked,
+            unlocked: file.unlocked.iter().map(|id| normalize(id)).collect(),
+            last_modes: normalize_keys(file.last_modes),
+            lock_previous: normalize_keys(file.lock_previous),
+            shared_defaults: Vec::new(),
+            shared_locked: Vec::new(),
+        })
+    }
+
+    /// 정책 파일을 읽어 파싱한다.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Cannot read mode policy {}: {e}", path.display()))?;
+        Self::parse(&text)
+    }
+
+    /// 공유 설정(팀 잠금 목록 등)의 기본 모드 규칙과 잠금 목록을 합친다.
+    /// 사용자 규칙이 먼저 적용되며, 공유 규칙은 [`to_json5`](Self::to_json5)에 포함되지 않는다.
+    pub fn merge_shared(&mut self, shared: PolicyState) {
+        self.shared_defaults.extend(shared.defaults);
+        self.shared_locked.extend(shared.locked);
+    }
+
+    /// 앱이 활성화될 때 적용할 모드. None이면 현재 모드를 유지한다.
+    /// 잠긴 앱은 영문, 그 밖에는 마지막 모드 → 기본 모드 규칙 → `default_mode` 순.
+    pub fn activate_app(&self, bundle_id: &str) -> Option<InputMode> {
+        let bundle_id = &normalize(bundle_id);
+        if self.is_locked(bundle_id) {
+            return Some(InputMode::English);
+        }
+        if let Some(&mode) = self.last_modes.get(bundle_id) {
+            return Some(mode);
+        }
+        self.defaults
+            .iter()
+            .chain(&self.shared_defaults)
+            .find(|rule| matches(&rule.app, bundle_id))
+            .map(|rule| rule.mode)
+            .or(self.default_mode)
+    }
+
+    /// 앱의 마지막 모드 (기억된 값이 없으면 None)
+    pub fn last_mode(&self, bundle_id: &str) -> Option<InputMode> {
+        self.last_modes.get(&normalize(bundle_id)).copied()
+    }
+
+    /// 앱의 현재 모드를 기억한다. 잠긴 앱은 잠금 중 모드를 기억하지 않는다.
+    pub fn save_mode(&mut self, bundle_id: &str, mode: InputMode) {
+        let bundle_id = normalize(bundle_id);
+        if !self.is_locked(&bundle_id) {
+            self.last_modes.insert(bundle_id, mode);
+        }
+    }
+
+    /// English Lock 여부
+    pub fn is_locked(&self, bundle_id: &str) -> bool {
+        !self.unlocked.contains(&normalize(bundle_id))
+            && self
+                .locked
+                .iter()
+                .chain(&self.shared_locked)
+                .any(|pattern| matches(pattern, bundle_id))
+    }
+
+    /// 앱을 잠근다. `current_mode`는 잠금 해제 시 되돌릴 모드.
+    pub fn lock(&mut self, bundle_id: &str, current_mode: InputMode) {
+        let bundle_id = normalize(bundle_id);
+        if self.is_locked(&bundle_id) {
+            return;
+        }
+        self.lock_previous.insert(bundle_id.clone(), current_mode);
+        self.unlocked.remove(&bundle_id);
+        if !self.is_locked(&bundle_id) {
+            self.locked.push(bundle_id);
+        }
+    }
+
+    /// 앱의 잠금을 해제하고 적용할 모드를 반환한다.
+    /// `restore_on_unlock`이면 잠그기 전 모드, 아니거나 기록이 없으면 한글.
+    pub fn unlock(&mut self, bundle_id: &str) -> InputMode {
+        let bundle_id = normalize(bundle_id);
+        let previous = self.lock_previous.remove(&bundle_id);
+        self.locked
+            .retain(|id| !id.eq_ignore_ascii_case(&bundle_id));
+        if self.is_locked(&bundle_id) {
+            // 패턴으로 잠긴 앱은 예외로 남긴다
+            self.unlocked.insert(bundle_id.clone());
+        }
+        let mode = match previous {
+            Some(mode) if self.restore_on_unlock => mode,
+            _ => InputMode::Korean,
+        };
+        self.last_modes.insert(bundle_id, mode);
+        mode
+    }
+
+    /// 잠금 해제 시 잠그기 전 모드로 되돌릴지
+    pub fn restore_on_unlock(&self) -> bool {
+        self.restore_on_unlock
+    }
+
+    pub fn set_restore_on_unlock(&mut self, enabled: bool) {
+        self.restore_on_unlock = enabled;
+    }
+
+    /// 사용자 정책을 JSON5 파일 내용으로 직렬화한다. (공유 규칙 제외)
+    pub fn to_json5(&self) -> String {
+        let mut out = String::new();
+        out.push_str("// Ongeul 앱별 입력 모드 정책\n");
+        out.push_str("// 앱은 번들 ID 또는 `*` 패턴 (대소문자 구분 없음)\n");
+        out.push_str("{\n");
+        let _ = writeln!(out, "  version: {POLICY_VERSION},");
+        out.push_str("  // 잠금 해제 시 잠그기 전 모드로 되돌린다 (false면 한글)\n");
+        let _ = writeln!(out, "  restore_on_unlock: {},", self.restore_on_unlock);
+        out.push_str("  // 기억된 모드나 규칙이 없는 앱의 모드 (없으면 현재 모드 유지)\n");
+        match self.default_mode {
+            Some(mode) => {
+                let _ = writeln!(out, "  default_mode: {},", quote(mode_name(mode)));
+            }
+            None => out.push_str("  // default_mode: \"english\",\n"),
+        }
+
+        out.push_str("  // 앱별 기본 모드 (위에서부터 처음 맞는 규칙)\n");
+        write_list(
+            &mut out,
+            "defaults",
+            self.defaults.iter().map(|rule| {
+                format!(
+                    "{{ app: {}, mode: {} }}",
+                    quote(&rule.app),
+                    quote(mode_name(rule.mode))
+                )
+            }),
+        );
+        out.push_str("  // English Lock을 걸 앱\n");
+        write_list(&mut out, "locked", self.locked.iter().map(|id| quote(id)));
+        out.push_str("  // 잠금 패턴에 걸려도 잠그지 않을 앱\n");
+        write_list(
+            &mut out,
+            "unlocked",
+            self.unlocked.iter().map(|id| quote(id)),
+        );
+
+        out.push_str("  // 앱별 마지막 모드 (자동 저장)\n");
+        write_modes(&mut out, "last_modes", &self.last_modes);
+        out.push_str("  // 잠근 앱의 잠금 직전 모드 (자동 저장)\n");
+        write_modes(&mut out, "lock_previous", &self.lock_previous);
+        out.push_str("}\n");
+        out
+    }
+}
+
+fn write_list(out: &mut String, key: &str, items: impl Iterator<Item = String>) {
+    let items: Vec<String> = items.collect();
+    if items.is_empty() {
+        let _ = writeln!(out, "  {key}: [],");
+        return;
+    }
+    let _ = writeln!(out, "  {key}: [");
+    for item in items {
+        let _ = writeln!(out, "    {item},");
+    }
+    out.push_str("  ],\n");
+}
+
+fn write_modes(out: &mut String, key: &str, modes: &BTreeMap<String, InputMode>) {
+    if modes.is_empty() {
+        let _ = writeln!(out, "  {key}: {{}},");
+        return;
+    }
+    let _ = writeln!(out, "  {key}: {{");
+    for (id, &mode) in modes {
+        let _ = writeln!(out, "    {}: {},", quote(id), quote(mode_name(mode)));
+    }
+    out.push_str("  },\n");
+}
+
+fn mode_name(mode: InputMode) -> &'static str {
+    match mode {
+        InputMode::English => "english",
+        InputMode::Korean => "korean",
+    }
+}
+
+/// 번들 ID를 저장·비교용으로 정규화한다. (ASCII 소문자)
+fn normalize(bundle_id: &str) -> String {
+    bundle_id.to_ascii_lowercase()
+}
+
+fn normalize_keys(modes: BTreeMap<String, InputMode>) -> BTreeMap<String, InputMode> {
+    modes
+        .into_iter()
+        .map(|(id, mode)| (normalize(&id), mode))
+        .collect()
+}
+
+/// `*` 와일드카드 패턴 매칭 (ASCII 대소문자 구분 없음)
+fn matches(pattern: &str, bundle_id: &str) -> bool {
+    let pattern = pattern.to_ascii_lowercase();
+    let id = bundle_id.to_ascii_lowercase();
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = id.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        // `*`가 없으면 전체 일치
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLICY: &str = r#"
+// 팀 공유 정책
+{
+  version: 1,
+  defaults: [
+    { app: "com.apple.Terminal", mode: "english" },
+    { app: "com.kakao.*", mode: "korean" },
+  ],
+  locked: ["com.microsoft.rdc.*", "*.vnc"],
+}
+"#;
+
+    #[test]
+    fn test_matches() {
+        assert!(matches("com.apple.Safari", "com.apple.safari"));
+        assert!(!matches("com.apple.Safari", "com.apple.SafariTech"));
+        assert!(matches("com.microsoft.rdc.*", "com.microsoft.rdc.macos"));
+        assert!(matches("*.vnc", "com.realvnc.vnc"));
+        assert!(matches("com.*term*", "com.googlecode.iterm2"));
+        assert!(!matches("com.*term*", "org.googlecode.iterm2"));
+        assert!(matches("*", "anything"));
+    }
+
+    #[test]
+    fn test_activate_app_priority() {
+        let mut policy = PolicyState::parse(POLICY).unwrap();
+        // 규칙 → 기본값 (없으면 현재 모드 유지)
+        assert_eq!(
+            policy.activate_app("com.kakao.KakaoTalk"),
+            Some(InputMode::Korean)
+        );
+        assert_eq!(
+            policy.activate_app("com.apple.Terminal"),
+            Some(InputMode::English)
+        );
+        assert_eq!(policy.activate_app("com.apple.Safari"), None);
+        // 마지막 모드가 규칙보다 우선
+        policy.save_mode("com.apple.Terminal", InputMode::Korean);
+        assert_eq!(
+            policy.activate_app("com.apple.Terminal"),
+            Some(InputMode::Korean)
+        );
+        // 잠금이 가장 우선
+        assert_eq!(
+            policy.activate_app("com.microsoft.rdc.macos"),
+            Some(InputMode::English)
+        );
+    }
+
+    #[test]
+    fn test_lock_and_restore() {
+        let mut policy = PolicyState::default();
+        policy.save_mode("com.test.app", InputMode::Korean);
+        policy.lock("com.test.app", InputMode::Korean);
+        assert!(policy.is_locked("com.test.app"));
+        assert_eq!(
+            policy.activate_app("com.test.app"),
+            Some(InputMode::English)
+        );
+        // 잠금 중 모드는 기억하지 않는다
+        policy.save_mode("com.test.app", InputMode::English);
+        assert_eq!(policy.last_mode("com.test.app"), Some(InputMode::Korean));
+
+        assert_eq!(policy.unlock("com.test.app"), InputMode::Korean);
+        assert!(!policy.is_locked("com.test.app"));
+
+        policy.lock("com.test.app", InputMode::English);
+        assert_eq!(policy.unlock("com.test.app"), InputMode::English);
+    }
+
+    #[test]
+    fn test_bundle_id_case_insensitive() {
+        let mut policy =
+            PolicyState::parse(r#"{ version: 1, last_modes: { "com.Test.App": "english" } }"#)
+                .unwrap();
+        assert_eq!(policy.last_mode("com.test.app"), Some(InputMode::English));
+        // 대소문자만 다른 ID는 같은 앱이다
+        policy.save_mode("COM.TEST.APP", InputMode::Korean);
+        assert_eq!(policy.last_mode("com.Test.App"), Some(InputMode::Korean));
+        assert_eq!(policy.last_modes.len(), 1);
+
+        policy.lock("com.Test.App", InputMode::Korean);
+        assert!(policy.is_locked("com.test.app"));
+        assert_eq!(policy.unlock("COM.test.APP"), InputMode::Korean);
+        assert!(!policy.is_locked("com.Test.App"));
+        assert!(policy.locked.is_empty());
+    }
+
+    #[test]
+    fn test_unlock_without_restore() {
+        let mut policy = PolicyState::default();
+        policy.set_restore_on_unlock(false);
+        policy.lock("com.test.app", InputMode::English);
+        assert_eq!(policy.unlock("com.test.app"), InputMode::Korean);
+        assert_eq!(policy.activate_app("com.test.app"), Some(InputMode::Korean));
+    }
+
+    #[test]
+    fn test_unlock_pattern_locked_app() {
+        let mut policy = PolicyState::parse(POLICY).unwrap();
+        let app = "com.microsoft.rdc.macos";
+        assert!(policy.is_locked(app));
+        policy.unlock(app);
+        assert!(!policy.is_locked(app));
+        // 같은 패턴의 다른 앱은 그대로 잠겨 있다
+        assert!(policy.is_locked("com.microsoft.rdc.beta"));
+        // 다시 잠그면 예외만 지운다
+        policy.lock(app, InputMode::Korean);
+        assert!(policy.is_locked(app));
+        assert_eq!(policy.locked, vec!["com.microsoft.rdc.*", "*.vnc"]);
+    }
+
+    #[test]
+    fn test_shared_rules_apply_but_are_not_saved() {
+        let mut policy = PolicyState::default();
+        policy.merge_shared(PolicyState::parse(POLICY).unwrap());
+        assert!(policy.is_locked("com.realvnc.vnc"));
+        assert_eq!(
+            policy.activate_app("com.kakao.KakaoTalk"),
+            Some(InputMode::Korean)
+        );
+        let text = policy.to_json5();
+        assert!(!text.contains("vnc"));
+        assert!(!text.contains("kakao"));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut policy = PolicyState::parse(POLICY).unwrap();
+        policy.save_mode("com.apple.Safari", InputMode::Korean);
+        policy.lock("com.test.\"quoted\"", InputMode::Korean);
+        policy.unlock("com.realvnc.vnc");
+        policy.set_restore_on_unlock(false);
+        let reparsed = PolicyState::parse(&policy.to_json5()).unwrap();
+        assert_eq!(reparsed, policy);
+        assert_eq!(
+            PolicyState::parse(&PolicyState::default().to_json5()).unwrap(),
+            PolicyState::default()
+        );
+    }
+
+    #[test]
+    fn test_version_check() {
+        assert!(PolicyState::parse("{ version: 2 }").is_err());
+        assert!(PolicyState::parse("{ locked: [] }").is_err());
+        assert!(PolicyState::parse("{ version: 1 }").is_ok());
+    }
+}
@@ -0,0 +1,391 @@
+//! 키 입력 기록과 재현 (버그 보고용)
+//!
+//! 엔진 API 호출과 그 결과를 한 줄에 하나씩 JSON Lines 파일에 기록하고,
+//! 기록을 현재 엔진에 다시 입력해 결과가 처음 달라지는 곳을 찾는다.
+//! 첫 줄은 형식 버전과 기록한 엔진 버전을 담은 헤더다.
+//!
+//! 레이아웃은 ID만 기록하므로 재현할 때 같은 ID의 레이아웃이 필요하다. 자동 한영 전환
+//! 단어 목록은 파일 대신 단어를 기록한다.
+//! 커서 앞 텍스트는 엔진이 쓰는 마지막 글자만 남긴다.
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+use crate::engine::InputMode;
+use crate::event::{KeyAction, KeyEvent};
+use crate::{HangulEngine, ProcessResult};
+
+/// 기록 파일 형식 버전
+pub const RECORD_VERSION: u32 = 1;
+
+/// 기록 파일 첫 줄
+#[derive(Debug, Serialize, Deserialize)]
+struct Header {
+    version: u32,
+    /// 기록한 엔진 버전 (참고용)
+    engine: String,
+}
+
+/// 기록하는 엔진 호출
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "call", rename_all = "snake_case")]
+pub enum Call {
+    LoadLayout {
+        id: String,
+    },
+    LoadEnglishLayout {
+        id: String,
+    },
+    ClearEnglishLayout,
+    SetMode {
+        mode: InputMode,
+    },
+    ToggleMode,
+    ProcessKey {
+        key: String,
+    },
+    ProcessPhysicalKey {
+        usage: u32,
+        shift: bool,
+    },
+    HandleEvent {
+        event: KeyEvent,
+        shift_space_toggle: bool,
+    },
+    KeyDown {
+        key: String,
+        timestamp_ms: u64,
+    },
+    KeyUp {
+        key: String,
+        timestamp_ms: u64,
+    },
+    SetChordWindow {
+        window_ms: u64,
+    },
+    Backspace,
+    /// 커서 앞 텍스트의 마지막 글자만 기록한다
+    BackspaceWithContext {
+        text: String,
+    },
+    Flush,
+    Reset,
+    SetDirectCommit {
+        enabled: bool,
+    },
+    Suspend,
+    Resume,
+    /// 단어 목록 파일 대신 읽어 들인 단어를 기록한다
+    LoadEnglishWords {
+        words: Vec<String>,
+    },
+    ClearEnglishWordList,
+    SetEnglishDetectionThreshold {
+        threshold: f32,
+    },
+    SetEnglishDetectionExceptions {
+        words: Vec<String>,
+    },
+    /// 엔진 상태를 스냅샷으로 되돌린다. 재현할 수 없는 호출(한자 변환 등)이나
+    /// 기록 제외 구간이 끝난 뒤의 상태를 맞출 때 기록한다.
+    Restore {
+        snapshot: String,
+    },
+}
+
+/// 기록된 호출 결과
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub action: Option<KeyAction>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub committed: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub composing: Option<String>,
+    /// 시스템에 위임한 키 (기록을 줄이려고 handled = false일 때만 남긴다)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub unhandled: bool,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub delete_before: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suggested_mode: Option<InputMode>,
+}
+
+impl Output {
+    pub fn new(action: Option<KeyAction>, result: &ProcessResult) -> Self {
+        Output {
+            action,
+            committed: result.committed.clone(),
+            composing: result.composing.clone(),
+            unhandled: !result.handled,
+            delete_before: result.delete_before,
+            suggested_mode: result.suggested_mode.map(InputMode::from),
+        }
+    }
+}
+
+impl From<&ProcessResult> for Output {
+    fn from(result: &ProcessResult) -> Self {
+        Output::new(None, result)
+    }
+}
+
+fn is_zero(n: &u32) -> bool {
+    *n == 0
+}
+
+/// 기록 한 줄
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    /// 기록 시작부터 지난 시간 (밀리초)
+    pub t: u64,
+    #[serde(flatten)]
+    pub call: Call,
+    /// 결과를 반환하지 않는 호출은 None
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub out: Option<Output>,
+}
+
+/// 기록 파일 작성기
+pub struct Recorder {
+    out: BufWriter<File>,
+    started: Instant,
+    /// 보안 입력 등 기록에서 제외하는 구간인지
+    excluded: bool,
+}
+
+impl Recorder {
+    /// 기록 파일을 만들고 헤더를 쓴다. 같은 이름의 파일은 덮어쓴다.
+    pub fn create(path: &Path) -> Result<Self, String> {
+        let file = File::create(path).map_err(|e| format!("{}: {e}", path.display()))?;
+        let mut recorder = Recorder {
+            out: BufWriter::new(file),
+            started: Instant::now(),
+            excluded: false,
+        };
+        let header = Header {
+            version: RECORD_VERSION,
+            engine: env!("CARGO_PKG_VERSION").to_string(),
+        };
+        recorder
+            .write_line(&header)
+            .map_err(|e| format!("{}: {e}", path.display()))?;
+        Ok(recorder)
+    }
+
+    /// 호출 하나를 기록한다. 제외 구간에서는 기록하지 않는다.
+    pub fn record(&mut self, call: Call, out: Option<Output>) {
+        if self.excluded {
+            return;
+        }
+        let entry = Entry {
+            t: self.started.elapsed().as_millis() as u64,
+            call,
+            out,
+        };
+        if let Err(e) = self.write_line(&entry) {
+            crate::warn_unexpected("Cannot write input record", e);
+        }
+    }
+
+    /// 기록 제외 구간을 시작하거나 끝낸다.
+    /// 구간이 끝나면 그동안 바뀐 엔진 상태를 맞추도록 `snapshot`을 [`Call::Restore`]로 기록한다.
+    pub fn set_excluded(&mut self, excluded: bool, snapshot: impl FnOnce() -> String) {
+        if self.excluded == excluded {
+            return;
+        }
+        self.excluded = excluded;
+        if !excluded {
+            self.record(
+                Call::Restore {
+                    snapshot: snapshot(),
+                },
+                None,
+            );
+        }
+    }
+
+    /// 한 줄을 쓰고 바로 내보낸다. (입력기가 비정상 종료되어도 그 전까지의 기록은 남는다)
+    fn write_line(&mut self, value: &impl Serialize) -> Result<(), String> {
+        let line = json5::to_string(value).map_err(|e| e.to_string())?;
+        writeln!(self.out, "{line}").map_err(|e| e.to_string())?;
+        self.out.flush().map_err(|e| e.to_string())
+    }
+}
+
+/// 기록을 읽는다. 헤더의 형식 버전을 확인한다.
+pub fn parse_record(text: &str) -> Result<Vec<Entry>, String> {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty());
+    let (_, first) = lines.next().ok_or("Empty record")?;
+    let header: Header =
+        json5::from_str(first).map_err(|e| format!("Invalid record header: {e}"))?;
+    if header.version != RECORD_VERSION {
+        return Err(format!(
+            "Unsupported record version {} (expected {RECORD_VERSION})",
+            header.version
+        ));
+    }
+    lines
+        .map(|(i, line)| {
+            json5::from_str(line).map_err(|e| format!("line {}: invalid entry: {e}", i + 1))
+        })
+        .collect()
+}
+
+/// 기록과 재현 결과가 처음 달라진 곳
+#[derive(Debug, Clone, PartialEq)]
+pub struct Divergence {
+    /// 기록 항목 번호 (0부터, 헤더 제외)
+    pub index: usize,
+    pub entry: Entry,
+    /// 현재 엔진의 결과
+    pub actual: Option<Output>,
+}
+
+/// 기록을 현재 엔진으로 재현한다. 모든 결과가 같으면 None, 처음 달라진 곳이 있으면 그 항목.
+///
+/// `layout`은 레이아웃 ID로 JSON5 원본을 찾는다. 레이아웃을 찾지 못하거나 로드·복원에
+/// 실패하면 에러.
+pub fn replay(
+    entries: &[Entry],
+    layout: impl Fn(&str) -> Option<String>,
+) -> Result<Option<Divergence>, String> {
+    let engine = HangulEngine::new();
+    for (index, entry) in entries.iter().enumerate() {
+        let actual = run_call(&engine, &entry.call, &layout)
+            .map_err(|e| format!("entry {index} ({:?}): {e}", entry.call))?;
+        if actual != entry.out {
+            return Ok(Some(Divergence {
+                index,
+                entry: entry.clone(),
+                actual,
+            }));
+        }
+    }
+    Ok(None)
+}
+
+fn run_call(
+    engine: &HangulEngine,
+    call: &Call,
+    layout: &impl Fn(&str) -> Option<String>,
+) -> Result<Option<Output>, String> {
+    let find = |id: &str| layout(id).ok_or_else(|| format!("Layout '{id}' not found"));
+    let result = match call.clone() {
+        Call::LoadLayout { id } => {
+            engine.load_layout(find(&id)?).map_err(|e| e.to_string())?;
+            return Ok(None);
+        }
+        Call::LoadEnglishLayout { id } => {
+            engine
+                .load_english_layout(find(&id)?)
+                .map_err(|e| e.to_string())?;
+            return Ok(None);
+        }
+        Call::ClearEnglishLayout => {
+            engine.clear_english_layout();
+            return Ok(None);
+        }
+        Call::SetMode { mode } => {
+            engine.set_mode(mode.into());
+            return Ok(None);
+        }
+        Call::SetChordWindow { window_ms } => {
+            engine.set_chord_window(window_ms);
+            return Ok(None);
+        }
+        Call::LoadEnglishWords { words } => {
+            engine.load_english_words(words);
+            return Ok(None);
+        }
+        Call::ClearEnglishWordList => {
+            engine.clear_english_word_list();
+            return Ok(None);
+        }
+        Call::SetEnglishDetectionThreshold { threshold } => {
+            engine.set_english_detection_threshold(threshold);
+            return Ok(None);
+        }
+        Call::SetEnglishDetectionExceptions { words } => {
+            engine.set_english_detection_exceptions(words);
+            return Ok(None);
+        }
+        Call::Reset => {
+            engine.reset();
+            return Ok(None);
+        }
+        Call::Restore { snapshot } => {
+            engine.restore(snapshot).map_err(|e| e.to_string())?;
+            return Ok(None);
+        }
+        Call::HandleEvent {
+            event,
+            shift_space_toggle,
+        } => {
+            let result = engine.handle_event(event, shift_space_toggle);
+            return Ok(Some(Output::new(Some(result.action), &result.result)));
+        }
+        Call::ToggleMode => engine.toggle_mode(),
+        Call::ProcessKey { key } => engine.process_key(key),
+        Call::ProcessPhysicalKey { usage, shift } => engine.process_physical_key(usage, shift),
+        Call::KeyDown { key, timestamp_ms } => engine.key_down(key, timestamp_ms),
+        Call::KeyUp { key, timestamp_ms } => engine.key_up(key, timestamp_ms),
+        Call::Backspace => engine.backspace(),
+        Call::BackspaceWithContext { text } => engine.backspace_with_context(text),
+        Call::Flush => engine.flush(),
+        Call::SetDirectCommit { enabled } => engine.set_direct_commit(enabled),
+        Call::Suspend => engine.suspend(),
+        Call::Resume => engine.resume(),
+    };
+    Ok(Some(Output::from(&result)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entry_is_compact() {
+        let entry = Entry {
+            t: 12,
+            call: Call::ProcessKey { key: "r".into() },
+            out: Some(Output {
+                action: None,
+                committed: None,
+                composing: Some("ㄱ".into()),
+                unhandled: false,
+                delete_before: 0,
+                suggested_mode: None,
+            }),
+        };
+        let line = json5::to_string(&entry).unwrap();
+        assert_eq!(
+            line,
+            r#"{"t":12,"call":"process_key","key":"r","out":{"composing":"ㄱ"}}"#
+        );
+        assert_eq!(json5::from_str::<Entry>(&line).unwrap(), entry);
+    }
+
+    #[test]
+    fn test_unit_call_round_trip() {
+        let line = r#"{"t":3,"call":"flush","out":{"committed":"가"}}"#;
+        let entry: Entry = json5::from_str(line).unwrap();
+        assert_eq!(entry.call, Call::Flush);
+        assert_eq!(entry.out.unwrap().committed.as_deref(), Some("가"));
+    }
+
+    #[test]
+    fn test_parse_record_checks_version() {
+        assert!(parse_record("").is_err());
+        assert!(parse_record(r#"{"version":9,"engine":"0.0.0"}"#).is_err());
+        let text = "{\"version\":1,\"engine\":\"0.2.0\"}\n\n{\"t\":0,\"call\":\"reset\"}\n";
+        assert_eq!(parse_record(text).unwrap().len(), 1);
+    }
+}
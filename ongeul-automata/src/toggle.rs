@@ -0,0 +1,373 @@
+//! 한영 전환 키 감지 (modifier tap + 4키 English Lock)
+//!
+//! 플랫폼 키 코드 대신 [`ModifierKey`]/[`ModifierFlags`]와 밀리초 시각을 받는 순수 상태 머신이다.
+//! 부수 효과(엔진 호출, UI 표시)는 호출자가 [`ToggleAction`]에 따라 처리한다.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+/// 탭으로 인정하는 최대 누름 시간 (밀리초)
+pub const TOGGLE_TIMEOUT_MS: u64 = 500;
+
+/// 플랫폼 중립 modifier 키
+#[derive(uniffi::Enum, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ModifierKey {
+    LeftShift,
+    RightShift,
+    LeftCommand,
+    RightCommand,
+    LeftOption,
+    RightOption,
+    LeftControl,
+    RightControl,
+    CapsLock,
+    /// 그 밖의 modifier (Fn 등)
+    Other,
+}
+
+impl ModifierKey {
+    /// 이 키가 눌려 있을 때 켜지는 flag인지
+    fn is_down(self, flags: &ModifierFlags) -> bool {
+        match self {
+            ModifierKey::LeftShift | ModifierKey::RightShift => flags.shift,
+            ModifierKey::LeftCommand | ModifierKey::RightCommand => flags.command,
+            ModifierKey::LeftOption | ModifierKey::RightOption => flags.option,
+            ModifierKey::LeftControl | ModifierKey::RightControl => flags.control,
+            ModifierKey::CapsLock | ModifierKey::Other => false,
+        }
+    }
+}
+
+/// 이벤트 시점에 눌려 있는 modifier (CapsLock 제외)
+#[derive(uniffi::Record, Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModifierFlags {
+    pub shift: bool,
+    pub command: bool,
+    pub option: bool,
+    pub control: bool,
+}
+
+impl ModifierFlags {
+    fn active_count(&self) -> usize {
+        [self.shift, self.command, self.option, self.control]
+            .iter()
+            .filter(|&&on| on)
+            .count()
+    }
+}
+
+/// modifier tap 또는 4키 English Lock 감지 결과
+#[derive(uniffi::Enum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToggleAction {
+    None,
+    Toggle,
+    EnglishLockToggle,
+}
+
+/// English Lock을 켜고 끄는 4키 (좌우 Command + 좌우 Option)
+const FOUR_KEYS: [ModifierKey; 4] = [
+    ModifierKey::LeftCommand,
+    ModifierKey::RightCommand,
+    ModifierKey::LeftOption,
+    ModifierKey::RightOption,
+];
+
+/// 전환 키 감지 상태
+#[derive(Debug, Clone, Default)]
+pub struct ToggleState {
+    /// 눌린 전환 키 (탭 판정 대기 중)
+    pub pending_key: Option<ModifierKey>,
+    /// 탭 판정 만료 시각 (밀리초)
+    pub expires_at: u64,
+    /// 지금까지 눌린 4키
+    pub four_keys_seen: HashSet<ModifierKey>,
+    /// 4키가 모두 눌렸는지
+    pub all_four_reached: bool,
+}
+
+impl ToggleState {
+    /// 일반 키 입력 시 진행 중인 감지 상태를 초기화한다.
+    pub fn cancel_on_key_down(&mut self) {
+        self.pending_key = None;
+        self.four_keys_seen.clear();
+        self.all_four_reached = false;
+    }
+
+    /// modifier 변화 이벤트로 수행할 동작을 판정한다.
+    ///
+    /// `toggle_key`는 탭으로 전환할 modifier 키이며, modifier 탭이 아닌 전환 방식
+    /// (Shift+Space, CapsLock)은 None으로 넘겨 4키 English Lock만 감지한다.
+    pub fn handle_flags_changed(
+        &mut self,
+        key: ModifierKey,
+        flags: ModifierFlags,
+        toggle_key: Option<ModifierKey>,
+        now_ms: u64,
+    ) -> ToggleAction {
+        // === 4키 English Lock 감지 ===
+
+        // 대상 키는 누적 (중복 이벤트 무관), 다른 modifier는 사이클 취소
+        if FOUR_KEYS.contains(&key) {
+            self.four_keys_seen.insert(key);
+        } else {
+            self.four_keys_seen.clear();
+            self.all_four_reached = false;
+        }
+
+        if self.four_keys_seen.len() == FOUR_KEYS.len() {
+            self.all_four_reached = true;
+            self.pending_key = None;
+        }
+
+        // Command/Option이 모두 떼어지면 사이클 종료
+        if !flags.command && !flags.option {
+            self.four_keys_seen.clear();
+            if self.all_four_reached {
+                self.all_four_reached = false;
+                return ToggleAction::EnglishLockToggle;
+            }
+        }
+
+        // === modifier tap 감지 ===
+
+        let Some(toggle_key) = toggle_key else {
+            return ToggleAction::None;
+        };
+
+        // ① 다중 modifier 가드 (CapsLock 제외)
+        if flags.active_count() > 1 {
+            self.pending_key = None;
+            return ToggleAction::None;
+        }
+
+        // ② 전환 키 누름
+        if key == toggle_key
+            && toggle_key.is_down(&flags)
+            && self.pending_key.is_none()
+            && !self.all_four_reached
+        {
+            self.pending_key = Some(key);
+            self.expires_at = now_ms + TOGGLE_TIMEOUT_MS;
+            return ToggleAction::None;
+        }
+
+        // ③ 전환 키 뗌: 시간 안에 떼었으면 탭
+        if key == toggle_key && !toggle_key.is_down(&flags) {
+            let tapped = self.pending_key == Some(key) && now_ms < self.expires_at;
+            self.pending_key = None;
+            return if tapped {
+                ToggleAction::Toggle
+            } else {
+                ToggleAction::None
+            };
+        }
+
+        // ④ 다른 modifier 키 → 대기 해제
+        self.pending_key = None;
+        ToggleAction::None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: u64 = 1_000_000;
+
+    const NONE: ModifierFlags = ModifierFlags {
+        shift: false,
+        command: false,
+        option: false,
+        control: false,
+    };
+    const COMMAND: ModifierFlags = ModifierFlags {
+        command: true,
+        ..NONE
+    };
+    const OPTION: ModifierFlags = ModifierFlags {
+        option: true,
+        ..NONE
+    };
+    const SHIFT: ModifierFlags = ModifierFlags {
+        shift: true,
+        ..NONE
+    };
+    const COMMAND_OPTION: ModifierFlags = ModifierFlags {
+        command: true,
+        option: true,
+        ..NONE
+    };
+    const COMMAND_SHIFT: ModifierFlags = ModifierFlags {
+        command: true,
+        shift: true,
+        ..NONE
+    };
+
+    /// 전환 키를 누르고 `held_ms` 후 뗀 결과
+    fn tap(
+        state: &mut ToggleState,
+        key: ModifierKey,
+        down: ModifierFlags,
+        held_ms: u64,
+    ) -> ToggleAction {
+        let pressed = state.handle_flags_changed(key, down, Some(key), NOW);
+        assert_eq!(pressed, ToggleAction::None);
+        state.handle_flags_changed(key, NONE, Some(key), NOW + held_ms)
+    }
+
+    // ── 오른쪽 Command ──
+
+    #[test]
+    fn test_right_command_press_release_toggles() {
+        let mut state = ToggleState::default();
+        let action = tap(&mut state, ModifierKey::RightCommand, COMMAND, 200);
+        assert_eq!(action, ToggleAction::Toggle);
+    }
+
+    #[test]
+    fn test_right_command_timeout_no_toggle() {
+        let mut state = ToggleState::default();
+        let action = tap(&mut state, ModifierKey::RightCommand, COMMAND, 600);
+        assert_eq!(action, ToggleAction::None);
+    }
+
+    #[test]
+    fn test_key_down_cancels_pending() {
+        let mut state = ToggleState::default();
+        let toggle = Some(ModifierKey::RightCommand);
+        state.handle_flags_changed(ModifierKey::RightCommand, COMMAND, toggle, NOW);
+        state.cancel_on_key_down();
+        let action = state.handle_flags_changed(ModifierKey::RightCommand, NONE, toggle, NOW + 100);
+        assert_eq!(action, ToggleAction::None);
+    }
+
+    // ── 다중 modifier 가드 ──
+
+    #[test]
+    fn test_multi_modifier_cancels_pending() {
+        let mut state = ToggleState::default();
+        let toggle = Some(ModifierKey::RightCommand);
+        state.handle_flags_changed(ModifierKey::RightCommand, COMMAND, toggle, NOW);
+        // Shift 추가 → 다중 modifier
+        let action =
+            state.handle_flags_changed(ModifierKey::LeftShift, COMMAND_SHIFT, toggle, NOW + 100);
+        assert_eq!(action, ToggleAction::None);
+        assert_eq!(state.pending_key, None);
+    }
+
+    // ── 4키 English Lock ──
+
+    #[test]
+    fn test_four_key_english_lock() {
+        let mut state = ToggleState::default();
+        let toggle = Some(ModifierKey::RightCommand);
+        state.handle_flags_changed(ModifierKey::LeftCommand, COMMAND, toggle, NOW);
+        state.handle_flags_changed(ModifierKey::RightCommand, COMMAND, toggle, NOW);
+        state.handle_flags_changed(ModifierKey::LeftOption, COMMAND_OPTION, toggle, NOW);
+        state.handle_flags_changed(ModifierKey::RightOption, COMMAND_OPTION, toggle, NOW);
+        // 모두 해제
+        let action = state.handle_flags_changed(ModifierKey::RightOption, NONE, toggle, NOW);
+        assert_eq!(action, ToggleAction::EnglishLockToggle);
+    }
+
+    #[test]
+    fn test_four_key_partial_no_lock() {
+        let mut state = ToggleState::default();
+        let toggle = Some(ModifierKey::RightCommand);
+        // 3키만
+        state.handle_flags_changed(ModifierKey::LeftCommand, COMMAND, toggle, NOW);
+        state.handle_flags_changed(ModifierKey::RightCommand, COMMAND, toggle, NOW);
+        state.handle_flags_changed(ModifierKey::LeftOption, COMMAND_OPTION, toggle, NOW);
+        let action = state.handle_flags_changed(ModifierKey::LeftOption, NONE, toggle, NOW);
+        assert_eq!(action, ToggleAction::None);
+    }
+
+    #[test]
+    fn test_four_key_interrupted_by_other_modifier_resets() {
+        let mut state = ToggleState::default();
+        let toggle = Some(ModifierKey::RightCommand);
+        state.handle_flags_changed(ModifierKey::LeftCommand, COMMAND, toggle, NOW);
+        state.handle_flags_changed(ModifierKey::RightCommand, COMMAND, toggle, NOW);
+        // Shift(4키 대상 아님) → 사이클 취소
+        state.handle_flags_changed(ModifierKey::LeftShift, COMMAND_SHIFT, toggle, NOW);
+        assert!(state.four_keys_seen.is_empty());
+    }
+
+    #[test]
+    fn test_four_key_lock_without_tap_toggle_key() {
+        // Shift+Space 등 modifier 탭이 아닌 전환 방식에서도 4키는 감지한다
+        let mut state = ToggleState::default();
+        for (key, flags) in [
+            (ModifierKey::LeftCommand, COMMAND),
+            (ModifierKey::RightCommand, COMMAND),
+            (ModifierKey::LeftOption, COMMAND_OPTION),
+            (ModifierKey::RightOption, COMMAND_OPTION),
+        ] {
+            state.handle_flags_changed(key, flags, None, NOW);
+        }
+        let action = state.handle_flags_changed(ModifierKey::RightOption, NONE, None, NOW);
+        assert_eq!(action, ToggleAction::EnglishLockToggle);
+    }
+
+    // ── 오른쪽 Option, 좌우 Shift ──
+
+    #[test]
+    fn test_right_option_toggle() {
+        let mut state = ToggleState::default();
+        let action = tap(&mut state, ModifierKey::RightOption, OPTION, 100);
+        assert_eq!(action, ToggleAction::Toggle);
+    }
+
+    #[test]
+    fn test_left_shift_toggle() {
+        let mut state = ToggleState::default();
+        let action = tap(&mut state, ModifierKey::LeftShift, SHIFT, 100);
+        assert_eq!(action, ToggleAction::Toggle);
+    }
+
+    #[test]
+    fn test_right_shift_toggle() {
+        let mut state = ToggleState::default();
+        let action = tap(&mut state, ModifierKey::RightShift, SHIFT, 100);
+        assert_eq!(action, ToggleAction::Toggle);
+    }
+
+    // ── modifier 탭이 아닌 전환 방식 ──
+
+    #[test]
+    fn test_no_tap_toggle_key_returns_none() {
+        let mut state = ToggleState::default();
+        let action = state.handle_flags_changed(ModifierKey::LeftShift, SHIFT, None, NOW);
+        assert_eq!(action, ToggleAction::None);
+        let action = state.handle_flags_changed(ModifierKey::LeftShift, NONE, None, NOW + 100);
+        assert_eq!(action, ToggleAction::None);
+    }
+
+    // ── 연속 뗌 ──
+
+    #[test]
+    fn test_double_release_without_repress_no_second_toggle() {
+        let mut state = ToggleState::default();
+        let action = tap(&mut state, ModifierKey::RightCommand, COMMAND, 100);
+        assert_eq!(action, ToggleAction::Toggle);
+        // 다시 누르지 않고 뗌 이벤트만 → 대기 중인 키가 없으므로 전환하지 않는다
+        let action = state.handle_flags_changed(
+            ModifierKey::RightCommand,
+            NONE,
+            Some(ModifierKey::RightCommand),
+            NOW + 200,
+        );
+        assert_eq!(action, ToggleAction::None);
+    }
+
+    #[test]
+    fn test_other_toggle_key_not_triggered_by_different_modifier() {
+        // 전환 키가 오른쪽 Command일 때 왼쪽 Command 탭은 전환하지 않는다
+        let mut state = ToggleState::default();
+        let toggle = Some(ModifierKey::RightCommand);
+        state.handle_flags_changed(ModifierKey::LeftCommand, COMMAND, toggle, NOW);
+        let action = state.handle_flags_changed(ModifierKey::LeftCommand, NONE, toggle, NOW + 100);
+        assert_eq!(action, ToggleAction::None);
+    }
+}
@@ -398,6 +398,78 @@ pub fn is_korean_jamo(ch: char) -> bool {
         || is_compat_vowel(ch)
 }
 
+// ── 옛한글 첫가끝 자모 (Hangul Jamo + Extended-A/B) ──
+
+/// 초성 채움 문자 (U+115F) — 초성 없는 첫가끝 음절의 자리 표시
+pub const CHOSEONG_FILLER: char = '\u{115F}';
+/// 중성 채움 문자 (U+1160) — 중성 없는 첫가끝 음절의 자리 표시
+pub const JUNGSEONG_FILLER: char = '\u{1160}';
+
+/// 옛한글을 포함한 초성인지 (U+1100~U+115E, 확장 A U+A960~U+A97C)
+pub fn is_old_choseong(ch: char) -> bool {
+    matches!(ch as u32, 0x1100..=0x115E | 0xA960..=0xA97C)
+}
+
+/// 옛한글을 포함한 중성인지 (U+1161~U+11A7, 확장 B U+D7B0~U+D7C6)
+pub fn is_old_jungseong(ch: char) -> bool {
+    matches!(ch as u32, 0x1161..=0x11A7 | 0xD7B0..=0xD7C6)
+}
+
+/// 옛한글을 포함한 종성인지 (U+11A8~U+11FF, 확장 B U+D7CB~U+D7FB)
+pub fn is_old_jongseong(ch: char) -> bool {
+    matches!(ch as u32, 0x11A8..=0x11FF | 0xD7CB..=0xD7FB)
+}
+
+/// 옛한글을 포함한 한글 자모인지 (옛한글 배열용, 채움 문자 제외)
+pub fn is_old_korean_jamo(ch: char) -> bool {
+    is_old_choseong(ch)
+        || is_old_jungseong(ch)
+        || is_old_jongseong(ch)
+        || is_compat_consonant(ch)
+        || is_compat_vowel(ch)
+}
+
+/// 첫가끝 자모로 음절 문자열을 만든다.
+///
+/// 초/중/(종)성이 모두 현대 자모면 완성형 음절(U+AC00~)로 합성하고, 아니면
+/// L+V(+T) 첫가끝 시퀀스를 반환한다. 빠진 초성/중성 자리는 채움 문자로 메운다.
+/// 현대 자모 하나만 있으면 기존 조합과 같게 호환 자모로 표시한다.
+pub fn compose_conjoining(l: Option<char>, v: Option<char>, t: Option<char>) -> Option<String> {
+    // 완성형 합성 시도
+    if let (Some(l), Some(v)) = (l, v)
+        && let (Some(l_idx), Some(v_idx)) = (choseong_to_index(l), jungseong_to_index(v))
+    {
+        let t_idx = match t {
+            Some(t) => jongseong_to_index(t),
+            None => Some(0),
+        };
+        if let Some(syllable) = t_idx.and_then(|t_idx| compose_syllable(l_idx, v_idx, t_idx)) {
+            return Some(syllable.to_string());
+        }
+    }
+
+    // 현대 자모 단독 → 호환 자모
+    let compat = match (l, v, t) {
+        (None, None, None) => return None,
+        (Some(l), None, None) => choseong_to_index(l).and_then(choseong_to_compat),
+        (None, Some(v), None) => jungseong_to_index(v).and_then(jungseong_to_compat),
+        (None, None, Some(t)) => jongseong_to_index(t).and_then(jongseong_to_compat),
+        _ => None,
+    };
+    if let Some(ch) = compat {
+        return Some(ch.to_string());
+    }
+
+    // 첫가끝 시퀀스
+    let mut text = String::new();
+    text.push(l.unwrap_or(CHOSEONG_FILLER));
+    text.push(v.unwrap_or(JUNGSEONG_FILLER));
+    if let Some(t) = t {
+        text.push(t);
+    }
+    Some(text)
+}
+
 /// 위치 초성 → 초성 인덱스
 pub fn choseong_to_index(ch: char) -> Option<u32> {
     if is_choseong(ch) {
@@ -761,10 +833,91 @@ mod tests {
         // 호환 자모
         assert!(is_korean_jamo('ㄱ'));
         assert!(is_korean_jamo('ㅏ'));
+        // 옛한글 첫가끝 자모는 옛한글 배열에서만 자모로 본다
+        for ch in ['\u{1140}', '\u{119E}', '\u{A960}', '\u{D7CB}'] {
+            assert!(!is_korean_jamo(ch));
+            assert!(is_old_korean_jamo(ch));
+        }
+        assert!(is_old_korean_jamo('\u{1100}'));
+        assert!(is_old_korean_jamo('ㄱ'));
         // 비자모
+        assert!(!is_korean_jamo('\u{115F}')); // 초성 채움 문자
+        assert!(!is_old_korean_jamo('\u{115F}'));
+        assert!(!is_old_korean_jamo('가'));
         assert!(!is_korean_jamo('가')); // 완성형 음절
         assert!(!is_korean_jamo('A'));
         assert!(!is_korean_jamo('1'));
         assert!(!is_korean_jamo(' '));
     }
+
+    #[test]
+    fn test_old_jamo_classification() {
+        assert!(is_old_choseong('\u{1100}'));
+        assert!(is_old_choseong('\u{1140}')); // ㅿ
+        assert!(is_old_choseong('\u{A97C}'));
+        assert!(!is_old_choseong('\u{115F}')); // 채움 문자
+        assert!(!is_old_choseong('\u{A97D}'));
+        assert!(is_old_jungseong('\u{119E}')); // ㆍ
+        assert!(is_old_jungseong('\u{D7C6}'));
+        assert!(!is_old_jungseong('\u{1160}')); // 채움 문자
+        assert!(is_old_jongseong('\u{11F0}')); // ㆁ
+        assert!(is_old_jongseong('\u{D7FB}'));
+        assert!(!is_old_jongseong('\u{D7C7}'));
+    }
+
+    #[test]
+    fn test_compose_conjoining_modern() {
+        // 현대 자모만 → 완성형
+        assert_eq!(
+            compose_conjoining(Some('\u{1112}'), Some('\u{1161}'), Some('\u{11AB}')),
+            Some("한".to_string())
+        );
+        assert_eq!(
+            compose_conjoining(Some('\u{1100}'), Some('\u{1161}'), None),
+            Some("가".to_string())
+        );
+        // 단독 현대 자모 → 호환 자모
+        assert_eq!(
+            compose_conjoining(Some('\u{1100}'), None, None),
+            Some("ㄱ".to_string())
+        );
+        assert_eq!(
+            compose_conjoining(None, Some('\u{1161}'), None),
+            Some("ㅏ".to_string())
+        );
+        assert_eq!(
+            compose_conjoining(None, None, Some('\u{11A8}')),
+            Some("ㄱ".to_string())
+        );
+        assert_eq!(compose_conjoining(None, None, None), None);
+    }
+
+    #[test]
+    fn test_compose_conjoining_old() {
+        // ㅿ + ㆍ → 첫가끝 시퀀스
+        assert_eq!(
+            compose_conjoining(Some('\u{1140}'), Some('\u{119E}'), None),
+            Some("\u{1140}\u{119E}".to_string())
+        );
+        // 현대 초중성 + 옛 종성 → 완성형 불가, 시퀀스
+        assert_eq!(
+            compose_conjoining(Some('\u{1100}'), Some('\u{1161}'), Some('\u{11F0}')),
+            Some("\u{1100}\u{1161}\u{11F0}".to_string())
+        );
+        // 옛 초성 단독 → 중성 채움
+        assert_eq!(
+            compose_conjoining(Some('\u{1140}'), None, None),
+            Some("\u{1140}\u{1160}".to_string())
+        );
+        // 초성 없는 옛 중성 → 초성 채움
+        assert_eq!(
+            compose_conjoining(None, Some('\u{119E}'), None),
+            Some("\u{115F}\u{119E}".to_string())
+        );
+        // 옛 종성 단독 → 초성·중성 채움
+        assert_eq!(
+            compose_conjoining(None, None, Some('\u{11F0}')),
+            Some("\u{115F}\u{1160}\u{11F0}".to_string())
+        );
+    }
 }
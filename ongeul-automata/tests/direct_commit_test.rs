@@ -0,0 +1,182 @@
+/// 직접 확정 모드 통합 테스트
+/// marked text 없이 delete_before + committed만으로 문서가 올바르게 갱신되는지 검증한다.
+use ongeul_automata::{HangulEngine, InputMode, ProcessResult};
+
+const LAYOUT_2BUL: &str = include_str!("../layouts/2-standard.json5");
+const LAYOUT_390: &str = include_str!("../layouts/3-390.json5");
+
+fn create_engine(layout: &str) -> HangulEngine {
+    let engine = HangulEngine::new();
+    engine.load_layout(layout.to_string()).unwrap();
+    engine.set_mode(InputMode::Korean);
+    engine.set_direct_commit(true);
+    engine
+}
+
+/// marked text를 지원하지 않는 클라이언트 흉내: 결과를 문서 문자열에 적용한다.
+fn apply(doc: &mut String, key: &str, result: &ProcessResult) {
+    assert_eq!(
+        result.composing, None,
+        "직접 확정 모드에서는 marked text가 없어야 한다"
+    );
+    for _ in 0..result.delete_before {
+        doc.pop();
+    }
+    if let Some(c) = &result.committed {
+        doc.push_str(c);
+    }
+    if !result.handled {
+        doc.push_str(key);
+    }
+}
+
+fn type_keys(engine: &HangulEngine, doc: &mut String, keys: &[&str]) {
+    for key in keys {
+        let result = engine.process_key(key.to_string());
+        apply(doc, key, &result);
+    }
+}
+
+#[test]
+fn test_2bul_each_key_updates_document() {
+    let engine = create_engine(LAYOUT_2BUL);
+    let mut doc = String::new();
+
+    // ㄱ → "ㄱ"
+    let result = engine.process_key("r".to_string());
+    assert_eq!(result.committed, Some("ㄱ".to_string()));
+    assert_eq!(result.delete_before, 0);
+    apply(&mut doc, "r", &result);
+
+    // ㅏ → "ㄱ"을 지우고 "가"
+    let result = engine.process_key("k".to_string());
+    assert_eq!(result.committed, Some("가".to_string()));
+    assert_eq!(result.delete_before, 1);
+    apply(&mut doc, "k", &result);
+    assert_eq!(doc, "가");
+}
+
+#[test]
+fn test_2bul_jongseong_split() {
+    // ㄱ ㅏ ㄴ ㅏ → "간" → "가나": 공통 앞부분 없음 → 1글자 지우고 "가나"
+    let engine = create_engine(LAYOUT_2BUL);
+    let mut doc = String::new();
+    type_keys(&engine, &mut doc, &["r", "k", "s"]);
+    assert_eq!(doc, "간");
+    let result = engine.process_key("k".to_string());
+    assert_eq!(result.delete_before, 1);
+    assert_eq!(result.committed, Some("가나".to_string()));
+    apply(&mut doc, "k", &result);
+    assert_eq!(doc, "가나");
+}
+
+#[test]
+fn test_2bul_sentence_and_passthrough() {
+    let engine = create_engine(LAYOUT_2BUL);
+    let mut doc = String::from(">");
+    type_keys(
+        &engine,
+        &mut doc,
+        &[
+            "g", "k", "s", "r", "m", "f", " ", "d", "l", "q", "f", "u", "r", ".",
+        ],
+    );
+    // 공백과 마침표는 레이아웃에 없는 키 → 시스템이 그대로 입력
+    assert_eq!(doc, ">한글 입력.");
+}
+
+#[test]
+fn test_2bul_backspace() {
+    // "한" → BS → "하" → BS → "ㅎ" → BS → "" → BS → 시스템 위임
+    let engine = create_engine(LAYOUT_2BUL);
+    let mut doc = String::from("x");
+    type_keys(&engine, &mut doc, &["g", "k", "s"]);
+    for expected in ["x하", "xㅎ", "x"] {
+        let result = engine.backspace();
+        assert!(result.handled);
+        apply(&mut doc, "", &result);
+        assert_eq!(doc, expected);
+    }
+    assert!(!engine.backspace().handled);
+}
+
+#[test]
+fn test_2bul_backspace_with_context() {
+    // 확정된 "과" 앞에서 BS → 음절을 되살려 "고"로 바꾸고 이어서 조합
+    let engine = create_engine(LAYOUT_2BUL);
+    let mut doc = String::from("과");
+    let result = engine.backspace_with_context(doc.clone());
+    assert_eq!(result.delete_before, 1);
+    assert_eq!(result.committed, Some("고".to_string()));
+    apply(&mut doc, "", &result);
+    assert_eq!(doc, "고");
+    type_keys(&engine, &mut doc, &["l"]);
+    assert_eq!(doc, "괴");
+}
+
+#[test]
+fn test_390_direct_commit() {
+    // 세벌식: ㅎ초(m) ㅏ중(f) ㄴ종(s) ㄱ초(k) ㅡ중(g) ㄹ종(w) → "한글"
+    let engine = create_engine(LAYOUT_390);
+    let mut doc = String::new();
+    type_keys(&engine, &mut doc, &["m", "f", "s"]);
+    assert_eq!(doc, "한");
+    // 새 초성: 앞 음절은 그대로 두고 "ㄱ"만 추가
+    let result = engine.process_key("k".to_string());
+    assert_eq!(result.delete_before, 0);
+    assert_eq!(result.committed, Some("ㄱ".to_string()));
+    apply(&mut doc, "k", &result);
+    type_keys(&engine, &mut doc, &["g", "w"]);
+    assert_eq!(doc, "한글");
+}
+
+#[test]
+fn test_390_backspace_double_vowel() {
+    // ㄱ초(k) ㅗ(v) ㅏ(f) → "과" → BS → "고"
+    let engine = create_engine(LAYOUT_390);
+    let mut doc = String::new();
+    type_keys(&engine, &mut doc, &["k", "v", "f"]);
+    assert_eq!(doc, "과");
+    let result = engine.backspace();
+    assert_eq!(result.delete_before, 1);
+    apply(&mut doc, "", &result);
+    assert_eq!(doc, "고");
+}
+
+#[test]
+fn test_flush_and_toggle_do_not_duplicate() {
+    // 이미 문서에 있으므로 flush/모드 전환은 아무것도 넣지 않는다
+    let engine = create_engine(LAYOUT_2BUL);
+    let mut doc = String::new();
+    type_keys(&engine, &mut doc, &["g", "k", "s"]);
+    let result = engine.flush();
+    assert_eq!(result.committed, None);
+    assert_eq!(result.delete_before, 0);
+
+    type_keys(&engine, &mut doc, &["r", "k"]);
+    let result = engine.toggle_mode();
+    assert_eq!(result.committed, None);
+    assert_eq!(result.delete_before, 0);
+    assert_eq!(doc, "한가");
+}
+
+#[test]
+fn test_switching_mode_off_commits_once() {
+    // 일반 모드에서 조합 중 → 직접 확정 켜기: 조합은 확정되어 한 번만 반환
+    let engine = HangulEngine::new();
+    engine.load_layout(LAYOUT_2BUL.to_string()).unwrap();
+    engine.set_mode(InputMode::Korean);
+    let result = engine.process_key("r".to_string());
+    assert_eq!(result.composing, Some("ㄱ".to_string()));
+    let result = engine.set_direct_commit(true);
+    assert_eq!(result.committed, Some("ㄱ".to_string()));
+
+    // 직접 확정 중 → 끄기: 이미 문서에 있으므로 반환할 것이 없다
+    engine.process_key("k".to_string());
+    let result = engine.set_direct_commit(false);
+    assert_eq!(result.committed, None);
+    assert_eq!(result.delete_before, 0);
+    // 이후 일반 모드로 조합
+    let result = engine.process_key("r".to_string());
+    assert_eq!(result.composing, Some("ㄱ".to_string()));
+}
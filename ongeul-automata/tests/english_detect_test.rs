@@ -0,0 +1,181 @@
+/// 자동 한영 전환 통합 테스트
+/// 한글 모드에서 친 영어 단어가 단어 경계에서 영어로 바뀌는지 문서 상태로 검증한다.
+use ongeul_automata::{HangulEngine, InputMode, ProcessResult};
+
+const LAYOUT_2BUL: &str = include_str!("../layouts/2-standard.json5");
+const LAYOUT_COLEMAK: &str = include_str!("../layouts/en-colemak.json5");
+
+const WORDS: &str = "\
+# 테스트 단어 목록
+git
+commit
+go
+hi
+";
+
+/// 테스트 단어 목록 파일을 쓰고 경로를 반환한다.
+fn word_list(name: &str) -> String {
+    let path = std::env::temp_dir().join(format!("ongeul-words-{name}.txt"));
+    std::fs::write(&path, WORDS).unwrap();
+    path.to_string_lossy().into_owned()
+}
+
+fn create_engine(name: &str) -> HangulEngine {
+    let engine = HangulEngine::new();
+    engine.load_layout(LAYOUT_2BUL.to_string()).unwrap();
+    engine.load_english_word_list(word_list(name)).unwrap();
+    engine.set_mode(InputMode::Korean);
+    engine
+}
+
+/// marked text를 지원하는 클라이언트 흉내: 확정 텍스트만 문서에 적용한다.
+/// 조합 중 텍스트는 marked text로 보이므로 문서에 넣지 않는다.
+fn apply(doc: &mut String, key: &str, result: &ProcessResult) {
+    for _ in 0..result.delete_before {
+        doc.pop();
+    }
+    if let Some(c) = &result.committed {
+        doc.push_str(c);
+    }
+    if !result.handled {
+        doc.push_str(key);
+    }
+}
+
+/// 키를 차례로 입력하고 마지막 결과를 반환한다.
+fn type_keys(engine: &HangulEngine, doc: &mut String, keys: &str) -> ProcessResult {
+    let mut last = None;
+    for ch in keys.chars() {
+        let key = ch.to_string();
+        let result = engine.process_key(key.clone());
+        apply(doc, &key, &result);
+        last = Some(result);
+    }
+    last.unwrap()
+}
+
+#[test]
+fn test_git_commit_becomes_english() {
+    let engine = create_engine("git-commit");
+    let mut doc = String::new();
+
+    let result = type_keys(&engine, &mut doc, "git ");
+    assert_eq!(result.committed, Some("git".to_string()));
+    assert_eq!(result.suggested_mode, Some(InputMode::English));
+    assert_eq!(doc, "git ");
+
+    // "채ㅡㅡ"는 이미 확정되어 문서에 있고 "샤"는 조합 중 → 확정분을 지우고 "commit"
+    let result = type_keys(&engine, &mut doc, "commit ");
+    assert_eq!(result.delete_before, 3);
+    assert_eq!(doc, "git commit ");
+}
+
+#[test]
+fn test_korean_word_is_kept() {
+    let engine = create_engine("korean");
+    let mut doc = String::new();
+    let result = type_keys(&engine, &mut doc, "dkssud ");
+    assert_eq!(result.suggested_mode, None);
+    assert_eq!(doc, "안녕 ");
+
+    // 목록에 있어도 한글로 자연스러운 단어는 그대로: go → "해"
+    type_keys(&engine, &mut doc, "go ");
+    assert_eq!(doc, "안녕 해 ");
+}
+
+#[test]
+fn test_no_suggestion_without_word_boundary() {
+    let engine = create_engine("no-boundary");
+    let mut doc = String::new();
+    let result = type_keys(&engine, &mut doc, "git");
+    assert_eq!(result.composing, Some("햣".to_string()));
+    assert_eq!(result.suggested_mode, None);
+}
+
+#[test]
+fn test_physical_non_character_key_is_boundary() {
+    // "git" + Enter(0x28) → 확정 텍스트가 영어로 바뀌고 Enter는 시스템에 위임
+    let engine = create_engine("enter");
+    let mut doc = String::new();
+    type_keys(&engine, &mut doc, "git");
+    let result = engine.process_physical_key(0x28, false);
+    assert!(!result.handled);
+    assert_eq!(result.committed, Some("git".to_string()));
+}
+
+#[test]
+fn test_backspace_disables_word() {
+    // 고쳐 친 단어는 사용자가 의도한 것으로 보고 바꾸지 않는다
+    let engine = create_engine("backspace");
+    let mut doc = String::new();
+    type_keys(&engine, &mut doc, "gix");
+    engine.backspace();
+    type_keys(&engine, &mut doc, "t");
+    let result = type_keys(&engine, &mut doc, " ");
+    assert_eq!(result.suggested_mode, None);
+}
+
+#[test]
+fn test_threshold_and_exceptions() {
+    let engine = create_engine("threshold");
+    let mut doc = String::new();
+    engine.set_english_detection_exceptions(vec!["Git".to_string()]);
+    type_keys(&engine, &mut doc, "git ");
+    assert_eq!(doc, "햣 ");
+
+    engine.set_english_detection_threshold(0.5);
+    type_keys(&engine, &mut doc, "go ");
+    assert_eq!(doc, "햣 go ");
+}
+
+#[test]
+fn test_settings_before_word_list() {
+    // 임계값과 예외 단어는 단어 목록보다 먼저 설정해도 적용되고, 목록을 다시 로드해도 유지된다
+    let path = word_list("settings-first");
+    let engine = HangulEngine::new();
+    engine.load_layout(LAYOUT_2BUL.to_string()).unwrap();
+    engine.set_english_detection_exceptions(vec!["Git".to_string()]);
+    engine.set_english_detection_threshold(0.5);
+    engine.load_english_word_list(path.clone()).unwrap();
+    engine.set_mode(InputMode::Korean);
+
+    let mut doc = String::new();
+    type_keys(&engine, &mut doc, "git go ");
+    assert_eq!(doc, "햣 go ");
+
+    engine.load_english_word_list(path).unwrap();
+    type_keys(&engine, &mut doc, "git go ");
+    assert_eq!(doc, "햣 go 햣 go ");
+}
+
+#[test]
+fn test_disabled_detection() {
+    let engine = create_engine("disabled");
+    engine.clear_english_word_list();
+    let mut doc = String::new();
+    type_keys(&engine, &mut doc, "git ");
+    assert_eq!(doc, "햣 ");
+}
+
+#[test]
+fn test_english_layout_text() {
+    // 영문 배열(Colemak)이 있으면 같은 키를 영문 모드에서 쳤을 때의 글자로 바꾼다
+    let engine = create_engine("colemak");
+    engine
+        .load_english_layout(LAYOUT_COLEMAK.to_string())
+        .unwrap();
+    let mut doc = String::new();
+    // QWERTY 위치 h l → Colemak "hi" (두벌식으로는 "ㅚ")
+    let result = type_keys(&engine, &mut doc, "hl ");
+    assert_eq!(result.committed, Some("hi".to_string()));
+    assert_eq!(doc, "hi ");
+}
+
+#[test]
+fn test_direct_commit_replaces_document_text() {
+    let engine = create_engine("direct");
+    engine.set_direct_commit(true);
+    let mut doc = String::new();
+    type_keys(&engine, &mut doc, "commit ");
+    assert_eq!(doc, "commit ");
+}
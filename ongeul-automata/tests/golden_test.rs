@@ -0,0 +1,86 @@
+/// 골든 테스트 벡터 실행기
+/// tests/vectors 아래의 모든 벡터 파일(*.txt)을 layouts/의 자판으로 실행한다. (형식은 `golden` 모듈 참고)
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use ongeul_automata::golden;
+use ongeul_automata::layout::{self, schema::LayoutType};
+
+const VECTORS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/vectors");
+const LAYOUTS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/layouts");
+
+/// 디렉터리 아래의 벡터 파일 (하위 디렉터리 포함, 경로 순)
+fn vector_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir).unwrap().flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            files.extend(vector_files(&path));
+        } else if path.extension().is_some_and(|ext| ext == "txt") {
+            files.push(path);
+        }
+    }
+    files.sort();
+    files
+}
+
+#[test]
+fn test_golden_vectors() {
+    let mut layouts = HashMap::new();
+    for file in layout::read_dir(Path::new(LAYOUTS_DIR)).unwrap() {
+        let parsed = file.layout.unwrap();
+        if parsed.layout_type != LayoutType::Latin {
+            layouts.insert(parsed.id, file.json);
+        }
+    }
+
+    let mut failures = Vec::new();
+    let mut covered = HashMap::new();
+    let mut count = 0;
+    for path in vector_files(Path::new(VECTORS_DIR)) {
+        let name = path
+            .strip_prefix(VECTORS_DIR)
+            .unwrap()
+            .display()
+            .to_string();
+        let text = std::fs::read_to_string(&path).unwrap();
+        let vectors = match golden::parse(&text) {
+            Ok(vectors) => vectors,
+            Err(e) => {
+                failures.push(format!("{name}: {e}"));
+                continue;
+            }
+        };
+        for vector in vectors {
+            count += 1;
+            let Some(json) = layouts.get(&vector.layout) else {
+                failures.push(format!(
+                    "{name}:{}: unknown layout '{}'",
+                    vector.line, vector.layout
+                ));
+                continue;
+            };
+            *covered.entry(vector.layout.clone()).or_insert(0) += 1;
+            if let Err(e) = vector.check(json) {
+                failures.push(format!("{name}:{}: {e}", vector.line));
+            }
+        }
+    }
+
+    // 모든 한글 자판에 벡터가 있어야 한다
+    let mut ids: Vec<&String> = layouts.keys().collect();
+    ids.sort();
+    for id in ids {
+        if !covered.contains_key(id) {
+            failures.push(format!("layout '{id}' has no golden vectors"));
+        }
+    }
+
+    assert!(count > 0, "no vectors in {VECTORS_DIR}");
+    assert!(
+        failures.is_empty(),
+        "{} of {count} golden vectors failed:\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+}
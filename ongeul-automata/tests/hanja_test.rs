@@ -0,0 +1,115 @@
+/// 한자 변환 통합 테스트
+/// 조합 중 음절과 방금 확정된 단어의 변환 경로를 검증한다.
+use ongeul_automata::{HangulEngine, InputMode};
+
+const LAYOUT_2BUL: &str = include_str!("../layouts/2-standard.json5");
+
+const DICTIONARY: &str = "\
+# 테스트 사전
+한:韓:나라 이름 한
+한:漢:한수 한
+국:國:나라 국
+한국:韓國:대한민국의 약칭
+";
+
+fn create_engine(name: &str) -> HangulEngine {
+    let path = std::env::temp_dir().join(format!("ongeul-hanja-{name}.txt"));
+    std::fs::write(&path, DICTIONARY).unwrap();
+
+    let engine = HangulEngine::new();
+    engine.load_layout(LAYOUT_2BUL.to_string()).unwrap();
+    engine
+        .load_hanja_dictionary(path.to_string_lossy().into_owned())
+        .unwrap();
+    engine.set_mode(InputMode::Korean);
+    engine
+}
+
+fn process_keys(engine: &HangulEngine, keys: &[&str]) -> String {
+    let mut committed = String::new();
+    for key in keys {
+        if let Some(c) = engine.process_key(key.to_string()).committed {
+            committed.push_str(&c);
+        }
+    }
+    committed
+}
+
+#[test]
+fn test_candidates_with_meaning() {
+    let engine = create_engine("candidates");
+    let candidates = engine.hanja_candidates("한".to_string());
+    assert_eq!(candidates.len(), 2);
+    assert_eq!(candidates[0].hanja, "韓");
+    assert_eq!(candidates[0].reading, "한");
+    assert_eq!(candidates[0].meaning, "나라 이름 한");
+    assert!(engine.hanja_candidates("없음".to_string()).is_empty());
+}
+
+#[test]
+fn test_convert_composing_syllable() {
+    // ㅎ ㅏ ㄴ → "한" 조합 중 → 漢
+    let engine = create_engine("composing");
+    process_keys(&engine, &["g", "k", "s"]);
+    assert_eq!(engine.hanja_conversion_target(), Some("한".to_string()));
+
+    let conversion = engine.convert_composing_to_hanja(1).unwrap();
+    assert_eq!(conversion.hanja, "漢");
+    assert_eq!(conversion.reading, "한");
+    assert_eq!(conversion.delete_before, 0);
+
+    // 조합이 폐기되었으므로 flush할 것이 없다
+    assert_eq!(engine.flush().committed, None);
+}
+
+#[test]
+fn test_convert_committed_word() {
+    // "한국" 입력 후 flush → 확정 단어 변환
+    let engine = create_engine("committed");
+    let mut committed = process_keys(&engine, &["g", "k", "s", "r", "n", "r"]);
+    committed.push_str(&engine.flush().committed.unwrap());
+    assert_eq!(committed, "한국");
+    assert_eq!(engine.hanja_conversion_target(), Some("한국".to_string()));
+
+    let conversion = engine.convert_composing_to_hanja(0).unwrap();
+    assert_eq!(conversion.hanja, "韓國");
+    assert_eq!(conversion.delete_before, 2);
+
+    // 변환 후에는 대상이 남지 않는다
+    assert_eq!(engine.hanja_conversion_target(), None);
+}
+
+#[test]
+fn test_committed_word_broken_by_passthrough_key() {
+    // "한" 확정 후 레이아웃에 없는 키(공백) → 단어 경계
+    let engine = create_engine("boundary");
+    process_keys(&engine, &["g", "k", "s", " "]);
+    assert_eq!(engine.hanja_conversion_target(), None);
+}
+
+#[test]
+fn test_backspace_shrinks_committed_word() {
+    // "한국" 확정 → 시스템 백스페이스 → "한"만 남음
+    let engine = create_engine("backspace");
+    process_keys(&engine, &["g", "k", "s", "r", "n", "r"]);
+    engine.flush();
+    assert!(!engine.backspace().handled);
+    assert_eq!(engine.hanja_conversion_target(), Some("한".to_string()));
+}
+
+#[test]
+fn test_invalid_index_and_missing_dictionary() {
+    let engine = create_engine("invalid");
+    process_keys(&engine, &["g", "k", "s"]);
+    assert!(engine.convert_composing_to_hanja(5).is_none());
+    // 변환 실패 시 조합은 유지된다
+    assert_eq!(engine.flush().committed, Some("한".to_string()));
+
+    let engine = HangulEngine::new();
+    assert!(engine.hanja_candidates("한".to_string()).is_empty());
+    assert!(
+        engine
+            .load_hanja_dictionary("/nonexistent/hanja.txt".to_string())
+            .is_err()
+    );
+}
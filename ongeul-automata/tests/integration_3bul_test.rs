@@ -164,6 +164,17 @@ fn test_390_mode_toggle() {
     assert_eq!(result.committed, Some("k".to_string()));
 }
 
+#[test]
+fn test_390_backspace_with_context() {
+    // 확정된 "닭" 앞에서 BS → "달" 조합 → ㄱ종(x)으로 다시 "닭"
+    let engine = create_engine_390();
+    let result = engine.backspace_with_context("닭".to_string());
+    assert_eq!(result.delete_before, 1);
+    assert_eq!(result.composing, Some("달".to_string()));
+    let result = engine.process_key("x".to_string());
+    assert_eq!(result.composing, Some("닭".to_string()));
+}
+
 // ── 세벌식 390 Shift 매핑 테스트 ──
 
 #[test]
@@ -420,3 +431,254 @@ fn test_390_auto_reorder_pending_fail() {
     assert_eq!(committed, "ㄱㄴ");
     assert_eq!(composing, Some("ㄷ".to_string()));
 }
+
+// ── 옛한글 세벌식 390 테스트 ──
+
+const LAYOUT_390_OLD: &str = include_str!("../layouts/3-390-old.json5");
+
+fn create_engine_390_old() -> HangulEngine {
+    let engine = HangulEngine::new();
+    engine.load_layout(LAYOUT_390_OLD.to_string()).unwrap();
+    engine.set_mode(InputMode::Korean);
+    engine
+}
+
+#[test]
+fn test_390_old_hunminjeongeum() {
+    // "ᄒᆞᆫ" "글" — ㅎ초(m) ㆍ(G) ㄴ종(s) ㄱ초(k) ㅡ중(g) ㄹ종(w)
+    let engine = create_engine_390_old();
+    let (committed, composing) = process_keys(&engine, &["m", "G", "s", "k", "g", "w"]);
+    assert_eq!(committed, "\u{1112}\u{119E}\u{11AB}");
+    assert_eq!(composing, Some("글".to_string()));
+}
+
+#[test]
+fn test_390_old_three_jamo_chain_and_backspace() {
+    // ㅂ ㅅ ㄷ 연쇄 → ᄣ, ㅏ중 → "ᄣᅡ", BS 두 번 → ᄡ 단독
+    let engine = create_engine_390_old();
+    let (_, composing) = process_keys(&engine, &[";", "n", "u", "f"]);
+    assert_eq!(composing, Some("\u{1123}\u{1161}".to_string()));
+    engine.backspace();
+    let result = engine.backspace();
+    assert_eq!(result.composing, Some("\u{1121}\u{1160}".to_string()));
+}
+
+#[test]
+fn test_390_old_modern_text_unchanged() {
+    // 현대 한글은 완성형으로 출력 — "한글"
+    let engine = create_engine_390_old();
+    let (committed, composing) = process_keys(&engine, &["m", "f", "s", "k", "g", "w"]);
+    assert_eq!(committed, "한");
+    assert_eq!(composing, Some("글".to_string()));
+}
+
+#[test]
+fn test_390_old_non_jamo_flushes() {
+    // ᅀᅡ 조합 중 숫자(Shift+U = 7) → 확정 + 숫자
+    let engine = create_engine_390_old();
+    process_keys(&engine, &["Y", "f"]);
+    let result = engine.process_key("U".to_string());
+    assert_eq!(result.committed, Some("\u{1140}\u{1161}7".to_string()));
+    assert_eq!(result.composing, None);
+}
+
+#[test]
+fn test_390_old_jamo_on_modern_layout_is_literal() {
+    // 현대 세벌식 배열의 옛한글 자모는 조합하지 않고 그대로 확정한다
+    let engine = HangulEngine::new();
+    engine
+        .load_layout(r#"{ id: "x", extends: "3-390", keymap: { "`": "0x1140" } }"#.to_string())
+        .unwrap();
+    engine.set_mode(InputMode::Korean);
+    let result = engine.process_key("`".to_string());
+    assert!(result.handled);
+    assert_eq!(result.committed, Some("\u{1140}".to_string()));
+    assert_eq!(result.composing, None);
+
+    let (committed, composing) = process_keys(&engine, &["k", "f", "`"]);
+    assert_eq!(committed, "가\u{1140}");
+    assert_eq!(composing, None);
+}
+
+// ── 신세벌식 테스트 ──
+
+const LAYOUT_SHIN: &str = include_str!("../layouts/3-shin.json5");
+
+fn create_engine_shin() -> HangulEngine {
+    let engine = HangulEngine::new();
+    engine.load_layout(LAYOUT_SHIN.to_string()).unwrap();
+    engine.set_mode(InputMode::Korean);
+    engine
+}
+
+#[test]
+fn test_shin_hangul_word() {
+    // "한글" = ㅎ초(m) f(ㅏ) s(ㄴ종) ㄱ초(k) g(ㅡ) w(ㄹ종)
+    let engine = create_engine_shin();
+    let (committed, composing) = process_keys(&engine, &["m", "f", "s", "k", "g", "w"]);
+    assert_eq!(committed, "한");
+    assert_eq!(composing, Some("글".to_string()));
+}
+
+#[test]
+fn test_shin_sebeolsik_word() {
+    // "세벌식" = ㅅ초(n) c(ㅔ) ㅂ초(;) t(ㅓ) w(ㄹ종) ㅅ초(n) d(ㅣ) x(ㄱ종)
+    let engine = create_engine_shin();
+    let (committed, composing) = process_keys(&engine, &["n", "c", ";", "t", "w", "n", "d", "x"]);
+    assert_eq!(committed, "세벌");
+    assert_eq!(composing, Some("식".to_string()));
+}
+
+#[test]
+fn test_shin_compound_vowel_and_double_jongseong() {
+    // "왜" = ㅇ초(j) ㅗ(/) r(ㅐ), "닭" = ㄷ초(u) f(ㅏ) w(ㄹ종) x(ㄱ종)
+    let engine = create_engine_shin();
+    let (committed, composing) = process_keys(&engine, &["j", "/", "r", "u", "f", "w", "x"]);
+    assert_eq!(committed, "왜");
+    assert_eq!(composing, Some("닭".to_string()));
+}
+
+#[test]
+fn test_shin_backspace_changes_key_meaning() {
+    // "간" → BS → "가" → BS → "ㄱ" → s는 이제 ㅒ
+    let engine = create_engine_shin();
+    process_keys(&engine, &["k", "f", "s"]);
+    assert_eq!(engine.backspace().composing, Some("가".to_string()));
+    assert_eq!(engine.backspace().composing, Some("ㄱ".to_string()));
+    let (_, composing) = process_keys(&engine, &["s"]);
+    assert_eq!(composing, Some("걔".to_string()));
+}
+
+#[test]
+fn test_shin_unmapped_key_flushes() {
+    let engine = create_engine_shin();
+    let (committed, _) = process_keys(&engine, &["k", "f"]);
+    assert_eq!(committed, "");
+    let result = engine.process_key("1".to_string());
+    assert_eq!(result.committed, Some("가".to_string()));
+    assert!(!result.handled);
+}
+
+// ── 모아치기 테스트 ──
+
+/// 모아치기를 켠(허용 시간 50ms) 세벌식 390 엔진
+fn create_chord_engine() -> HangulEngine {
+    let engine = create_engine_390();
+    engine.set_chord_window(50);
+    engine
+}
+
+/// (키, 누름 시각, 뗌 시각) 목록을 시각 순 이벤트로 재생한다.
+fn play_chords(engine: &HangulEngine, keys: &[(&str, u64, u64)]) -> (String, Option<String>) {
+    let mut events: Vec<(u64, bool, &str)> = Vec::new();
+    for &(key, down, up) in keys {
+        events.push((down, true, key));
+        events.push((up, false, key));
+    }
+    events.sort_by_key(|&(t, is_down, _)| (t, !is_down));
+
+    let mut committed = String::new();
+    let mut composing = None;
+    for (t, is_down, key) in events {
+        let result = if is_down {
+            engine.key_down(key.to_string(), t)
+        } else {
+            engine.key_up(key.to_string(), t)
+        };
+        if let Some(c) = &result.committed {
+            committed.push_str(c);
+        }
+        if result.handled {
+            composing = result.composing;
+        }
+    }
+    (committed, composing)
+}
+
+#[test]
+fn test_chord_off_by_default() {
+    // 모아치기는 켜지 않으면 동작하지 않는다: 빠르게 이어 친 키도 순서대로 처리
+    let engine = create_engine_390();
+    let chord = play_chords(&engine, &[("s", 0, 60), ("f", 10, 70), ("k", 20, 80)]);
+    let sequential = process_keys(&create_engine_390(), &["s", "f", "k"]);
+    assert_eq!(chord, sequential);
+    assert_ne!(chord.1, Some("간".to_string()));
+}
+
+#[test]
+fn test_chord_reversed_order() {
+    // ㄴ종(s) ㅏ중(f) ㄱ초(k)를 거의 동시에 → "간"
+    let engine = create_chord_engine();
+    let (committed, composing) =
+        play_chords(&engine, &[("s", 0, 60), ("f", 10, 70), ("k", 20, 80)]);
+    assert_eq!(committed, "");
+    assert_eq!(composing, Some("간".to_string()));
+}
+
+#[test]
+fn test_chord_consecutive_syllables() {
+    // 모아치기 "한" + 모아치기 "글" (각 묶음은 거꾸로 누름)
+    let engine = create_chord_engine();
+    let (committed, composing) = play_chords(
+        &engine,
+        &[
+            ("s", 0, 40),
+            ("f", 5, 40),
+            ("m", 10, 45),
+            ("w", 100, 140),
+            ("g", 105, 140),
+            ("k", 110, 145),
+        ],
+    );
+    assert_eq!(committed, "한");
+    assert_eq!(composing, Some("글".to_string()));
+}
+
+#[test]
+fn test_chord_outside_window_is_sequential() {
+    // 허용 시간(50ms)을 넘겨 눌린 키는 새 묶음 → 순서대로 처리
+    let engine = create_chord_engine();
+    let (committed, composing) = play_chords(&engine, &[("s", 0, 200), ("k", 100, 210)]);
+    // ㄴ종 단독 확정 후 ㄱ 초성 조합
+    assert_eq!(committed, "ㄴ");
+    assert_eq!(composing, Some("ㄱ".to_string()));
+}
+
+#[test]
+fn test_chord_window_configurable() {
+    let engine = create_chord_engine();
+    engine.set_chord_window(200);
+    let (committed, composing) = play_chords(&engine, &[("s", 0, 200), ("k", 100, 210)]);
+    assert_eq!(committed, "");
+    assert_eq!(composing, Some("ㄱ".to_string()));
+
+    // 0이면 누르는 즉시 처리
+    let engine = create_chord_engine();
+    engine.set_chord_window(0);
+    let result = engine.key_down("k".to_string(), 0);
+    assert_eq!(result.composing, Some("ㄱ".to_string()));
+    assert!(!engine.key_up("k".to_string(), 10).handled);
+}
+
+#[test]
+fn test_chord_flush_resolves_pending_keys() {
+    // 키를 떼기 전에 flush → 모아 둔 키를 조합해 확정
+    let engine = create_chord_engine();
+    engine.key_down("f".to_string(), 0);
+    engine.key_down("k".to_string(), 5);
+    assert_eq!(engine.flush().committed, Some("가".to_string()));
+    // 이미 처리된 키의 뗌은 무시
+    assert!(!engine.key_up("k".to_string(), 30).handled);
+}
+
+#[test]
+fn test_chord_non_jamo_key_not_buffered() {
+    // 레이아웃에 없는 키(-)는 묶지 않고 즉시 처리: 모아 둔 "가" 확정 후 패스스루
+    let engine = create_chord_engine();
+    engine.key_down("k".to_string(), 0);
+    engine.key_down("f".to_string(), 5);
+    let result = engine.key_down("-".to_string(), 10);
+    assert_eq!(result.committed, Some("가".to_string()));
+    assert!(!result.handled);
+    assert!(!engine.key_up("k".to_string(), 30).handled);
+}
@@ -1,6 +1,6 @@
 /// HangulEngine 통합 테스트
 /// 설계 문서의 핵심 테스트 시나리오를 검증한다.
-use ongeul_automata::{HangulEngine, InputMode};
+use ongeul_automata::{EngineError, HangulEngine, InputMode, list_layouts};
 
 const LAYOUT_2BUL: &str = include_str!("../layouts/2-standard.json5");
 
@@ -110,6 +110,44 @@ fn test_backspace_to_empty() {
     assert!(!result.handled);
 }
 
+#[test]
+fn test_backspace_with_context_reopens_syllable() {
+    // "값" 확정 후 BS → 앞 글자를 지우고 "갑" 조합 → ㅏ 입력 시 "가" + "바"
+    let engine = create_engine();
+    process_keys(&engine, &["r", "k", "q", "t"]);
+    engine.flush();
+    let result = engine.backspace_with_context("값".to_string());
+    assert!(result.handled);
+    assert_eq!(result.delete_before, 1);
+    assert_eq!(result.committed, None);
+    assert_eq!(result.composing, Some("갑".to_string()));
+
+    let (committed, composing) = process_keys(&engine, &["k"]);
+    assert_eq!(committed, "가");
+    assert_eq!(composing, Some("바".to_string()));
+}
+
+#[test]
+fn test_backspace_with_context_while_composing() {
+    // 조합 중이면 일반 백스페이스와 같다
+    let engine = create_engine();
+    process_keys(&engine, &["g", "k", "s"]);
+    let result = engine.backspace_with_context("값".to_string());
+    assert_eq!(result.delete_before, 0);
+    assert_eq!(result.composing, Some("하".to_string()));
+}
+
+#[test]
+fn test_backspace_with_context_non_hangul() {
+    // 커서 앞이 한글 음절이 아니면 시스템에 위임
+    let engine = create_engine();
+    for text in ["", "abc", "가ㄱ"] {
+        let result = engine.backspace_with_context(text.to_string());
+        assert!(!result.handled);
+        assert_eq!(result.delete_before, 0);
+    }
+}
+
 // ── 모드 전환 ──
 
 #[test]
@@ -239,3 +277,263 @@ fn test_eui_combination() {
     assert_eq!(committed, "");
     assert_eq!(composing, Some("의".to_string()));
 }
+
+// ── 물리 키 입력 ──
+
+/// HID usage 시퀀스를 처리하고 (committed 누적, 최종 composing) 반환
+fn process_physical_keys(engine: &HangulEngine, keys: &[(u32, bool)]) -> (String, Option<String>) {
+    let mut committed = String::new();
+    let mut composing = None;
+    for &(usage, shift) in keys {
+        let result = engine.process_physical_key(usage, shift);
+        if let Some(c) = &result.committed {
+            committed.push_str(c);
+        }
+        composing = result.composing;
+    }
+    (committed, composing)
+}
+
+#[test]
+fn test_physical_key_hangul_word() {
+    // G K S R M F 위치 → "한글" (시스템 배열과 무관)
+    let engine = create_engine();
+    let (committed, composing) = process_physical_keys(
+        &engine,
+        &[
+            (0x0A, false),
+            (0x0E, false),
+            (0x16, false),
+            (0x15, false),
+            (0x10, false),
+            (0x09, false),
+        ],
+    );
+    assert_eq!(committed, "한");
+    assert_eq!(composing, Some("글".to_string()));
+}
+
+#[test]
+fn test_physical_key_shift() {
+    // Shift + R 위치 → ㄲ, K 위치 → ㅏ → "까"
+    let engine = create_engine();
+    let (_, composing) = process_physical_keys(&engine, &[(0x15, true), (0x0E, false)]);
+    assert_eq!(composing, Some("까".to_string()));
+}
+
+#[test]
+fn test_physical_key_non_character_flushes() {
+    // "하" 조합 중 Enter(0x28) → 확정 후 패스스루
+    let engine = create_engine();
+    process_physical_keys(&engine, &[(0x0A, false), (0x0E, false)]);
+    let result = engine.process_physical_key(0x28, false);
+    assert!(!result.handled);
+    assert_eq!(result.committed, Some("하".to_string()));
+}
+
+#[test]
+fn test_physical_key_english_mode_not_handled() {
+    let engine = create_engine();
+    engine.set_mode(InputMode::English);
+    assert!(!engine.process_physical_key(0x14, false).handled);
+}
+
+#[test]
+fn test_physical_key_usage_lookup() {
+    assert_eq!(
+        ongeul_automata::physical_key_usage("KeyQ".to_string()),
+        Some(0x14)
+    );
+    assert_eq!(
+        ongeul_automata::physical_key_usage("Enter".to_string()),
+        None
+    );
+}
+
+// ── 영문 배열 ──
+
+const LAYOUT_COLEMAK: &str = include_str!("../layouts/en-colemak.json5");
+const LAYOUT_DVORAK: &str = include_str!("../layouts/en-dvorak.json5");
+
+/// 키 시퀀스의 committed만 이어 붙인다.
+fn type_english(engine: &HangulEngine, keys: &[&str]) -> String {
+    process_keys(engine, keys).0
+}
+
+#[test]
+fn test_english_layout_colemak() {
+    // QWERTY 위치 h k u u ; → Colemak "hello" (대문자는 Shift 레이블)
+    let engine = create_engine();
+    engine
+        .load_english_layout(LAYOUT_COLEMAK.to_string())
+        .unwrap();
+    engine.set_mode(InputMode::English);
+    assert_eq!(type_english(&engine, &["h", "k", "u", "u", ";"]), "hello");
+    assert_eq!(type_english(&engine, &["H", "K"]), "HE");
+    // 매핑이 없는 키는 그대로
+    assert_eq!(type_english(&engine, &["a", "1", " "]), "a1 ");
+}
+
+#[test]
+fn test_english_layout_does_not_affect_korean() {
+    // 영문은 Dvorak, 한글은 두벌식
+    let engine = create_engine();
+    engine
+        .load_english_layout(LAYOUT_DVORAK.to_string())
+        .unwrap();
+    let (_, composing) = process_keys(&engine, &["g", "k", "s"]);
+    assert_eq!(composing, Some("한".to_string()));
+
+    engine.toggle_mode();
+    assert_eq!(type_english(&engine, &["j", "d", "p", "p", "s"]), "hello");
+}
+
+#[test]
+fn test_english_layout_physical_key() {
+    // 영문 배열이 있으면 영문 모드 물리 키도 엔진이 처리한다
+    let engine = create_engine();
+    engine.set_mode(InputMode::English);
+    assert!(!engine.process_physical_key(0x0D, false).handled);
+
+    engine
+        .load_english_layout(LAYOUT_DVORAK.to_string())
+        .unwrap();
+    let result = engine.process_physical_key(0x0D, false); // J 위치 → Dvorak h
+    assert_eq!(result.committed, Some("h".to_string()));
+
+    engine.clear_english_layout();
+    assert_eq!(type_english(&engine, &["j"]), "j");
+}
+
+#[test]
+fn test_english_layout_workman() {
+    // QWERTY 위치 d k m m l → Workman "hello"
+    let engine = create_engine();
+    engine
+        .load_english_layout(include_str!("../layouts/en-workman.json5").to_string())
+        .unwrap();
+    engine.set_mode(InputMode::English);
+    assert_eq!(type_english(&engine, &["d", "k", "m", "m", "l"]), "hello");
+}
+
+#[test]
+fn test_layout_type_mismatch_rejected() {
+    let engine = HangulEngine::new();
+    assert!(engine.load_layout(LAYOUT_COLEMAK.to_string()).is_err());
+    assert!(engine.load_english_layout(LAYOUT_2BUL.to_string()).is_err());
+}
+
+// ── 동봉 레이아웃 ──
+
+#[test]
+fn test_load_builtin_layout() {
+    // 동봉 레이아웃은 ID로 로드하고, latin 배열은 영문 모드 배열이 된다
+    let engine = HangulEngine::new();
+    engine.load_builtin_layout("3-390".into()).unwrap();
+    engine.load_builtin_layout("en-dvorak".into()).unwrap();
+    engine.set_mode(InputMode::Korean);
+    let (_, composing) = process_keys(&engine, &["k", "f"]);
+    assert_eq!(composing, Some("가".to_string()));
+    engine.toggle_mode();
+    assert_eq!(type_english(&engine, &["j"]), "h");
+
+    assert!(matches!(
+        engine.load_builtin_layout("nope".into()),
+        Err(EngineError::LayoutError { .. })
+    ));
+    // 목록의 모든 레이아웃을 로드할 수 있다
+    for info in list_layouts() {
+        engine.load_builtin_layout(info.id).unwrap();
+    }
+}
+
+// ── 로마자 소리 입력 ──
+
+const LAYOUT_ROMAJA: &str = include_str!("../layouts/romaja.json5");
+
+fn create_romaja_engine() -> HangulEngine {
+    let engine = HangulEngine::new();
+    engine.load_layout(LAYOUT_ROMAJA.to_string()).unwrap();
+    engine.set_mode(InputMode::Korean);
+    engine
+}
+
+#[test]
+fn test_romaja_hangul_then_space() {
+    // h a n g u l → "한굴", 공백은 조합 확정 후 시스템에 위임
+    let engine = create_romaja_engine();
+    let (committed, composing) = process_keys(&engine, &["h", "a", "n", "g", "u", "l"]);
+    assert_eq!(committed, "한");
+    assert_eq!(composing, Some("굴".to_string()));
+
+    let result = engine.process_key(" ".to_string());
+    assert_eq!(result.committed, Some("굴".to_string()));
+    assert!(!result.handled);
+}
+
+#[test]
+fn test_romaja_uppercase_and_sentence() {
+    // "Annyeong haseyo" → "안녕 하세요"
+    let engine = create_romaja_engine();
+    let keys: Vec<String> = "Annyeong haseyo".chars().map(String::from).collect();
+    let mut committed = String::new();
+    for key in &keys {
+        let result = engine.process_key(key.clone());
+        if let Some(c) = &result.committed {
+            committed.push_str(c);
+        }
+        if !result.handled {
+            committed.push_str(key);
+        }
+    }
+    committed.push_str(&engine.flush().committed.unwrap_or_default());
+    assert_eq!(committed, "안녕 하세요");
+}
+
+#[test]
+fn test_romaja_backspace_one_keystroke() {
+    // "ch" 입력 중 백스페이스 → "c"
+    let engine = create_romaja_engine();
+    process_keys(&engine, &["c", "h"]);
+    assert_eq!(engine.backspace().composing, Some("c".to_string()));
+    assert_eq!(engine.backspace().composing, None);
+    assert!(!engine.backspace().handled);
+}
+
+// ── 한/영 착오 변환 ──
+
+#[test]
+fn test_convert_mistyped_english() {
+    let engine = create_engine();
+    assert_eq!(
+        engine.convert_to_hangul("dkssudgktpdy".to_string()),
+        "안녕하세요"
+    );
+    assert_eq!(
+        engine.convert_to_latin("안녕하세요".to_string()),
+        "dkssudgktpdy"
+    );
+}
+
+#[test]
+fn test_convert_does_not_touch_composing() {
+    let engine = create_engine();
+    process_keys(&engine, &["g", "k"]);
+    assert_eq!(engine.convert_to_hangul("rk".to_string()), "가");
+    let result = engine.flush();
+    assert_eq!(result.committed, Some("하".to_string()));
+}
+
+#[test]
+fn test_convert_with_english_layout() {
+    // 영문 배열(Colemak)로 입력된 글자는 먼저 키 레이블로 되돌린 뒤 한글로 바꾼다
+    let engine = create_engine();
+    engine
+        .load_english_layout(LAYOUT_COLEMAK.to_string())
+        .unwrap();
+    engine.set_mode(InputMode::English);
+    let typed = type_english(&engine, &["d", "k", "s", "s", "u", "d"]);
+    engine.set_mode(InputMode::Korean);
+    assert_eq!(engine.convert_to_hangul(typed.clone()), "안녕");
+    assert_eq!(engine.convert_to_latin("안녕".to_string()), typed);
+}
@@ -0,0 +1,179 @@
+/// 키 이벤트 라우팅 통합 테스트
+/// handle_event가 엔진 동작과 프런트엔드가 할 일을 함께 결정하는지 검증한다.
+use ongeul_automata::event::{KeyAction, KeyEvent};
+use ongeul_automata::toggle::ModifierFlags;
+use ongeul_automata::{HangulEngine, InputMode, KeyEventResult};
+
+const LAYOUT_2BUL: &str = include_str!("../layouts/2-standard.json5");
+const LAYOUT_COLEMAK: &str = include_str!("../layouts/en-colemak.json5");
+
+const ENTER: u32 = 0x28;
+const ESCAPE: u32 = 0x29;
+const BACKSPACE: u32 = 0x2A;
+const SPACE: u32 = 0x2C;
+const ARROW_LEFT: u32 = 0x50;
+
+fn create_engine() -> HangulEngine {
+    let engine = HangulEngine::new();
+    engine.load_layout(LAYOUT_2BUL.to_string()).unwrap();
+    engine.set_mode(InputMode::Korean);
+    engine
+}
+
+fn key(keycode: u32, characters: Option<&str>) -> KeyEvent {
+    KeyEvent {
+        keycode,
+        characters: characters.map(str::to_string),
+        modifiers: ModifierFlags::default(),
+        caps_lock: false,
+    }
+}
+
+/// QWERTY 문자 키 (keycode는 라우팅에 쓰이지 않으므로 A 위치로 둔다)
+fn char_key(ch: &str) -> KeyEvent {
+    key(0x04, Some(ch))
+}
+
+fn type_chars(engine: &HangulEngine, chars: &str) -> KeyEventResult {
+    let mut last = None;
+    for ch in chars.chars() {
+        last = Some(engine.handle_event(char_key(&ch.to_string()), false));
+    }
+    last.unwrap()
+}
+
+#[test]
+fn test_character_keys_are_processed() {
+    let engine = create_engine();
+    let r = type_chars(&engine, "gks");
+    assert_eq!(r.action, KeyAction::Apply);
+    assert!(r.result.handled);
+    assert_eq!(r.result.composing, Some("한".to_string()));
+}
+
+#[test]
+fn test_caps_lock_is_normalized() {
+    // CapsLock ON: OS가 "R"을 보내도 ㄱ (Shift면 ㄲ)
+    let engine = create_engine();
+    let mut e = char_key("R");
+    e.caps_lock = true;
+    let r = engine.handle_event(e, false);
+    assert_eq!(r.result.composing, Some("ㄱ".to_string()));
+
+    let mut e = char_key("r");
+    e.caps_lock = true;
+    e.modifiers.shift = true;
+    let r = engine.handle_event(e, false);
+    assert_eq!(r.result.committed, Some("ㄱ".to_string()));
+    assert_eq!(r.result.composing, Some("ㄲ".to_string()));
+}
+
+#[test]
+fn test_space_flushes_and_passes() {
+    let engine = create_engine();
+    type_chars(&engine, "rk");
+    let r = engine.handle_event(key(SPACE, Some(" ")), false);
+    assert_eq!(r.action, KeyAction::Apply);
+    assert!(!r.result.handled);
+    assert_eq!(r.result.committed, Some("가".to_string()));
+    assert_eq!(r.result.composing, None);
+}
+
+#[test]
+fn test_backspace() {
+    let engine = create_engine();
+    type_chars(&engine, "rk");
+    let r = engine.handle_event(key(BACKSPACE, None), false);
+    assert_eq!(r.action, KeyAction::Apply);
+    assert!(r.result.handled);
+    assert_eq!(r.result.composing, Some("ㄱ".to_string()));
+
+    engine.handle_event(key(BACKSPACE, None), false);
+    // 조합이 비면 시스템에 위임
+    let r = engine.handle_event(key(BACKSPACE, None), false);
+    assert!(!r.result.handled);
+}
+
+#[test]
+fn test_enter_reposts_only_when_composing() {
+    let engine = create_engine();
+    type_chars(&engine, "rk");
+    let r = engine.handle_event(key(ENTER, Some("\r")), false);
+    assert_eq!(r.action, KeyAction::Enter { repost: true });
+    assert_eq!(r.result.committed, Some("가".to_string()));
+
+    let r = engine.handle_event(key(ENTER, Some("\r")), false);
+    assert_eq!(r.action, KeyAction::Enter { repost: false });
+    assert!(!r.result.handled);
+}
+
+#[test]
+fn test_enter_in_direct_commit_mode_does_not_repost() {
+    let engine = create_engine();
+    engine.set_direct_commit(true);
+    type_chars(&engine, "rk");
+    let r = engine.handle_event(key(ENTER, Some("\r")), false);
+    assert_eq!(r.action, KeyAction::Enter { repost: false });
+}
+
+#[test]
+fn test_escape_and_control_bracket() {
+    let engine = create_engine();
+    type_chars(&engine, "rk");
+    let r = engine.handle_event(key(ESCAPE, Some("\u{1b}")), false);
+    assert_eq!(r.action, KeyAction::Escape);
+    assert_eq!(r.result.committed, Some("가".to_string()));
+
+    let mut e = key(0x2F, Some("\u{1b}"));
+    e.modifiers.control = true;
+    let r = engine.handle_event(e, false);
+    assert_eq!(r.action, KeyAction::Escape);
+}
+
+#[test]
+fn test_shortcut_and_arrow_flush() {
+    let engine = create_engine();
+    type_chars(&engine, "rk");
+    let mut e = char_key("s");
+    e.modifiers.command = true;
+    let r = engine.handle_event(e, false);
+    assert_eq!(r.action, KeyAction::Apply);
+    assert!(!r.result.handled);
+    assert_eq!(r.result.committed, Some("가".to_string()));
+
+    type_chars(&engine, "sk");
+    let r = engine.handle_event(key(ARROW_LEFT, None), false);
+    assert!(!r.result.handled);
+    assert_eq!(r.result.committed, Some("나".to_string()));
+}
+
+#[test]
+fn test_shift_space_toggle_keeps_state() {
+    let engine = create_engine();
+    type_chars(&engine, "rk");
+    let mut e = key(SPACE, Some(" "));
+    e.modifiers.shift = true;
+    let r = engine.handle_event(e, true);
+    assert_eq!(r.action, KeyAction::ToggleMode);
+    // 모드 전환은 프런트엔드 몫: 엔진은 조합을 그대로 둔다
+    assert_eq!(r.result.composing, Some("가".to_string()));
+    assert_eq!(engine.get_mode(), InputMode::Korean);
+}
+
+#[test]
+fn test_english_mode() {
+    let engine = create_engine();
+    engine.set_mode(InputMode::English);
+    let r = engine.handle_event(char_key("g"), false);
+    assert_eq!(r.action, KeyAction::PassToSystem);
+    assert!(!r.result.handled);
+
+    // 영문 배열이 있으면 엔진이 입력한다 (QWERTY k → Colemak e)
+    engine
+        .load_english_layout(LAYOUT_COLEMAK.to_string())
+        .unwrap();
+    let r = engine.handle_event(char_key("k"), false);
+    assert_eq!(r.action, KeyAction::Apply);
+    assert!(r.result.handled);
+    assert_eq!(r.result.committed, Some("e".to_string()));
+}
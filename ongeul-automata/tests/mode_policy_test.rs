@@ -0,0 +1,132 @@
+/// 앱별 입력 모드 정책 통합 테스트
+/// 정책 파일 저장/복원과 공유 잠금 목록을 검증한다.
+use std::path::{Path, PathBuf};
+
+use ongeul_automata::{InputMode, ModePolicy};
+
+const SHARED: &str = r#"
+// 팀 공유 잠금 목록
+{
+  version: 1,
+  locked: ["com.microsoft.rdc.*", "com.apple.Terminal"],
+  defaults: [{ app: "com.kakao.*", mode: "korean" }],
+}
+"#;
+
+fn temp_path(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("ongeul-policy-{name}"));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir.join("mode-policy.json5")
+}
+
+fn open(path: &Path) -> std::sync::Arc<ModePolicy> {
+    ModePolicy::open(path.to_string_lossy().into_owned()).unwrap()
+}
+
+#[test]
+fn test_policy_persists_across_open() {
+    let path = temp_path("persist");
+    let policy = open(&path);
+    assert_eq!(policy.activate_app("com.apple.Safari".into()), None);
+    // 바뀌기 전에는 파일을 만들지 않는다
+    assert!(!path.exists());
+
+    policy.save_mode("com.apple.Safari".into(), InputMode::Korean);
+    policy.lock("com.parallels.desktop".into(), InputMode::Korean);
+    // 닫을 때 남은 변경을 쓴다
+    drop(policy);
+    assert!(path.exists());
+
+    let reopened = open(&path);
+    assert_eq!(
+        reopened.activate_app("com.apple.Safari".into()),
+        Some(InputMode::Korean)
+    );
+    assert!(reopened.is_locked("com.parallels.desktop".into()));
+    assert_eq!(
+        reopened.unlock("com.parallels.desktop".into()),
+        InputMode::Korean
+    );
+    reopened.save().unwrap();
+    assert!(!open(&path).is_locked("com.parallels.desktop".into()));
+}
+
+#[test]
+fn test_policy_saved_in_background() {
+    let path = temp_path("background");
+    let policy = open(&path);
+    policy.save_mode("com.apple.Safari".into(), InputMode::English);
+
+    // 키 입력 경로에서는 쓰지 않고 저장 스레드가 잠시 뒤 쓴다
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+    while !path.exists() && std::time::Instant::now() < deadline {
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+    assert_eq!(
+        open(&path).last_mode("com.apple.Safari".into()),
+        Some(InputMode::English)
+    );
+}
+
+#[test]
+fn test_memory_policy_save_is_error() {
+    let policy = ModePolicy::new();
+    policy.lock("com.test.app".into(), InputMode::Korean);
+    assert!(policy.save().is_err());
+}
+
+#[test]
+fn test_shared_lock_list() {
+    let path = temp_path("shared");
+    let shared = path.with_file_name("team.json5");
+    std::fs::create_dir_all(shared.parent().unwrap()).unwrap();
+    std::fs::write(&shared, SHARED).unwrap();
+
+    let policy = open(&path);
+    policy
+        .load_shared(shared.to_string_lossy().into_owned())
+        .unwrap();
+    assert!(policy.is_locked("com.microsoft.rdc.macos".into()));
+    assert_eq!(
+        policy.activate_app("com.apple.Terminal".into()),
+        Some(InputMode::English)
+    );
+    assert_eq!(
+        policy.activate_app("com.kakao.KakaoTalk".into()),
+        Some(InputMode::Korean)
+    );
+
+    // 사용자가 해제한 앱만 예외로 저장되고 공유 목록은 사용자 파일에 복사되지 않는다
+    policy.unlock("com.apple.Terminal".into());
+    policy.save().unwrap();
+    let saved = std::fs::read_to_string(&path).unwrap();
+    assert!(saved.contains("unlocked: [\n    \"com.apple.terminal\","));
+    assert!(!saved.contains("com.microsoft.rdc"));
+}
+
+#[test]
+fn test_invalid_file_is_error() {
+    let path = temp_path("invalid");
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(&path, "{ version: 99 }").unwrap();
+    assert!(ModePolicy::open(path.to_string_lossy().into_owned()).is_err());
+}
+
+#[test]
+fn test_export_is_loadable() {
+    let policy = ModePolicy::new();
+    policy.save_mode("com.apple.Safari".into(), InputMode::Korean);
+    policy.set_restore_on_unlock(false);
+    let path = temp_path("export");
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(&path, policy.export()).unwrap();
+
+    let restored = open(&path);
+    assert_eq!(
+        restored.last_mode("com.apple.Safari".into()),
+        Some(InputMode::Korean)
+    );
+    restored.lock("com.test.app".into(), InputMode::English);
+    // restore_on_unlock = false → 해제하면 한글
+    assert_eq!(restored.unlock("com.test.app".into()), InputMode::Korean);
+}
@@ -0,0 +1,235 @@
+/// 키 입력 기록·재현 통합 테스트
+/// 기록한 호출이 현재 엔진에서 같은 결과로 재현되는지, 달라지면 그 위치를 찾는지 검증한다.
+use std::path::PathBuf;
+
+use ongeul_automata::event::KeyEvent;
+use ongeul_automata::record::{self, Call, Entry};
+use ongeul_automata::toggle::ModifierFlags;
+use ongeul_automata::{HangulEngine, InputMode};
+
+const LAYOUT_2BUL: &str = include_str!("../layouts/2-standard.json5");
+const LAYOUT_390: &str = include_str!("../layouts/3-390.json5");
+
+fn temp_path(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join("ongeul-record");
+    std::fs::create_dir_all(&dir).unwrap();
+    dir.join(format!("{name}.jsonl"))
+}
+
+fn layout_by_id(id: &str) -> Option<String> {
+    [LAYOUT_2BUL, LAYOUT_390]
+        .into_iter()
+        .find(|json| json.contains(&format!("id: \"{id}\"")))
+        .map(str::to_string)
+}
+
+fn read_entries(path: &PathBuf) -> Vec<Entry> {
+    record::parse_record(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+fn record_session(name: &str) -> PathBuf {
+    let path = temp_path(name);
+    let engine = HangulEngine::new();
+    engine.load_layout(LAYOUT_2BUL.to_string()).unwrap();
+    engine
+        .start_recording(path.to_string_lossy().into_owned())
+        .unwrap();
+    engine.set_mode(InputMode::Korean);
+    for key in ["d", "k", "s", "s", "u", "d"] {
+        engine.process_key(key.into());
+    }
+    engine.backspace();
+    engine.toggle_mode();
+    engine.process_key("a".into());
+    engine.toggle_mode();
+    engine.process_key("r".into());
+    engine.flush();
+    engine.stop_recording();
+    // 기록을 멈춘 뒤의 호출은 남지 않는다
+    engine.process_key("k".into());
+    path
+}
+
+#[test]
+fn test_record_and_replay() {
+    let path = record_session("replay");
+    let entries = read_entries(&path);
+    // 시작 시점의 레이아웃과 상태가 먼저 기록된다
+    assert_eq!(
+        entries[0].call,
+        Call::LoadLayout {
+            id: "2-standard".into()
+        }
+    );
+    assert!(matches!(entries[1].call, Call::Restore { .. }));
+    assert_eq!(entries.last().unwrap().call, Call::Flush);
+    assert!(entries.windows(2).all(|w| w[0].t <= w[1].t));
+
+    assert_eq!(record::replay(&entries, layout_by_id).unwrap(), None);
+}
+
+#[test]
+fn test_replay_reports_first_divergence() {
+    let path = record_session("divergence");
+    let mut entries = read_entries(&path);
+    // 기록된 결과를 하나 바꿔 엔진 동작이 달라진 것처럼 만든다
+    let index = entries
+        .iter()
+        .position(|e| e.call == Call::Backspace)
+        .unwrap();
+    entries[index].out.as_mut().unwrap().composing = Some("아".into());
+
+    let divergence = record::replay(&entries, layout_by_id).unwrap().unwrap();
+    assert_eq!(divergence.index, index);
+    assert_eq!(divergence.actual.unwrap().composing.as_deref(), Some("녀"));
+}
+
+#[test]
+fn test_replay_needs_layout() {
+    let path = record_session("missing-layout");
+    let entries = read_entries(&path);
+    assert!(record::replay(&entries, |_| None).is_err());
+}
+
+#[test]
+fn test_excluded_section_is_not_recorded() {
+    let path = temp_path("excluded");
+    let engine = HangulEngine::new();
+    engine.load_layout(LAYOUT_2BUL.to_string()).unwrap();
+    engine.set_mode(InputMode::Korean);
+    engine
+        .start_recording(path.to_string_lossy().into_owned())
+        .unwrap();
+
+    engine.process_key("r".into());
+    engine.set_recording_excluded(true);
+    for key in ["k", "t", "k"] {
+        engine.process_key(key.into());
+    }
+    engine.set_recording_excluded(false);
+    engine.process_key("s".into());
+    engine.stop_recording();
+
+    let entries = read_entries(&path);
+    let keys: Vec<&str> = entries
+        .iter()
+        .filter_map(|e| match &e.call {
+            Call::ProcessKey { key } => Some(key.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(keys, ["r", "s"]);
+    // 제외 구간 뒤 상태는 스냅샷으로 맞추므로 그대로 재현된다
+    assert!(matches!(
+        entries[entries.len() - 2].call,
+        Call::Restore { .. }
+    ));
+    assert_eq!(record::replay(&entries, layout_by_id).unwrap(), None);
+}
+
+#[test]
+fn test_record_handle_event_and_context() {
+    let path = temp_path("event");
+    let engine = HangulEngine::new();
+    engine.load_layout(LAYOUT_2BUL.to_string()).unwrap();
+    engine.set_mode(InputMode::Korean);
+    engine
+        .start_recording(path.to_string_lossy().into_owned())
+        .unwrap();
+
+    let event = KeyEvent {
+        keycode: 0x0A,
+        characters: Some("g".into()),
+        modifiers: ModifierFlags::default(),
+        caps_lock: false,
+    };
+    engine.handle_event(event, false);
+    engine.flush();
+    engine.backspace_with_context("비밀번호 아님 한".into());
+    engine.stop_recording();
+
+    let entries = read_entries(&path);
+    let context = entries
+        .iter()
+        .find_map(|e| match &e.call {
+            Call::BackspaceWithContext { text } => Some(text.as_str()),
+            _ => None,
+        })
+        .unwrap();
+    // 커서 앞 텍스트는 마지막 글자만 남긴다
+    assert_eq!(context, "한");
+    assert_eq!(record::replay(&entries, layout_by_id).unwrap(), None);
+}
+
+#[test]
+fn test_concurrent_calls_replay_in_order() {
+    // 여러 스레드에서 호출해도 기록 순서가 처리 순서와 같아 그대로 재현된다
+    let path = temp_path("concurrent");
+    let engine = HangulEngine::new();
+    engine.load_layout(LAYOUT_2BUL.to_string()).unwrap();
+    engine.set_mode(InputMode::Korean);
+    engine
+        .start_recording(path.to_string_lossy().into_owned())
+        .unwrap();
+    std::thread::scope(|scope| {
+        for keys in ["dkssud", "gktpdy", "rkskek"] {
+            let engine = &engine;
+            scope.spawn(move || {
+                for _ in 0..20 {
+                    for key in keys.chars() {
+                        engine.process_key(key.to_string());
+                    }
+                    engine.backspace();
+                }
+            });
+        }
+    });
+    engine.stop_recording();
+
+    let entries = read_entries(&path);
+    assert_eq!(entries.len(), 2 + 3 * 20 * 7);
+    assert_eq!(record::replay(&entries, layout_by_id).unwrap(), None);
+}
+
+#[test]
+fn test_english_detection_is_recorded() {
+    let words = std::env::temp_dir().join("ongeul-record-words.txt");
+    std::fs::write(&words, "git\ncommit\n").unwrap();
+    let path = temp_path("english-detection");
+    let engine = HangulEngine::new();
+    engine.load_layout(LAYOUT_2BUL.to_string()).unwrap();
+    engine
+        .load_english_word_list(words.to_string_lossy().into_owned())
+        .unwrap();
+    engine.set_english_detection_exceptions(vec!["commit".into()]);
+    engine
+        .start_recording(path.to_string_lossy().into_owned())
+        .unwrap();
+    engine.set_mode(InputMode::Korean);
+    for key in "git ".chars() {
+        engine.process_key(key.to_string());
+    }
+    engine.set_english_detection_threshold(1.0);
+    engine.set_mode(InputMode::Korean);
+    for key in "git commit ".chars() {
+        engine.process_key(key.to_string());
+    }
+    engine.stop_recording();
+
+    let entries = read_entries(&path);
+    assert!(
+        entries
+            .iter()
+            .any(|e| e.out.as_ref().is_some_and(|o| o.suggested_mode.is_some()))
+    );
+    // 단어 목록 파일 없이 재현된다
+    std::fs::remove_file(&words).unwrap();
+    assert_eq!(record::replay(&entries, layout_by_id).unwrap(), None);
+
+    // 단어 목록이 없으면 자동 전환 제안이 달라진다
+    let without: Vec<Entry> = entries
+        .into_iter()
+        .filter(|e| !matches!(e.call, Call::LoadEnglishWords { .. }))
+        .collect();
+    assert!(record::replay(&without, layout_by_id).unwrap().is_some());
+}
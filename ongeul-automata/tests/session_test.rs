@@ -0,0 +1,142 @@
+/// 세션 관리자 통합 테스트
+/// 창마다 조합 상태가 따로 유지되고 레이아웃 설정이 모든 세션에 적용되는지 검증한다.
+use std::sync::Arc;
+
+use ongeul_automata::{InputMode, SessionManager};
+
+const LAYOUT_2BUL: &str = include_str!("../layouts/2-standard.json5");
+const LAYOUT_390: &str = include_str!("../layouts/3-390.json5");
+
+fn create_manager() -> SessionManager {
+    let manager = SessionManager::new();
+    manager.load_layout(LAYOUT_2BUL.to_string()).unwrap();
+    manager
+}
+
+fn korean_session(manager: &SessionManager, id: &str) -> Arc<ongeul_automata::HangulEngine> {
+    let session = manager.session(id.to_string());
+    session.set_mode(InputMode::Korean);
+    session
+}
+
+#[test]
+fn test_sessions_keep_separate_composition() {
+    let manager = create_manager();
+    let a = korean_session(&manager, "window-a");
+    let b = korean_session(&manager, "window-b");
+
+    // A에서 "가"를 조합하다가 B로 이동
+    a.process_key("r".into());
+    assert_eq!(a.process_key("k".into()).composing.as_deref(), Some("가"));
+    let suspended = manager.suspend("window-a".into()).unwrap();
+    assert_eq!(suspended.composing, None);
+    assert_eq!(suspended.committed, None);
+
+    // B의 입력은 A의 조합과 섞이지 않는다
+    assert_eq!(b.process_key("s".into()).composing.as_deref(), Some("ㄴ"));
+    manager.suspend("window-b".into());
+
+    // A로 돌아오면 "가"를 이어서 조합한다
+    let resumed = manager.resume("window-a".into());
+    assert_eq!(resumed.composing.as_deref(), Some("가"));
+    assert_eq!(a.process_key("s".into()).composing.as_deref(), Some("간"));
+    assert_eq!(
+        manager.resume("window-b".into()).composing.as_deref(),
+        Some("ㄴ")
+    );
+}
+
+#[test]
+fn test_session_returns_same_engine() {
+    let manager = create_manager();
+    let first = manager.session("editor".into());
+    let second = manager.session("editor".into());
+    assert!(Arc::ptr_eq(&first, &second));
+    assert_eq!(manager.session_count(), 1);
+
+    assert!(manager.drop_session("editor".into()));
+    assert!(!manager.drop_session("editor".into()));
+    assert_eq!(manager.session_count(), 0);
+    assert!(manager.suspend("editor".into()).is_none());
+}
+
+#[test]
+fn test_resume_unknown_session_creates_it() {
+    let manager = create_manager();
+    let resumed = manager.resume("new".into());
+    assert!(resumed.handled);
+    assert_eq!(resumed.composing, None);
+    assert_eq!(manager.session_count(), 1);
+}
+
+#[test]
+fn test_suspend_in_direct_commit_mode_commits() {
+    let manager = create_manager();
+    let session = korean_session(&manager, "terminal");
+    session.set_direct_commit(true);
+    session.process_key("r".into());
+    session.process_key("k".into());
+
+    // "가"는 이미 문서에 있으므로 더 보낼 것이 없고 조합은 끝난다
+    let suspended = manager.suspend("terminal".into()).unwrap();
+    assert_eq!(suspended.committed, None);
+    assert_eq!(suspended.delete_before, 0);
+    assert_eq!(manager.resume("terminal".into()).composing, None);
+    assert_eq!(
+        session.process_key("s".into()).committed.as_deref(),
+        Some("ㄴ")
+    );
+}
+
+#[test]
+fn test_layout_change_applies_to_all_sessions() {
+    let manager = create_manager();
+    let a = korean_session(&manager, "a");
+    a.process_key("r".into());
+
+    manager.load_layout(LAYOUT_390.to_string()).unwrap();
+    // 배열이 바뀌면 조합은 폐기되고, 이후 세션도 새 배열로 만들어진다
+    assert_eq!(a.resume().composing, None);
+    let b = korean_session(&manager, "b");
+    for session in [&a, &b] {
+        // 390 자판: k → ㄱ 초성
+        assert_eq!(
+            session.process_key("k".into()).composing.as_deref(),
+            Some("ㄱ")
+        );
+        session.flush();
+    }
+}
+
+#[test]
+fn test_session_mode_is_independent() {
+    let manager = create_manager();
+    let a = korean_session(&manager, "a");
+    let b = manager.session("b".into());
+    assert_eq!(a.get_mode(), InputMode::Korean);
+    assert_eq!(b.get_mode(), InputMode::English);
+
+    // 레이아웃 설정은 세션 모드를 바꾸지 않는다
+    manager.load_layout(LAYOUT_2BUL.to_string()).unwrap();
+    assert_eq!(a.get_mode(), InputMode::Korean);
+}
+
+#[test]
+fn test_invalid_layout_keeps_sessions() {
+    let manager = create_manager();
+    let a = korean_session(&manager, "a");
+    a.process_key("r".into());
+    assert!(manager.load_layout("{ invalid".into()).is_err());
+    assert_eq!(a.resume().composing.as_deref(), Some("ㄱ"));
+}
+
+#[test]
+fn test_builtin_layout_applies_to_all_sessions() {
+    let manager = create_manager();
+    let a = korean_session(&manager, "a");
+    manager.load_builtin_layout("3-390".into()).unwrap();
+    // 390 자판: k → ㄱ 초성
+    assert_eq!(a.process_key("k".into()).composing.as_deref(), Some("ㄱ"));
+    assert!(manager.load_builtin_layout("nope".into()).is_err());
+    assert_eq!(a.resume().composing.as_deref(), Some("ㄱ"));
+}
@@ -0,0 +1,105 @@
+/// 조합 상태 스냅샷 통합 테스트
+/// 새 엔진(재시작된 입력기)에서 조합을 이어 갈 수 있는지 검증한다.
+use ongeul_automata::{EngineError, HangulEngine, InputMode};
+
+const LAYOUT_2BUL: &str = include_str!("../layouts/2-standard.json5");
+const LAYOUT_390: &str = include_str!("../layouts/3-390.json5");
+const LAYOUT_SHIN: &str = include_str!("../layouts/3-shin.json5");
+
+fn create_engine(layout: &str) -> HangulEngine {
+    let engine = HangulEngine::new();
+    engine.load_layout(layout.to_string()).unwrap();
+    engine.set_mode(InputMode::Korean);
+    engine
+}
+
+fn type_keys(engine: &HangulEngine, keys: &str) {
+    for ch in keys.chars() {
+        engine.process_key(ch.to_string());
+    }
+}
+
+#[test]
+fn test_restore_continues_composition() {
+    let engine = create_engine(LAYOUT_2BUL);
+    // "닭" (겹받침 ㄺ)
+    type_keys(&engine, "ekfr");
+    let snapshot = engine.snapshot();
+
+    let restarted = create_engine(LAYOUT_2BUL);
+    restarted.set_mode(InputMode::English);
+    let result = restarted.restore(snapshot).unwrap();
+    assert_eq!(result.composing.as_deref(), Some("닭"));
+    assert_eq!(restarted.get_mode(), InputMode::Korean);
+
+    // 백스페이스 복원 정보도 되살아난다: 닭 → 달
+    assert_eq!(restarted.backspace().composing.as_deref(), Some("달"));
+    // 겹받침 분리: 달 + ㄱ + ㅏ → 달가
+    let result = restarted.process_key("r".into());
+    assert_eq!(result.composing.as_deref(), Some("닭"));
+    let result = restarted.process_key("k".into());
+    assert_eq!(result.committed.as_deref(), Some("달"));
+    assert_eq!(result.composing.as_deref(), Some("가"));
+}
+
+#[test]
+fn test_snapshot_is_versioned_json() {
+    let engine = create_engine(LAYOUT_390);
+    type_keys(&engine, "kf");
+    let snapshot = engine.snapshot();
+    assert!(snapshot.contains("\"version\":1"), "{snapshot}");
+    assert!(snapshot.contains("\"kind\":\"jaso\""), "{snapshot}");
+
+    let restored = create_engine(LAYOUT_390);
+    let result = restored.restore(snapshot).unwrap();
+    assert_eq!(result.composing.as_deref(), Some("가"));
+}
+
+#[test]
+fn test_shin_snapshot_round_trip() {
+    let engine = create_engine(LAYOUT_SHIN);
+    type_keys(&engine, "k");
+    let snapshot = engine.snapshot();
+
+    let restored = create_engine(LAYOUT_SHIN);
+    restored.restore(snapshot.clone()).unwrap();
+    assert_eq!(restored.snapshot(), snapshot);
+}
+
+#[test]
+fn test_restore_rejects_other_layout() {
+    let engine = create_engine(LAYOUT_2BUL);
+    type_keys(&engine, "r");
+    let snapshot = engine.snapshot();
+
+    let other = create_engine(LAYOUT_390);
+    type_keys(&other, "k");
+    let err = other.restore(snapshot).unwrap_err();
+    assert!(matches!(err, EngineError::SnapshotError { .. }));
+    // 실패하면 기존 조합을 그대로 둔다
+    assert_eq!(other.resume().composing.as_deref(), Some("ㄱ"));
+}
+
+#[test]
+fn test_restore_rejects_bad_input() {
+    let engine = create_engine(LAYOUT_2BUL);
+    assert!(engine.restore("not json".into()).is_err());
+    let future = engine.snapshot().replace("\"version\":1", "\"version\":2");
+    assert!(engine.restore(future).is_err());
+}
+
+#[test]
+fn test_direct_commit_snapshot() {
+    let engine = create_engine(LAYOUT_2BUL);
+    engine.set_direct_commit(true);
+    type_keys(&engine, "rk");
+    let snapshot = engine.snapshot();
+
+    // 직접 확정 모드의 "가"는 이미 문서에 있으므로 바뀐 부분만 다시 넣는다
+    let restored = create_engine(LAYOUT_2BUL);
+    let result = restored.restore(snapshot).unwrap();
+    assert_eq!(result.composing, None);
+    let result = restored.process_key("s".into());
+    assert_eq!(result.delete_before, 1);
+    assert_eq!(result.committed.as_deref(), Some("간"));
+}
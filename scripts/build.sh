@@ -136,11 +136,6 @@ cp "$PROJECT_ROOT/OngeulApp/Resources/Info.plist" "$APP_CONTENTS/Info.plist"
 # Resources 디렉토리 생성 및 리소스 복사
 mkdir -p "$APP_CONTENTS/Resources"
 
-# 레이아웃 파일 복사
-cp "$RUST_CRATE/layouts/2-standard.json5" "$APP_CONTENTS/Resources/"
-cp "$RUST_CRATE/layouts/3-390.json5" "$APP_CONTENTS/Resources/"
-cp "$RUST_CRATE/layouts/3-final.json5" "$APP_CONTENTS/Resources/"
-
 # 아이콘 복사
 cp "$PROJECT_ROOT/OngeulApp/Resources/icon_ko.tiff" "$APP_CONTENTS/Resources/"
 cp "$PROJECT_ROOT/OngeulApp/Resources/icon_en.tiff" "$APP_CONTENTS/Resources/"